@@ -0,0 +1,88 @@
+use parser::{integer, lines, parse_all, ParseError};
+use solution::{ParseResult, PartResult, Solution};
+use std::collections::HashSet;
+use std::fmt::Display;
+
+pub fn parse(input: &str) -> Result<Vec<i64>, ParseError> {
+    parse_all(input, lines(integer))
+}
+
+pub fn part1(input: &str) -> Result<i64, ParseError> {
+    Ok(parse(input)?.iter().sum())
+}
+
+pub fn part2(input: &str) -> Result<i64, ParseError> {
+    Ok(first_repeat(&parse(input)?))
+}
+
+// The first running total to come up twice, going round the list as often as it takes
+pub fn first_repeat(numbers: &[i64]) -> i64 {
+    let mut frequencies = HashSet::new();
+    let mut sum = 0;
+    frequencies.insert(sum);
+
+    for val in numbers.iter().cycle() {
+        sum += val;
+        if !frequencies.insert(sum) {
+            break;
+        }
+    }
+    sum
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<i64>;
+
+    fn parse(input: &str) -> ParseResult<Vec<i64>> {
+        Ok(parse(input)?)
+    }
+
+    fn part1(numbers: &Vec<i64>) -> PartResult<impl Display> {
+        Ok(numbers.iter().sum::<i64>())
+    }
+
+    fn part2(numbers: &Vec<i64>) -> PartResult<impl Display> {
+        Ok(first_repeat(numbers))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    #[test]
+    fn example1() {
+        let input = input::read!("test_input.txt").unwrap();
+        assert_eq!(super::part1(&input).unwrap(), 4);
+        assert_eq!(super::part1("+1\n+1\n-2").unwrap(), 0);
+    }
+
+    #[test]
+    fn part1() {
+        let input = input::read!("input.txt").unwrap();
+        assert_eq!(super::part1(&input).unwrap(), 595);
+    }
+
+    #[test]
+    fn example2() {
+        let input = input::read!("test_input.txt").unwrap();
+        assert_eq!(super::part2(&input).unwrap(), 10);
+        assert_eq!(super::part2("+1\n-1").unwrap(), 0);
+        assert_eq!(super::part2("-6\n+3\n+8\n+5\n-6").unwrap(), 5);
+        assert_eq!(super::part2("+7\n+7\n-2\n-7\n-4").unwrap(), 14);
+    }
+
+    #[test]
+    fn part2() {
+        let input = input::read!("input.txt").unwrap();
+        assert_eq!(super::part2(&input).unwrap(), 80598);
+    }
+
+    #[test]
+    fn bad_change() {
+        let error = super::parse("+1\n-2\n+three").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 1));
+        assert_eq!(error.expected, "integer");
+    }
+}
@@ -0,0 +1,239 @@
+use parser::nom::{
+    bytes::complete::tag,
+    character::complete::{char, space0},
+    combinator::map,
+    sequence::{delimited, preceded, separated_pair},
+};
+use parser::{integer, lines, parse_all, ParseError, Parsed};
+use solution::{ParseResult, PartResult, Solution};
+use std::fmt::Display;
+
+#[derive(Debug, Clone)]
+pub struct Observation {
+    x: i32,
+    y: i32,
+    dx: i32,
+    dy: i32,
+}
+
+impl Observation {
+    fn new(x: i32, y: i32, dx: i32, dy: i32) -> Self {
+        Observation { x, y, dx, dy }
+    }
+}
+
+fn predict(observations: &[Observation], t: i32) -> Vec<Observation> {
+    observations.iter().fold(Vec::new(), |mut v, observation| {
+        v.push(Observation::new(
+            (observation.x) + observation.dx * t,
+            (observation.y) + observation.dy * t,
+            observation.dx,
+            observation.dy,
+        ));
+
+        v
+    })
+}
+
+// "<-3, 11>", with the numbers padded out to line up
+fn vector(input: &str) -> Parsed<'_, (i32, i32)> {
+    let padded = |input| preceded(space0, integer)(input);
+    delimited(
+        char('<'),
+        separated_pair(padded, char(','), padded),
+        char('>'),
+    )(input)
+}
+
+// "position=< 9,  1> velocity=< 0,  2>"
+fn observation(input: &str) -> Parsed<'_, Observation> {
+    map(
+        separated_pair(
+            preceded(tag("position="), vector),
+            char(' '),
+            preceded(tag("velocity="), vector),
+        ),
+        |((x, y), (dx, dy))| Observation::new(x, y, dx, dy),
+    )(input)
+}
+
+pub fn parse(input: &str) -> Result<Vec<Observation>, ParseError> {
+    parse_all(input, lines(observation))
+}
+
+// Width plus height of the box around the points at time t.  The width is the
+// furthest point right minus the furthest left, each of which is the max (or min) of
+// straight lines, so it only ever falls and then rises; the same goes for the height
+// and so for their sum.
+fn spread(observations: &[Observation], t: i32) -> i64 {
+    let extent = |position: &dyn Fn(&Observation) -> i64| {
+        let max = observations.iter().map(position).max().unwrap();
+        let min = observations.iter().map(position).min().unwrap();
+        max - min
+    };
+
+    let t = t as i64;
+    extent(&|v| v.x as i64 + v.dx as i64 * t) + extent(&|v| v.y as i64 + v.dy as i64 * t)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Message {
+    pub time: i32,
+    pub points: Vec<(i32, i32)>,
+}
+
+impl Message {
+    // The points drawn with '#', one line per row
+    pub fn render(&self) -> String {
+        let min_x = self.points.iter().map(|p| p.0).min().unwrap();
+        let max_x = self.points.iter().map(|p| p.0).max().unwrap();
+        let min_y = self.points.iter().map(|p| p.1).min().unwrap();
+        let max_y = self.points.iter().map(|p| p.1).max().unwrap();
+
+        (min_y..=max_y)
+            .map(|y| {
+                (min_x..=max_x)
+                    .map(|x| {
+                        if self.points.contains(&(x, y)) {
+                            '#'
+                        } else {
+                            '.'
+                        }
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    pub fn read(&self) -> String {
+        ocr::read_points(self.points.iter().cloned())
+    }
+}
+
+// The points close in on each other until the message appears, then drift apart
+// again, so the message is at the second the box around them is smallest.  That
+// second is found with a ternary search, after doubling the range until the box
+// has started growing again.
+pub fn converge(observations: &[Observation]) -> Message {
+    let spread = |t| spread(observations, t);
+
+    let mut hi = 1;
+    while spread(hi) > spread(hi + 1) {
+        hi *= 2;
+    }
+
+    let mut lo = 0;
+    while hi - lo > 2 {
+        let m1 = lo + (hi - lo) / 3;
+        let m2 = hi - (hi - lo) / 3;
+        if spread(m1) <= spread(m2) {
+            hi = m2;
+        } else {
+            lo = m1;
+        }
+    }
+    let time = (lo..=hi).min_by_key(|&t| spread(t)).unwrap();
+
+    Message {
+        time,
+        points: predict(observations, time)
+            .iter()
+            .map(|v| (v.x, v.y))
+            .collect(),
+    }
+}
+
+pub fn part1(input: &str) -> Result<String, ParseError> {
+    Ok(converge(&parse(input)?).read())
+}
+
+pub fn part2(input: &str) -> Result<i32, ParseError> {
+    Ok(converge(&parse(input)?).time)
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Vec<Observation>;
+
+    fn parse(input: &str) -> ParseResult<Vec<Observation>> {
+        Ok(parse(input)?)
+    }
+
+    fn part1(observations: &Vec<Observation>) -> PartResult<impl Display> {
+        Ok(converge(observations).read())
+    }
+
+    fn part2(observations: &Vec<Observation>) -> PartResult<impl Display> {
+        Ok(converge(observations).time)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example1() {
+        let input = input::read!("test_input.txt").unwrap();
+        assert_eq!(
+            converge(&parse(&input).unwrap()).render(),
+            "#...#..###
+#...#...#.
+#...#...#.
+#####...#.
+#...#...#.
+#...#...#.
+#...#...#.
+#...#..###"
+        );
+    }
+
+    #[test]
+    fn part1() {
+        let input = input::read!("input.txt").unwrap();
+        assert_eq!(super::part1(&input).unwrap(), "GEJKHGHZ");
+    }
+
+    #[test]
+    fn example2() {
+        let input = input::read!("test_input.txt").unwrap();
+        assert_eq!(super::part2(&input).unwrap(), 3);
+    }
+
+    #[test]
+    fn part2() {
+        let input = input::read!("input.txt").unwrap();
+        assert_eq!(super::part2(&input).unwrap(), 10681);
+    }
+
+    #[test]
+    fn search_matches_scan() {
+        for filename in ["test_input.txt", "input.txt"].iter() {
+            let observations = parse(&input::read!(filename).unwrap()).unwrap();
+            let message = converge(&observations);
+
+            let scanned = (0..20000)
+                .min_by_key(|&t| spread(&observations, t))
+                .unwrap();
+            assert_eq!(message.time, scanned);
+            assert_eq!(message.points.len(), observations.len());
+        }
+
+        // already converged at the start
+        let observations =
+            parse("position=< 0,  0> velocity=< 1,  0>\nposition=< 1,  0> velocity=<-1,  0>")
+                .unwrap();
+        assert_eq!(converge(&observations).time, 0);
+    }
+
+    #[test]
+    fn bad_observation() {
+        let error =
+            parse("position=< 9,  1> velocity=< 0,  2>\nposition=< 7,  0> velocity=<-1  0>")
+                .unwrap_err();
+        assert_eq!((error.line, error.column), (2, 31));
+        assert_eq!(error.found, "  0>");
+    }
+}
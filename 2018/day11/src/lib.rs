@@ -0,0 +1,185 @@
+use parser::{integer, parse_all, ParseError};
+use solution::{ParseResult, PartResult, Solution};
+use std::fmt::Display;
+fn compute_coord(x: i32, y: i32, serial: i32) -> i32 {
+    let rack_id = x + 10;
+    let mut power_level = rack_id * y;
+    power_level += serial;
+    power_level *= rack_id;
+    (power_level / 100) % 10 - 5
+}
+
+// A square grid of fuel cells from 1,1 to size,size, stored as a summed-area table:
+// each entry is the total power of the cells above and to the left of it, inclusive,
+// with a row and column of zeros in front
+pub struct PowerGrid {
+    size: usize,
+    table: Vec<Vec<i32>>,
+}
+
+impl PowerGrid {
+    pub fn new(serial: i32, size: usize) -> Self {
+        let mut table = vec![vec![0; size + 1]; size + 1];
+        for y in 1..=size {
+            for x in 1..=size {
+                table[y][x] =
+                    compute_coord(x as i32, y as i32, serial) + table[y - 1][x] + table[y][x - 1]
+                        - table[y - 1][x - 1];
+            }
+        }
+        PowerGrid { size, table }
+    }
+
+    // Total power of the n by n square with its top-left corner at x,y
+    pub fn square(&self, x: usize, y: usize, n: usize) -> i32 {
+        let (right, bottom) = (x + n - 1, y + n - 1);
+        self.table[bottom][right] - self.table[y - 1][right] - self.table[bottom][x - 1]
+            + self.table[y - 1][x - 1]
+    }
+
+    // (x, y, size, power) of the best n by n square, the first in reading order on a tie
+    pub fn best_square(&self, n: usize) -> (usize, usize, usize, i32) {
+        let mut best = (0, 0, n, i32::MIN);
+        for y in 1..=self.size + 1 - n {
+            for x in 1..=self.size + 1 - n {
+                let power = self.square(x, y, n);
+                if power > best.3 {
+                    best = (x, y, n, power);
+                }
+            }
+        }
+        best
+    }
+
+    // The best square of any size, the smallest on a tie
+    pub fn best_any(&self) -> (usize, usize, usize, i32) {
+        (1..=self.size)
+            .map(|n| self.best_square(n))
+            .fold((0, 0, 0, i32::MIN), |best, square| {
+                if square.3 > best.3 {
+                    square
+                } else {
+                    best
+                }
+            })
+    }
+}
+
+fn parse(input: &str) -> Result<i32, ParseError> {
+    parse_all(input, integer)
+}
+
+pub fn best_three(serial: i32) -> String {
+    let (x, y, _, _) = PowerGrid::new(serial, 300).best_square(3);
+    format!("{},{}", x, y)
+}
+
+pub fn best_any(serial: i32) -> String {
+    let (x, y, n, _) = PowerGrid::new(serial, 300).best_any();
+    format!("{},{},{}", x, y, n)
+}
+
+pub fn part1(input: &str) -> Result<String, ParseError> {
+    Ok(best_three(parse(input)?))
+}
+
+pub fn part2(input: &str) -> Result<String, ParseError> {
+    Ok(best_any(parse(input)?))
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = i32;
+
+    fn parse(input: &str) -> ParseResult<i32> {
+        Ok(parse(input)?)
+    }
+
+    fn part1(&serial: &i32) -> PartResult<impl Display> {
+        Ok(best_three(serial))
+    }
+
+    fn part2(&serial: &i32) -> PartResult<impl Display> {
+        Ok(best_any(serial))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn power_levels() {
+        assert_eq!(compute_coord(3, 5, 8), 4);
+        assert_eq!(compute_coord(122, 79, 57), -5);
+        assert_eq!(compute_coord(217, 196, 39), 0);
+        assert_eq!(compute_coord(101, 153, 71), 4);
+    }
+
+    #[test]
+    fn squares() {
+        let grid = PowerGrid::new(18, 300);
+        assert_eq!(grid.square(33, 45, 3), 29);
+        assert_eq!(grid.best_square(3), (33, 45, 3, 29));
+        assert_eq!(grid.best_any(), (90, 269, 16, 113));
+
+        let grid = PowerGrid::new(42, 300);
+        assert_eq!(grid.square(21, 61, 3), 30);
+        assert_eq!(grid.best_any(), (232, 251, 12, 119));
+    }
+
+    #[test]
+    fn small_grid() {
+        // every square checked against adding up its cells one by one
+        let grid = PowerGrid::new(7, 12);
+        for n in 1..=12 {
+            for y in 1..=13 - n {
+                for x in 1..=13 - n {
+                    let total: i32 = (y..y + n)
+                        .flat_map(|cy| {
+                            (x..x + n).map(move |cx| compute_coord(cx as i32, cy as i32, 7))
+                        })
+                        .sum();
+                    assert_eq!(grid.square(x, y, n), total);
+                }
+            }
+        }
+
+        let best = grid.best_any();
+        assert_eq!(grid.square(best.0, best.1, best.2), best.3);
+        assert_eq!(grid.best_square(12), (1, 1, 12, grid.square(1, 1, 12)));
+    }
+
+    #[test]
+    fn example1() {
+        assert_eq!(super::part1("18").unwrap(), "33,45");
+        assert_eq!(super::part1("42").unwrap(), "21,61");
+    }
+
+    #[test]
+    fn part1() {
+        let input = input::read!("input.txt").unwrap();
+        assert_eq!(super::part1(&input).unwrap(), "21,13");
+    }
+
+    #[test]
+    fn example2() {
+        assert_eq!(super::part2("18").unwrap(), "90,269,16");
+        assert_eq!(super::part2("42").unwrap(), "232,251,12");
+    }
+
+    #[test]
+    fn part2() {
+        let input = input::read!("input.txt").unwrap();
+        assert_eq!(super::part2(&input).unwrap(), "235,268,13");
+    }
+
+    #[test]
+    fn bad_serial() {
+        assert_eq!(
+            parse("18 42").unwrap_err().to_string(),
+            "line 1, column 4: expected end of input, found \"42\""
+        );
+    }
+}
@@ -0,0 +1,373 @@
+use parser::nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{line_ending, one_of},
+    combinator::{eof, map, peek, verify},
+    error::context,
+    multi::many1,
+    sequence::{preceded, separated_pair, terminated},
+};
+use parser::{blank_lines, lines, parse_all, ParseError, Parsed};
+use solution::{ParseResult, PartResult, Solution};
+use std::collections::{BTreeSet, HashMap};
+use std::error::Error;
+use std::fmt::{self, Display};
+
+// The pots with plants in, which can run off in either direction
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pots {
+    pub plants: BTreeSet<i64>,
+}
+
+// Indexed by the five pots around a pot read as a binary number, plant = 1, and
+// whether that pot has a plant next generation.  ##.#. => # is rules[0b11010].
+#[derive(Debug)]
+pub struct Rules {
+    grows: [bool; 32],
+}
+
+// Whether a pot has a plant in
+fn pot(input: &str) -> Parsed<'_, bool> {
+    context("# or .", map(one_of("#."), |c| c == '#'))(input)
+}
+
+// "initial state: #..#.#..##"
+fn initial_state(input: &str) -> Parsed<'_, Pots> {
+    let pots = terminated(many1(pot), context("# or .", peek(alt((line_ending, eof)))));
+    let plants = map(pots, |pots| {
+        pots.iter()
+            .enumerate()
+            .filter(|&(_, &plant)| plant)
+            .map(|(i, _)| i as i64)
+            .collect()
+    });
+    map(
+        preceded(context("initial state", tag("initial state: ")), plants),
+        |plants| Pots { plants },
+    )(input)
+}
+
+// "##.#. => #", as the index into the rules and whether a plant grows
+fn rule(input: &str) -> Parsed<'_, (usize, bool)> {
+    let five = verify(many1(pot), |pots: &Vec<bool>| pots.len() == 5);
+    let from = map(context("five pots", five), |pots| {
+        pots.iter().fold(0, |p, &plant| p * 2 + plant as usize)
+    });
+    let rule = separated_pair(from, context("\" => \"", tag(" => ")), pot);
+    // Plants growing from nothing would fill every pot
+    context(
+        "a rule that doesn't grow plants in empty pots",
+        verify(rule, |&(from, grows)| from != 0 || !grows),
+    )(input)
+}
+
+pub fn parse(input: &str) -> Result<(Pots, Rules), ParseError> {
+    let (pots, rules) = parse_all(
+        input,
+        separated_pair(initial_state, blank_lines, lines(rule)),
+    )?;
+
+    let mut grows = [false; 32];
+    for (from, to) in rules {
+        grows[from] = to;
+    }
+    Ok((pots, Rules { grows }))
+}
+
+impl Pots {
+    pub fn next_generation(&self, rules: &Rules) -> Pots {
+        let (first, last) = match (self.plants.iter().next(), self.plants.iter().next_back()) {
+            (Some(&first), Some(&last)) => (first, last),
+            _ => return self.clone(),
+        };
+
+        // Only pots within two of a plant can have one next generation
+        let plants = (first - 2..=last + 2)
+            .filter(|&pot| {
+                let around =
+                    (pot - 2..=pot + 2).fold(0, |p, i| p * 2 + self.plants.contains(&i) as usize);
+                rules.grows[around]
+            })
+            .collect();
+        Pots { plants }
+    }
+
+    pub fn score(&self) -> i64 {
+        self.plants.iter().sum()
+    }
+
+    // The plants lined up from pot 0, to compare patterns wherever they are
+    fn shape(&self) -> Vec<i64> {
+        let first = self.plants.iter().next().cloned().unwrap_or(0);
+        self.plants.iter().map(|pot| pot - first).collect()
+    }
+
+    fn first(&self) -> i64 {
+        self.plants.iter().next().cloned().unwrap_or(0)
+    }
+}
+
+// How many generations to give the pattern to settle into a cycle
+pub const SETTLE_LIMIT: u64 = 1000;
+
+// When the pattern settles into a cycle, coming back to the same shape every
+// `period` generations having moved `shift` pots.  A glider has a period of 1, and
+// a pattern that keeps repeating where it is has a shift of 0.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cycle {
+    pub generation: u64,
+    pub period: u64,
+    pub shift: i64,
+    pub pots: Pots,
+}
+
+// The pattern hadn't repeated a shape within that many generations
+#[derive(Debug, Clone, PartialEq)]
+pub struct Unsettled {
+    pub generations: u64,
+}
+
+impl fmt::Display for Unsettled {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "the plants don't settle into a cycle within {} generations",
+            self.generations
+        )
+    }
+}
+
+impl Error for Unsettled {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PotsError {
+    Parse(ParseError),
+    Unsettled(Unsettled),
+}
+
+impl fmt::Display for PotsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PotsError::Parse(e) => write!(f, "{}", e),
+            PotsError::Unsettled(unsettled) => write!(f, "{}", unsettled),
+        }
+    }
+}
+
+impl Error for PotsError {}
+
+impl From<ParseError> for PotsError {
+    fn from(e: ParseError) -> Self {
+        PotsError::Parse(e)
+    }
+}
+
+impl From<Unsettled> for PotsError {
+    fn from(unsettled: Unsettled) -> Self {
+        PotsError::Unsettled(unsettled)
+    }
+}
+
+// Run until a generation has the same shape as an earlier one, or give up after `limit`
+pub fn find_cycle(pots: &Pots, rules: &Rules, limit: u64) -> Option<Cycle> {
+    let mut pots = pots.clone();
+    let mut seen = HashMap::new();
+    seen.insert(pots.shape(), (0, pots.first()));
+
+    for generation in 1..=limit {
+        pots = pots.next_generation(rules);
+        let shape = pots.shape();
+        if let Some(&(before, first)) = seen.get(&shape) {
+            return Some(Cycle {
+                generation,
+                period: generation - before,
+                shift: pots.first() - first,
+                pots,
+            });
+        }
+        seen.insert(shape, (generation, pots.first()));
+    }
+    None
+}
+
+pub fn simulate(pots: &Pots, rules: &Rules, generations: u64) -> Pots {
+    (0..generations).fold(pots.clone(), |pots, _| pots.next_generation(rules))
+}
+
+// The sum of the pots with plants in after that many generations.  Once the pattern
+// is in a cycle every plant moves the same distance each time round, so only the
+// part of a cycle left over at the end is simulated.
+pub fn score_after(pots: &Pots, rules: &Rules, generations: u64) -> Result<i64, Unsettled> {
+    if generations <= SETTLE_LIMIT {
+        return Ok(simulate(pots, rules, generations).score());
+    }
+
+    let cycle = find_cycle(pots, rules, SETTLE_LIMIT).ok_or(Unsettled {
+        generations: SETTLE_LIMIT,
+    })?;
+    let remaining = generations - cycle.generation;
+    let pots = simulate(&cycle.pots, rules, remaining % cycle.period);
+    let shift = (remaining / cycle.period) as i64 * cycle.shift;
+    Ok(pots.score() + shift * pots.plants.len() as i64)
+}
+
+pub fn part1(input: &str) -> Result<i64, ParseError> {
+    let (pots, rules) = parse(input)?;
+    Ok(simulate(&pots, &rules, 20).score())
+}
+
+pub fn part2(input: &str) -> Result<i64, PotsError> {
+    let (pots, rules) = parse(input)?;
+    Ok(score_after(&pots, &rules, 50_000_000_000)?)
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = (Pots, Rules);
+
+    fn parse(input: &str) -> ParseResult<(Pots, Rules)> {
+        Ok(parse(input)?)
+    }
+
+    fn part1((pots, rules): &(Pots, Rules)) -> PartResult<impl Display> {
+        Ok(simulate(pots, rules, 20).score())
+    }
+
+    fn part2((pots, rules): &(Pots, Rules)) -> PartResult<impl Display> {
+        Ok(score_after(pots, rules, 50_000_000_000)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example1() {
+        let input = input::read!("test_input.txt").unwrap();
+        let (mut pots, rules) = parse(&input).unwrap();
+
+        for _ in 0..20 {
+            pots = pots.next_generation(&rules);
+        }
+        let expected: BTreeSet<i64> = "#....##....#####...#######....#.#..##."
+            .chars()
+            .enumerate()
+            .filter(|&(_, c)| c == '#')
+            .map(|(i, _)| i as i64 - 2)
+            .collect();
+        assert_eq!(pots.plants, expected);
+
+        assert_eq!(super::part1(&input), Ok(325));
+    }
+
+    #[test]
+    fn part1() {
+        let input = input::read!("input.txt").unwrap();
+        assert_eq!(super::part1(&input), Ok(3410));
+    }
+
+    #[test]
+    fn cycles() {
+        // a pair of plants that moves one pot right every generation
+        let glider = "initial state: ##\n\n.##.. => #\n##... => #";
+        let (pots, rules) = parse(glider).unwrap();
+        let cycle = find_cycle(&pots, &rules, 10).unwrap();
+        assert_eq!((cycle.generation, cycle.period, cycle.shift), (1, 1, 1));
+        assert_eq!(score_after(&pots, &rules, 1000), Ok(2001));
+        assert_eq!(score_after(&pots, &rules, 1_000_000), Ok(2_000_001));
+
+        // plants that die out, leaving an empty pattern that doesn't go anywhere
+        let dying = "initial state: #.#\n\n..#.. => .";
+        let (pots, rules) = parse(dying).unwrap();
+        let cycle = find_cycle(&pots, &rules, 10).unwrap();
+        assert_eq!((cycle.generation, cycle.period, cycle.shift), (2, 1, 0));
+        assert_eq!(score_after(&pots, &rules, 1_000_000), Ok(0));
+
+        // a plant that flickers next to one that stays put
+        let flicker = "initial state: ##\n\n..#.. => #\n..##. => #\n.#... => #";
+        let (pots, rules) = parse(flicker).unwrap();
+        let cycle = find_cycle(&pots, &rules, 10).unwrap();
+        assert_eq!((cycle.generation, cycle.period, cycle.shift), (2, 2, 0));
+        assert_eq!(score_after(&pots, &rules, 1_000_000), Ok(1));
+        assert_eq!(score_after(&pots, &rules, 1_000_001), Ok(0));
+
+        // one plant and two plants in turn, four pots further on each time round
+        let hopping = "initial state: #\n\n..#.. => #\n#.... => #";
+        let (pots, rules) = parse(hopping).unwrap();
+        let cycle = find_cycle(&pots, &rules, 10).unwrap();
+        assert_eq!((cycle.period, cycle.shift), (2, 4));
+        for generations in SETTLE_LIMIT + 1..SETTLE_LIMIT + 4 {
+            assert_eq!(
+                score_after(&pots, &rules, generations),
+                Ok(simulate(&pots, &rules, generations).score())
+            );
+        }
+
+        // a patch that spreads a pot both ways each generation never repeats
+        let spreading: String = (0..32)
+            .filter(|p| p & 0b01110 != 0)
+            .map(|p| {
+                format!("{:05b} => #\n", p)
+                    .replace('0', ".")
+                    .replace('1', "#")
+            })
+            .collect();
+        let input = format!("initial state: #\n\n{}", spreading);
+        let (pots, rules) = parse(&input).unwrap();
+        assert_eq!(find_cycle(&pots, &rules, 50), None);
+        assert_eq!(score_after(&pots, &rules, 3), Ok(0));
+        assert_eq!(
+            score_after(&pots, &rules, 50_000_000_000),
+            Err(Unsettled {
+                generations: SETTLE_LIMIT
+            })
+        );
+        assert_eq!(
+            Day12::part2(&parse(&input).unwrap())
+                .err()
+                .unwrap()
+                .to_string(),
+            "the plants don't settle into a cycle within 1000 generations"
+        );
+
+        let input = input::read!("input.txt").unwrap();
+        let (pots, rules) = parse(&input).unwrap();
+        let cycle = find_cycle(&pots, &rules, SETTLE_LIMIT).unwrap();
+        assert_eq!((cycle.period, cycle.shift), (1, 1));
+        assert_eq!(cycle.pots.plants.len(), 80);
+    }
+
+    #[test]
+    fn part2() {
+        let input = input::read!("input.txt").unwrap();
+        assert_eq!(super::part2(&input), Ok(4000000001480));
+    }
+    #[test]
+    fn bad_notes() {
+        let error = parse("initial state: #..#.#\n\n...## -> #").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 6));
+        assert_eq!(error.expected, "\" => \"");
+
+        let error = parse("initial state: #..#.#\n\n...## => #\n..#. => .").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 4, column 1: expected five pots, found \"..#. => .\""
+        );
+
+        let error = parse("initial state: #..x.#\n\n...## => #").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 19));
+        assert_eq!(error.expected, "# or .");
+
+        let error = parse("state: #..#.#\n\n...## => #").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 1));
+        assert_eq!(error.expected, "initial state");
+
+        let error = parse("initial state: #\n\n..... => #").unwrap_err();
+        assert_eq!(
+            error.expected,
+            "a rule that doesn't grow plants in empty pots"
+        );
+    }
+}
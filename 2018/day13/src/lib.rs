@@ -0,0 +1,424 @@
+use grid::{Grid, GridError};
+use input::InputError;
+use solution::{ParseResult, PartResult, Solution};
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt::{self, Display};
+
+type Position = grid::Point;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    fn from_glyph(c: char) -> Option<Direction> {
+        match c {
+            '^' => Some(Direction::Up),
+            'v' => Some(Direction::Down),
+            '<' => Some(Direction::Left),
+            '>' => Some(Direction::Right),
+            _ => None,
+        }
+    }
+
+    fn glyph(self) -> char {
+        match self {
+            Direction::Up => '^',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+            Direction::Right => '>',
+        }
+    }
+
+    fn left(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Left => Direction::Down,
+            Direction::Down => Direction::Right,
+            Direction::Right => Direction::Up,
+        }
+    }
+
+    fn right(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cart {
+    pub x: usize,
+    pub y: usize,
+    pub direction: Direction,
+    // Intersections taken so far, modulo 3 as the cart turns left, goes straight then turns right
+    intersections: usize,
+}
+
+impl Cart {
+    fn new(x: usize, y: usize, direction: Direction) -> Self {
+        Cart {
+            x,
+            y,
+            direction,
+            intersections: 0,
+        }
+    }
+
+    fn position(&self) -> Position {
+        (self.x, self.y)
+    }
+
+    // Carts move top-to-bottom then left-to-right, so sort on (y, x)
+    fn order(&self) -> (usize, usize) {
+        (self.y, self.x)
+    }
+
+    fn advance(&mut self, track: &Grid<char>) {
+        match self.direction {
+            Direction::Up => self.y -= 1,
+            Direction::Down => self.y += 1,
+            Direction::Left => self.x -= 1,
+            Direction::Right => self.x += 1,
+        }
+
+        self.direction = match (track[self.position()], self.direction) {
+            ('/', Direction::Up) | ('/', Direction::Down) => self.direction.right(),
+            ('/', _) => self.direction.left(),
+            ('\\', Direction::Up) | ('\\', Direction::Down) => self.direction.left(),
+            ('\\', _) => self.direction.right(),
+            ('+', _) => {
+                self.intersections = (self.intersections + 1) % 3;
+                match self.intersections {
+                    1 => self.direction.left(),
+                    2 => self.direction,
+                    _ => self.direction.right(),
+                }
+            }
+            ('|', _) | ('-', _) => self.direction,
+            (c, _) => panic!("Off the track at x:{} y:{} ({:?})", self.x, self.y, c),
+        };
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    Crash(Position),
+    LastCart(Position),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MineError {
+    Track(GridError),
+    // The carts are back where they were on an earlier tick, so it never happens
+    Endless,
+    // Carts crash in pairs, so with an even number of them none is ever left
+    EvenCarts,
+}
+
+impl fmt::Display for MineError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MineError::Track(e) => write!(f, "{}", e),
+            MineError::Endless => write!(f, "the carts go round forever"),
+            MineError::EvenCarts => {
+                write!(f, "no cart is left, there's an even number of them")
+            }
+        }
+    }
+}
+
+impl Error for MineError {}
+
+impl From<GridError> for MineError {
+    fn from(e: GridError) -> Self {
+        MineError::Track(e)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Mine {
+    // The track as drawn, with the piece under each cart filled back in
+    track: Grid<char>,
+    pub carts: Vec<Cart>,
+    pub crashes: Vec<Position>,
+    pub ticks: usize,
+}
+
+pub fn parse_mine(input: &str) -> Result<Mine, GridError> {
+    // Editors like to trim trailing spaces, so pad every row out to the widest
+    let width = input.lines().map(str::len).max().unwrap_or(0);
+    let padded: String = input
+        .lines()
+        .map(|line| format!("{:width$}\n", line, width = width))
+        .collect();
+    let mut track = Grid::try_parse(&padded, |c| Some(c).filter(|c| " -|/\\+<>^v".contains(*c)))?;
+
+    let mut carts = Vec::new();
+    for (x, y) in track.points() {
+        if let Some(direction) = Direction::from_glyph(track[(x, y)]) {
+            carts.push(Cart::new(x, y, direction));
+        }
+    }
+    for cart in carts.iter() {
+        track[cart.position()] = match cart.direction {
+            Direction::Up | Direction::Down => '|',
+            Direction::Left | Direction::Right => '-',
+        };
+    }
+
+    Ok(Mine {
+        track,
+        carts,
+        crashes: Vec::new(),
+        ticks: 0,
+    })
+}
+
+pub fn read_mine(filename: &str) -> Result<Mine, InputError> {
+    input::parse!(filename, parse_mine)
+}
+
+impl Mine {
+    // Move every cart once.  Carts that crash are taken off the track straight away,
+    // so a cart that hasn't moved yet this tick can't run into the wreck.
+    pub fn tick(&mut self) -> Vec<Event> {
+        let mut events = Vec::new();
+        self.carts.sort_by_key(|c| c.order());
+
+        let mut crashed = vec![false; self.carts.len()];
+        for i in 0..self.carts.len() {
+            if crashed[i] {
+                continue;
+            }
+
+            self.carts[i].advance(&self.track);
+            let position = self.carts[i].position();
+            if let Some(j) = (0..self.carts.len())
+                .find(|&j| j != i && !crashed[j] && self.carts[j].position() == position)
+            {
+                crashed[i] = true;
+                crashed[j] = true;
+                self.crashes.push(position);
+                events.push(Event::Crash(position));
+            }
+        }
+
+        let mut crashed = crashed.into_iter();
+        self.carts.retain(|_| !crashed.next().unwrap());
+        self.ticks += 1;
+
+        if self.carts.len() == 1 && !events.is_empty() {
+            events.push(Event::LastCart(self.carts[0].position()));
+        }
+        events
+    }
+
+    // The carts in the order they move, which is all that decides what happens next
+    fn state(&self) -> Vec<Cart> {
+        let mut carts = self.carts.clone();
+        carts.sort_by_key(|c| c.order());
+        carts
+    }
+
+    // Tick until an event turns up.  The mine is finite so without one the carts
+    // must eventually repeat an earlier state, from which they'd go round forever.
+    fn tick_until<F>(&mut self, mut found: F) -> Result<Position, MineError>
+    where
+        F: FnMut(&Event) -> Option<Position>,
+    {
+        let mut seen = HashSet::new();
+        while seen.insert(self.state()) {
+            if let Some(position) = self.tick().iter().find_map(&mut found) {
+                return Ok(position);
+            }
+        }
+        Err(MineError::Endless)
+    }
+
+    pub fn first_crash(&mut self) -> Result<Position, MineError> {
+        self.tick_until(|event| match event {
+            Event::Crash(position) => Some(*position),
+            _ => None,
+        })
+    }
+
+    // Where the last cart is at the end of the tick that leaves it on its own
+    pub fn last_cart(&mut self) -> Result<Position, MineError> {
+        if self.carts.len().is_multiple_of(2) {
+            return Err(MineError::EvenCarts);
+        }
+        if self.carts.len() == 1 {
+            return Ok(self.carts[0].position());
+        }
+
+        self.tick_until(|event| match event {
+            Event::LastCart(position) => Some(*position),
+            _ => None,
+        })
+    }
+
+    // The track with carts drawn on top and an X at every crash site
+    pub fn render(&self) -> String {
+        let mut frame = self.track.clone();
+        for &position in self.crashes.iter() {
+            frame[position] = 'X';
+        }
+        for cart in self.carts.iter() {
+            frame[cart.position()] = cart.direction.glyph();
+        }
+        frame.to_string()
+    }
+}
+
+// Each part runs its own copy of the mine from the start
+fn first_crash(mine: &Mine) -> Result<String, MineError> {
+    let (x, y) = mine.clone().first_crash()?;
+    Ok(format!("{},{}", x, y))
+}
+
+fn last_cart(mine: &Mine) -> Result<String, MineError> {
+    let (x, y) = mine.clone().last_cart()?;
+    Ok(format!("{},{}", x, y))
+}
+
+pub fn part1(input: &str) -> Result<String, MineError> {
+    first_crash(&parse_mine(input)?)
+}
+
+pub fn part2(input: &str) -> Result<String, MineError> {
+    last_cart(&parse_mine(input)?)
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Mine;
+
+    fn parse(input: &str) -> ParseResult<Mine> {
+        Ok(parse_mine(input)?)
+    }
+
+    fn part1(mine: &Mine) -> PartResult<impl Display> {
+        Ok(first_crash(mine)?)
+    }
+
+    fn part2(mine: &Mine) -> PartResult<impl Display> {
+        Ok(last_cart(mine)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn straight_line() {
+        let mut mine = read_mine("test_input1.txt").unwrap();
+        assert_eq!(mine.first_crash(), Ok((0, 4)));
+        assert_eq!(mine.ticks, 2);
+        assert!(mine.carts.is_empty());
+        assert_eq!(mine.render(), "|\n|\n|\n|\nX\n|\n|\n|");
+    }
+
+    #[test]
+    fn example() {
+        let mut mine = read_mine("test_input2.txt").unwrap();
+        assert_eq!(
+            mine.render(),
+            input::read!("test_input2.txt")
+                .unwrap()
+                .trim_end_matches('\n')
+        );
+
+        mine.tick();
+        assert_eq!(
+            mine.render(),
+            "/-->\\        
+|   |  /----\\
+| /-+--+-\\  |
+| | |  | |  |
+\\-+-/  \\->--/
+  \\------/   "
+        );
+
+        assert_eq!(mine.first_crash(), Ok((7, 3)));
+        assert_eq!(mine.ticks, 14);
+    }
+
+    #[test]
+    fn example_last_cart() {
+        let mut mine = read_mine("test2_input1.txt").unwrap();
+        assert_eq!(mine.last_cart(), Ok((6, 4)));
+        assert_eq!(mine.ticks, 3);
+        assert_eq!(mine.crashes, vec![(2, 0), (2, 4), (6, 4), (2, 4)]);
+    }
+
+    #[test]
+    fn same_row() {
+        // both carts on one row, the left one moves first and runs into the other
+        let mut mine = parse_mine("->>-").unwrap();
+        let events = mine.tick();
+        assert_eq!(events, vec![Event::Crash((2, 0))]);
+        assert_eq!(mine.render(), "--X-");
+
+        let mut mine = parse_mine("-><--<-").unwrap();
+        assert_eq!(
+            mine.tick(),
+            vec![Event::Crash((2, 0)), Event::LastCart((4, 0))]
+        );
+    }
+
+    #[test]
+    fn no_last_cart() {
+        let mut mine = parse_mine("->--<-").unwrap();
+        assert_eq!(mine.last_cart(), Err(MineError::EvenCarts));
+        assert_eq!(mine.ticks, 0);
+        assert_eq!(parse_mine("-->").unwrap().last_cart(), Ok((2, 0)));
+    }
+
+    #[test]
+    fn endless() {
+        // two carts chasing each other round a loop never meet
+        let chase = "/>-\\\n|  |\n\\-</";
+        let mut mine = parse_mine(chase).unwrap();
+        assert_eq!(mine.first_crash(), Err(MineError::Endless));
+        assert_eq!(mine.ticks, 5);
+        assert_eq!(
+            part1(chase).unwrap_err().to_string(),
+            "the carts go round forever"
+        );
+
+        // the pair underneath crash, the three on the loop never do
+        let mut mine = parse_mine("/>->\\\n|   |\n\\-<-/\n->-<-").unwrap();
+        assert_eq!(mine.last_cart(), Err(MineError::Endless));
+        assert_eq!(mine.crashes, vec![(2, 3)]);
+    }
+
+    #[test]
+    fn input() {
+        let input = input::read!("input.txt").unwrap();
+        assert_eq!(part1(&input).unwrap(), "91,69");
+        assert_eq!(part2(&input).unwrap(), "44,87");
+    }
+
+    #[test]
+    fn bad_track() {
+        assert_eq!(
+            parse_mine("/->-\\\n|   |\n\\-*-/").unwrap_err(),
+            GridError::BadCell {
+                line: 3,
+                column: 3,
+                found: '*'
+            }
+        );
+    }
+}
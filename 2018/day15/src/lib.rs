@@ -1,64 +1,470 @@
-use std::fs::File;
-use std::io::{self, BufRead};
+use grid::{Grid, GridError};
+use input::InputError;
+use solution::{ParseResult, PartResult, Solution};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::Display;
+
+type Position = grid::Point;
 
 enum Action {
-    Move,
-    Attack,
+    Move(Position),
+    Attack(usize),
 }
 
-struct Unit{
-    unit_type:Type,
-    x:u32,
-    y:u32,
-    hp:u32,
+#[derive(Debug, Clone)]
+pub struct Unit {
+    pub unit_type: Type,
+    pub x: usize,
+    pub y: usize,
+    pub hp: i32,
+    pub power: i32,
 }
-enum Type{
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Type {
     Goblin,
     Elf,
 }
 
-type Map = Vec<Vec<u8>>;
+impl Unit {
+    fn new(unit_type: Type, x: usize, y: usize) -> Self {
+        Unit {
+            unit_type,
+            x,
+            y,
+            hp: 200,
+            power: 3,
+        }
+    }
+
+    fn alive(&self) -> bool {
+        self.hp > 0
+    }
+
+    fn position(&self) -> Position {
+        (self.x, self.y)
+    }
+}
+
+// Reading order is top-to-bottom then left-to-right, so sort on (y, x)
+fn reading_order(p: Position) -> (usize, usize) {
+    (p.1, p.0)
+}
+
+type Map = Grid<char>;
+
+#[derive(Clone)]
 pub struct Game {
     map: Map,
-    units: Vec<Unit>,
+    pub units: Vec<Unit>,
+    pub rounds: u32,
 }
-pub fn read_map(filename: &str) -> Game {
-    let file = match File::open(filename) {
-        Ok(f) => f,
-        Err(_) => panic!("Bad filename!"),
-    };
 
-    let lines = io::BufReader::new(file).lines();
+pub fn parse_map(input: &str) -> Result<Game, GridError> {
+    let trimmed: String = input
+        .lines()
+        .map(|line| line.trim().to_string() + "\n")
+        .collect();
+    let mut game = Game {
+        map: Grid::try_parse(&trimmed, |c| Some(c).filter(|c| "#.GE".contains(*c)))?,
+        units: Vec::new(),
+        rounds: 0,
+    };
 
-    let mut game = Game { map: Vec::new() };
-    for line in lines {
-        game.map
-            .push(line.expect("Unable to read line").into_bytes());
+    for (x, y) in game.map.points() {
+        let unit_type = match game.map[(x, y)] {
+            'G' => Type::Goblin,
+            'E' => Type::Elf,
+            _ => continue,
+        };
+        game.units.push(Unit::new(unit_type, x, y));
     }
-    game
+    for unit in game.units.iter() {
+        game.map[unit.position()] = '.';
+    }
+    Ok(game)
+}
+
+pub fn read_map(filename: &str) -> Result<Game, InputError> {
+    input::parse!(filename, parse_map)
 }
 
 impl Game {
+    pub fn render(&self) -> String {
+        let mut map = self.map.clone();
+        for unit in self.units.iter().filter(|u| u.alive()) {
+            map[unit.position()] = match unit.unit_type {
+                Type::Goblin => 'G',
+                Type::Elf => 'E',
+            };
+        }
+        map.to_string()
+    }
+
     pub fn print_map(&self) {
-        for line in &self.map {
-            for c in line {
-                print!("{}", *c as char);
+        println!("{}", self.render());
+    }
+
+    pub fn set_elf_power(&mut self, power: i32) {
+        for unit in self.units.iter_mut() {
+            if unit.unit_type == Type::Elf {
+                unit.power = power;
+            }
+        }
+    }
+
+    pub fn count(&self, unit_type: Type) -> usize {
+        self.units
+            .iter()
+            .filter(|u| u.alive() && u.unit_type == unit_type)
+            .count()
+    }
+
+    fn occupied(&self) -> HashSet<Position> {
+        self.units
+            .iter()
+            .filter(|u| u.alive())
+            .map(|u| u.position())
+            .collect()
+    }
+
+    fn is_open(&self, p: Position, occupied: &HashSet<Position>) -> bool {
+        self.map[p] == '.' && !occupied.contains(&p)
+    }
+
+    // Shortest number of steps to every reachable open square
+    fn distances(&self, from: Position, occupied: &HashSet<Position>) -> HashMap<Position, usize> {
+        let mut distances = HashMap::new();
+        let mut queue = VecDeque::new();
+        distances.insert(from, 0);
+        queue.push_back(from);
+
+        while let Some(p) = queue.pop_front() {
+            let d = distances[&p];
+            for next in self.map.neighbours4(p) {
+                if self.is_open(next, occupied) && !distances.contains_key(&next) {
+                    distances.insert(next, d + 1);
+                    queue.push_back(next);
+                }
+            }
+        }
+        distances
+    }
+
+    fn is_enemy(&self, i: usize, j: usize) -> bool {
+        self.units[j].alive() && self.units[i].unit_type != self.units[j].unit_type
+    }
+
+    // The adjacent enemy with the fewest hit points, ties broken in reading order
+    fn attack_target(&self, i: usize) -> Option<usize> {
+        let adjacent: Vec<Position> = self.map.neighbours4(self.units[i].position()).collect();
+        (0..self.units.len())
+            .filter(|&j| self.is_enemy(i, j))
+            .filter(|&j| adjacent.contains(&self.units[j].position()))
+            .min_by_key(|&j| (self.units[j].hp, reading_order(self.units[j].position())))
+    }
+
+    // Head for the nearest square in range of an enemy, taking the first step (in
+    // reading order) along a shortest path to it
+    fn step_towards_enemy(&self, i: usize) -> Option<Position> {
+        let mut occupied = self.occupied();
+        let here = self.units[i].position();
+        occupied.remove(&here);
+
+        let in_range: HashSet<Position> = (0..self.units.len())
+            .filter(|&j| self.is_enemy(i, j))
+            .flat_map(|j| self.map.neighbours4(self.units[j].position()))
+            .filter(|&p| self.is_open(p, &occupied))
+            .collect();
+
+        let distances = self.distances(here, &occupied);
+        let chosen = in_range
+            .iter()
+            .filter_map(|p| distances.get(p).map(|d| (*d, reading_order(*p), *p)))
+            .min()?
+            .2;
+
+        let back = self.distances(chosen, &occupied);
+        self.map
+            .neighbours4(here)
+            .filter_map(|p| back.get(&p).map(|d| (*d, reading_order(p), p)))
+            .min()
+            .map(|(_, _, p)| p)
+    }
+
+    fn choose_action(&self, i: usize) -> Option<Action> {
+        match self.attack_target(i) {
+            Some(target) => Some(Action::Attack(target)),
+            None => self.step_towards_enemy(i).map(Action::Move),
+        }
+    }
+
+    fn attack(&mut self, i: usize, target: usize) {
+        self.units[target].hp -= self.units[i].power;
+    }
+
+    // Returns false if combat ended part way through the round, in which case the
+    // round doesn't count
+    pub fn round(&mut self) -> bool {
+        let mut order: Vec<usize> = (0..self.units.len())
+            .filter(|&i| self.units[i].alive())
+            .collect();
+        order.sort_by_key(|&i| reading_order(self.units[i].position()));
+
+        for i in order {
+            if !self.units[i].alive() {
+                continue;
+            }
+            if !(0..self.units.len()).any(|j| self.is_enemy(i, j)) {
+                return false;
+            }
+
+            match self.choose_action(i) {
+                Some(Action::Attack(target)) => self.attack(i, target),
+                Some(Action::Move((x, y))) => {
+                    self.units[i].x = x;
+                    self.units[i].y = y;
+                    if let Some(target) = self.attack_target(i) {
+                        self.attack(i, target);
+                    }
+                }
+                None => {}
             }
-            println!();
         }
+
+        self.rounds += 1;
+        true
     }
 
+    pub fn outcome(&self) -> u32 {
+        let hp: i32 = self.units.iter().filter(|u| u.alive()).map(|u| u.hp).sum();
+        self.rounds * hp as u32
+    }
+
+    // Fight until one side is wiped out and return the outcome
+    pub fn run(&mut self) -> u32 {
+        while self.round() {}
+        self.outcome()
+    }
+
+    // Like `run`, but gives up as soon as an elf dies
+    fn run_without_losses(&mut self) -> Option<u32> {
+        let elves = self.count(Type::Elf);
+        while self.round() {
+            if self.count(Type::Elf) < elves {
+                return None;
+            }
+        }
+
+        if self.count(Type::Elf) < elves {
+            None
+        } else {
+            Some(self.outcome())
+        }
+    }
 }
 
-#[test]
-pub fn test_input_1() {
-    let game = read_map("testinput1.txt");
-    game.print_map();
+// The smallest elf attack power that wins without losing a single elf, and that
+// battle's outcome.  Fewer deaths with more power isn't guaranteed, so every power
+// is tried in turn rather than bisecting.
+pub fn minimum_elf_power(game: &Game) -> (i32, u32) {
+    (4..)
+        .find_map(|power| {
+            let mut game = game.clone();
+            game.set_elf_power(power);
+            game.run_without_losses().map(|outcome| (power, outcome))
+        })
+        .unwrap()
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input = Game;
+
+    fn parse(input: &str) -> ParseResult<Game> {
+        Ok(parse_map(input)?)
+    }
+
+    fn part1(game: &Game) -> PartResult<impl Display> {
+        Ok(game.clone().run())
+    }
+
+    fn part2(game: &Game) -> PartResult<impl Display> {
+        Ok(minimum_elf_power(game).1)
+    }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_1() {
+        let mut game = read_map("testinput1.txt").unwrap();
+        for _ in 0..3 {
+            game.round();
+        }
+
+        assert_eq!(
+            game.render(),
+            "#########
+#.......#
+#..GGG..#
+#..GEG..#
+#G..G...#
+#......G#
+#.......#
+#.......#
+#########"
+        );
+    }
+
+    #[test]
+    fn test_input_2() {
+        let game = read_map("testinput2.txt").unwrap();
+
+        let mut battle = game.clone();
+        assert_eq!(battle.run(), 27730);
+        assert_eq!(battle.rounds, 47);
+        assert_eq!(battle.count(Type::Elf), 0);
+        assert_eq!(
+            battle.render(),
+            "#######
+#G....#
+#.G...#
+#.#.#G#
+#...#.#
+#....G#
+#######"
+        );
 
-#[test]
-fn test_input_2() {
-    let game = read_map("testinput2.txt");
-    game.print_map();
+        assert_eq!(minimum_elf_power(&game), (15, 4988));
+    }
+
+    #[test]
+    fn examples() {
+        let battles = [
+            (
+                "#######
+                 #G..#E#
+                 #E#E.E#
+                 #G.##.#
+                 #...#E#
+                 #...E.#
+                 #######",
+                36334,
+            ),
+            (
+                "#######
+                 #E..EG#
+                 #.#G.E#
+                 #E.##E#
+                 #G..#.#
+                 #..E#.#
+                 #######",
+                39514,
+            ),
+            (
+                "#######
+                 #E.G#.#
+                 #.#G..#
+                 #G.#.G#
+                 #G..#.#
+                 #...E.#
+                 #######",
+                27755,
+            ),
+            (
+                "#######
+                 #.E...#
+                 #.#..G#
+                 #.###.#
+                 #E#G#G#
+                 #...#G#
+                 #######",
+                28944,
+            ),
+            (
+                "#########
+                 #G......#
+                 #.E.#...#
+                 #..##..G#
+                 #...##..#
+                 #...#...#
+                 #.G...G.#
+                 #.....G.#
+                 #########",
+                18740,
+            ),
+        ];
+
+        for (map, outcome) in battles.iter() {
+            assert_eq!(parse_map(map).unwrap().run(), *outcome);
+        }
+    }
+
+    #[test]
+    fn examples_elf_power() {
+        let battles = [
+            (
+                "#######
+                 #E..EG#
+                 #.#G.E#
+                 #E.##E#
+                 #G..#.#
+                 #..E#.#
+                 #######",
+                (4, 31284),
+            ),
+            (
+                "#######
+                 #E.G#.#
+                 #.#G..#
+                 #G.#.G#
+                 #G..#.#
+                 #...E.#
+                 #######",
+                (15, 3478),
+            ),
+            (
+                "#######
+                 #.E...#
+                 #.#..G#
+                 #.###.#
+                 #E#G#G#
+                 #...#G#
+                 #######",
+                (12, 6474),
+            ),
+            (
+                "#########
+                 #G......#
+                 #.E.#...#
+                 #..##..G#
+                 #...##..#
+                 #...#...#
+                 #.G...G.#
+                 #.....G.#
+                 #########",
+                (34, 1140),
+            ),
+        ];
+
+        for (map, expected) in battles.iter() {
+            assert_eq!(minimum_elf_power(&parse_map(map).unwrap()), *expected);
+        }
+    }
+
+    #[test]
+    fn bad_map() {
+        assert_eq!(
+            parse_map("#####\n#G.E#\n#.g.#\n#####").err(),
+            Some(GridError::BadCell {
+                line: 3,
+                column: 3,
+                found: 'g'
+            })
+        );
+    }
 }
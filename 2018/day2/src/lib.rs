@@ -0,0 +1,113 @@
+use parser::nom::{character::complete::alpha1, combinator::map};
+use parser::{lines, parse_all, ParseError};
+use solution::{ParseResult, PartResult, Solution};
+use std::collections::HashMap;
+use std::fmt::Display;
+
+fn letter_counts(line: &str) -> HashMap<char, usize> {
+    let mut counts = HashMap::new();
+    for c in line.chars() {
+        *counts.entry(c).or_insert(0) += 1;
+    }
+    counts
+}
+
+pub fn parse(input: &str) -> Result<Vec<String>, ParseError> {
+    parse_all(input, lines(map(alpha1, String::from)))
+}
+
+// Boxes with some letter exactly twice times boxes with some letter exactly three times
+pub fn checksum(ids: &[String]) -> usize {
+    let mut doubles = 0;
+    let mut triples = 0;
+
+    for line in ids {
+        let counts = letter_counts(line);
+        if counts.values().any(|&n| n == 2) {
+            doubles += 1;
+        }
+        if counts.values().any(|&n| n == 3) {
+            triples += 1;
+        }
+    }
+    doubles * triples
+}
+
+// The letters shared by the two ids that differ in exactly one position
+pub fn common_letters(ids: &[String]) -> String {
+    for (i, line) in ids.iter().enumerate() {
+        for compline in &ids[i + 1..] {
+            let resline: String = line
+                .chars()
+                .zip(compline.chars())
+                .filter(|(a, b)| a == b)
+                .map(|(a, _)| a)
+                .collect();
+
+            if resline.len() + 1 == line.len() {
+                return resline;
+            }
+        }
+    }
+    panic!("No two ids differ by a single letter");
+}
+
+pub fn part1(input: &str) -> Result<usize, ParseError> {
+    Ok(checksum(&parse(input)?))
+}
+
+pub fn part2(input: &str) -> Result<String, ParseError> {
+    Ok(common_letters(&parse(input)?))
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> ParseResult<Vec<String>> {
+        Ok(parse(input)?)
+    }
+
+    fn part1(ids: &Vec<String>) -> PartResult<impl Display> {
+        Ok(checksum(ids))
+    }
+
+    fn part2(ids: &Vec<String>) -> PartResult<impl Display> {
+        Ok(common_letters(ids))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    #[test]
+    fn example1() {
+        let input = input::read!("test_input.txt").unwrap();
+        assert_eq!(super::part1(&input).unwrap(), 12);
+    }
+
+    #[test]
+    fn part1() {
+        let input = input::read!("input.txt").unwrap();
+        assert_eq!(super::part1(&input).unwrap(), 7134);
+    }
+
+    #[test]
+    fn example2() {
+        let input = input::read!("test_input2.txt").unwrap();
+        assert_eq!(super::part2(&input).unwrap(), "fgij");
+    }
+
+    #[test]
+    fn part2() {
+        let input = input::read!("input.txt").unwrap();
+        assert_eq!(super::part2(&input).unwrap(), "kbqwtcvzhmhpoelrnaxydifyb");
+    }
+
+    #[test]
+    fn bad_id() {
+        let error = super::parse("abcdef\nbab-bc").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 4));
+    }
+}
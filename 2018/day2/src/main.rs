@@ -1,80 +1,19 @@
-use std::collections::HashMap;
-use std::fs::File;
-use std::io::{self, Read};
-
-fn main() -> io::Result<()> {
-    let mut file = File::open("input.txt")?;
-    //let mut file = File::open("test_input.txt")?;
-    //let mut file = File::open("test_input2.txt")?;
-    let mut contents = String::new();
-    file.read_to_string(&mut contents)?;
-
-    let collection: Vec<&str> = contents[..contents.len() - 1].split('\n').collect();
-    let letters = vec![
-        'a', 'b', 'a', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q',
-        'r', 's', 't', 'u', 'v', 'w', 'x', 'y', 'z',
-    ];
-
-    let mut hash = HashMap::new();
-    for line in &collection {
-        println!("{} --", &line);
-
-        let mut double = false;
-        let mut triple = false;
-
-        for letter in &letters {
-            let n = line.matches(*letter).count();
-
-            //println!("\t {} {}", *letter, n);
-
-            match n {
-                0 => {}
-                1 => {}
-                2 => double = true,
-                3 => triple = true,
-                _ => println!("need more buckets!"),
-            };
-        }
-        if double {
-            let counter = hash.entry(2).or_insert(0);
-            *counter += 1;
-        }
-        if triple {
-            let counter = hash.entry(3).or_insert(0);
-            *counter += 1;
-        }
+use std::env;
+use std::path::PathBuf;
+
+fn main() {
+    let path = env::args()
+        .nth(1)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| input::path!("input.txt"));
+    let input = input::read_file(&path).expect("Unable to read input");
+
+    match aoc2018_day2::part1(&input) {
+        Ok(answer) => println!("Part 1: {}", answer),
+        Err(e) => eprintln!("Part 1: {}", e),
     }
-
-    println!("\nord\tinst");
-    let mut total_hash = 1;
-    for val in 2..4 {
-        let x = hash.entry(val).or_insert(0);
-
-        println!("{}\t{}", val, *x);
-
-        total_hash *= *x;
+    match aoc2018_day2::part2(&input) {
+        Ok(answer) => println!("Part 2: {}", answer),
+        Err(e) => eprintln!("Part 2: {}", e),
     }
-
-    println!("total_hash = {}", total_hash);
-
-    // Part-2
-    for line in &collection {
-        for compline in &collection {
-            let mut resline = String::from("");
-            for i in 0..line.len() {
-                if line.as_bytes()[i] == compline.as_bytes()[i] {
-                    resline.push_str(&line[i..=i]);
-                }
-            }
-
-            if resline.len() + 1 == line.len() {
-                println!(
-                    "I think we have a match!!\nline\t--\t{}\ncmpline\t--\t{}\nresline\t--\t{}",
-                    line, compline, resline
-                );
-            }
-        }
-    }
-
-    Ok(())
 }
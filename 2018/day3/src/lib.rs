@@ -0,0 +1,141 @@
+use parser::nom::{
+    bytes::complete::tag, character::complete::char, combinator::map, sequence::preceded,
+    sequence::tuple,
+};
+use parser::{integer, lines, parse_all, ParseError, Parsed};
+use solution::{ParseResult, PartResult, Solution};
+use std::collections::HashMap;
+use std::fmt::Display;
+
+#[derive(Debug)]
+pub struct Claim {
+    id: u32,
+    x_start: u32,
+    y_start: u32,
+    x_size: u32,
+    y_size: u32,
+}
+
+impl Claim {
+    fn squares(&self) -> impl Iterator<Item = (u32, u32)> + '_ {
+        (0..self.x_size)
+            .flat_map(move |x| (0..self.y_size).map(move |y| (self.x_start + x, self.y_start + y)))
+    }
+}
+
+// "#1 @ 1,3: 4x4"
+fn claim(input: &str) -> Parsed<'_, Claim> {
+    map(
+        tuple((
+            preceded(char('#'), integer),
+            preceded(tag(" @ "), integer),
+            preceded(char(','), integer),
+            preceded(tag(": "), integer),
+            preceded(char('x'), integer),
+        )),
+        |(id, x_start, y_start, x_size, y_size)| Claim {
+            id,
+            x_start,
+            y_start,
+            x_size,
+            y_size,
+        },
+    )(input)
+}
+
+pub fn parse(input: &str) -> Result<Vec<Claim>, ParseError> {
+    parse_all(input, lines(claim))
+}
+
+fn record_claims(claims: &[Claim]) -> HashMap<(u32, u32), u32> {
+    let mut map = HashMap::new();
+    for claim in claims {
+        for square in claim.squares() {
+            *map.entry(square).or_insert(0) += 1;
+        }
+    }
+    map
+}
+
+// Square inches of fabric within two or more claims
+pub fn overlapping(claims: &[Claim]) -> usize {
+    record_claims(claims)
+        .values()
+        .filter(|&&inst| inst > 1)
+        .count()
+}
+
+// The id of the only claim that doesn't overlap any other
+pub fn intact(claims: &[Claim]) -> u32 {
+    let map = record_claims(claims);
+
+    claims
+        .iter()
+        .find(|claim| claim.squares().all(|square| map[&square] == 1))
+        .expect("Every claim overlaps another")
+        .id
+}
+
+pub fn part1(input: &str) -> Result<usize, ParseError> {
+    Ok(overlapping(&parse(input)?))
+}
+
+pub fn part2(input: &str) -> Result<u32, ParseError> {
+    Ok(intact(&parse(input)?))
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Vec<Claim>;
+
+    fn parse(input: &str) -> ParseResult<Vec<Claim>> {
+        Ok(parse(input)?)
+    }
+
+    fn part1(claims: &Vec<Claim>) -> PartResult<impl Display> {
+        Ok(overlapping(claims))
+    }
+
+    fn part2(claims: &Vec<Claim>) -> PartResult<impl Display> {
+        Ok(intact(claims))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    #[test]
+    fn example1() {
+        let input = input::read!("test_input.txt").unwrap();
+        assert_eq!(super::part1(&input).unwrap(), 4);
+    }
+
+    #[test]
+    fn part1() {
+        let input = input::read!("input.txt").unwrap();
+        assert_eq!(super::part1(&input).unwrap(), 109785);
+    }
+
+    #[test]
+    fn example2() {
+        let input = input::read!("test_input.txt").unwrap();
+        assert_eq!(super::part2(&input).unwrap(), 3);
+    }
+
+    #[test]
+    fn part2() {
+        let input = input::read!("input.txt").unwrap();
+        assert_eq!(super::part2(&input).unwrap(), 504);
+    }
+
+    #[test]
+    fn bad_claim() {
+        let error = super::parse("#1 @ 1,3: 4x4\n#2 @ 3,1 4x4").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 9));
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 9: expected tag, found \" 4x4\""
+        );
+    }
+}
@@ -0,0 +1,442 @@
+use solution::{ParseResult, PartResult, Solution};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+// Fields in the order they sort
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Timestamp {
+    pub year: u32,
+    pub month: u32,
+    pub day: u32,
+    pub hour: u32,
+    pub minute: u32,
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}-{:02}-{:02} {:02}:{:02}",
+            self.year, self.month, self.day, self.hour, self.minute
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    BeginsShift(u32),
+    FallsAsleep,
+    WakesUp,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Entry {
+    pub time: Timestamp,
+    pub event: Event,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LogError {
+    BadLine(String),
+    // The event can't happen at that point in the log, e.g. waking up while awake
+    OutOfOrder(Entry),
+    // Guards only sleep during the midnight hour
+    OutsideMidnight(Entry),
+    // The log ends with a guard still asleep
+    Unfinished(Entry),
+}
+
+impl fmt::Display for LogError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LogError::BadLine(line) => write!(f, "can't read log line \"{}\"", line),
+            LogError::OutOfOrder(entry) => {
+                write!(f, "{:?} out of order at {}", entry.event, entry.time)
+            }
+            LogError::OutsideMidnight(entry) => {
+                write!(
+                    f,
+                    "{:?} outside the midnight hour at {}",
+                    entry.event, entry.time
+                )
+            }
+            LogError::Unfinished(entry) => {
+                write!(f, "log ends with the guard asleep since {}", entry.time)
+            }
+        }
+    }
+}
+
+impl Error for LogError {}
+
+fn parse_timestamp(s: &str) -> Option<Timestamp> {
+    let (date, time) = s.split_once(' ')?;
+    let date: Vec<&str> = date.split('-').collect();
+    let time: Vec<&str> = time.split(':').collect();
+    if date.len() != 3 || time.len() != 2 {
+        return None;
+    }
+
+    let timestamp = Timestamp {
+        year: date[0].parse().ok()?,
+        month: date[1].parse().ok()?,
+        day: date[2].parse().ok()?,
+        hour: time[0].parse().ok()?,
+        minute: time[1].parse().ok()?,
+    };
+
+    if timestamp.hour < 24 && timestamp.minute < 60 {
+        Some(timestamp)
+    } else {
+        None
+    }
+}
+
+fn parse_event(s: &str) -> Option<Event> {
+    match s {
+        "falls asleep" => Some(Event::FallsAsleep),
+        "wakes up" => Some(Event::WakesUp),
+        _ => s
+            .strip_prefix("Guard #")?
+            .strip_suffix(" begins shift")?
+            .parse()
+            .ok()
+            .map(Event::BeginsShift),
+    }
+}
+
+// "[1518-11-01 00:05] falls asleep"
+impl FromStr for Entry {
+    type Err = LogError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let bad_line = || LogError::BadLine(line.to_string());
+
+        let (time, event) = line
+            .strip_prefix('[')
+            .and_then(|rest| rest.split_once("] "))
+            .ok_or_else(bad_line)?;
+
+        Ok(Entry {
+            time: parse_timestamp(time).ok_or_else(bad_line)?,
+            event: parse_event(event).ok_or_else(bad_line)?,
+        })
+    }
+}
+
+// Check the guards take turns sensibly, every nap being on someone's shift and
+// ending before the next shift starts
+fn check(log: &[Entry]) -> Result<(), LogError> {
+    let mut on_shift = false;
+    let mut asleep: Option<Entry> = None;
+
+    for (i, entry) in log.iter().enumerate() {
+        if i > 0 && log[i - 1].time == entry.time {
+            return Err(LogError::OutOfOrder(*entry));
+        }
+
+        match entry.event {
+            Event::BeginsShift(_) => {
+                if asleep.is_some() {
+                    return Err(LogError::OutOfOrder(*entry));
+                }
+                on_shift = true;
+            }
+            Event::FallsAsleep | Event::WakesUp => {
+                if entry.time.hour != 0 {
+                    return Err(LogError::OutsideMidnight(*entry));
+                }
+
+                let waking = entry.event == Event::WakesUp;
+                if !on_shift || asleep.is_some() != waking {
+                    return Err(LogError::OutOfOrder(*entry));
+                }
+                asleep = if waking { None } else { Some(*entry) };
+            }
+        }
+    }
+
+    match asleep {
+        Some(entry) => Err(LogError::Unfinished(entry)),
+        None => Ok(()),
+    }
+}
+
+// The log lines come in any order, they're sorted by time before being checked
+pub fn parse_log(input: &str) -> Result<Vec<Entry>, LogError> {
+    let mut log = input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.trim().parse())
+        .collect::<Result<Vec<Entry>, LogError>>()?;
+
+    log.sort_by_key(|entry| entry.time);
+    check(&log)?;
+    Ok(log)
+}
+
+// How many times a guard was asleep at each minute past midnight
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    pub minutes: [u32; 60],
+}
+
+impl Histogram {
+    pub fn total(&self) -> u32 {
+        self.minutes.iter().sum()
+    }
+
+    // The minute most often slept through and how often, the earliest one on a tie
+    pub fn sleepiest_minute(&self) -> (usize, u32) {
+        let mut best = (0, self.minutes[0]);
+        for (minute, &count) in self.minutes.iter().enumerate() {
+            if count > best.1 {
+                best = (minute, count);
+            }
+        }
+        best
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Guards {
+    pub histograms: BTreeMap<u32, Histogram>,
+}
+
+impl Guards {
+    // Expects a log that came through `parse_log`
+    pub fn new(log: &[Entry]) -> Self {
+        let mut histograms = BTreeMap::new();
+        let mut guard = 0;
+        let mut fell_asleep = 0;
+
+        for entry in log {
+            match entry.event {
+                Event::BeginsShift(id) => {
+                    guard = id;
+                    // guards who never nod off still get an (empty) histogram
+                    histograms
+                        .entry(id)
+                        .or_insert(Histogram { minutes: [0; 60] });
+                }
+                Event::FallsAsleep => fell_asleep = entry.time.minute as usize,
+                Event::WakesUp => {
+                    let histogram = histograms.get_mut(&guard).unwrap();
+                    for minute in fell_asleep..entry.time.minute as usize {
+                        histogram.minutes[minute] += 1;
+                    }
+                }
+            }
+        }
+        Guards { histograms }
+    }
+
+    // The guard, ties going to the lowest id, whose histogram scores best
+    fn best_by<F: Fn(&Histogram) -> u32>(&self, score: F) -> Option<(u32, usize)> {
+        let mut best: Option<(u32, &Histogram)> = None;
+        for (&id, histogram) in self.histograms.iter() {
+            if best.is_none_or(|(_, b)| score(histogram) > score(b)) {
+                best = Some((id, histogram));
+            }
+        }
+        best.map(|(id, histogram)| (id, histogram.sleepiest_minute().0))
+    }
+
+    // The guard asleep the longest, and the minute they're most often asleep
+    pub fn strategy1(&self) -> Option<(u32, usize)> {
+        self.best_by(Histogram::total)
+    }
+
+    // The guard most often asleep on the same minute, and that minute
+    pub fn strategy2(&self) -> Option<(u32, usize)> {
+        self.best_by(|h| h.sleepiest_minute().1)
+    }
+}
+
+fn guards(input: &str) -> Result<Guards, LogError> {
+    Ok(Guards::new(&parse_log(input)?))
+}
+
+// The guard's id times the minute a strategy picked
+fn answer(choice: Option<(u32, usize)>) -> usize {
+    let (guard, minute) = choice.expect("No guards");
+    guard as usize * minute
+}
+
+pub fn part1(input: &str) -> Result<usize, LogError> {
+    Ok(answer(guards(input)?.strategy1()))
+}
+
+pub fn part2(input: &str) -> Result<usize, LogError> {
+    Ok(answer(guards(input)?.strategy2()))
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = Guards;
+
+    fn parse(input: &str) -> ParseResult<Guards> {
+        Ok(Guards::new(&parse_log(input)?))
+    }
+
+    fn part1(guards: &Guards) -> PartResult<impl Display> {
+        Ok(answer(guards.strategy1()))
+    }
+
+    fn part2(guards: &Guards) -> PartResult<impl Display> {
+        Ok(answer(guards.strategy2()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parsing() {
+        let log = parse_log(&input::read!("test_input.txt").unwrap()).unwrap();
+        assert_eq!(log.len(), 17);
+        assert_eq!(
+            log[0],
+            Entry {
+                time: Timestamp {
+                    year: 1518,
+                    month: 11,
+                    day: 1,
+                    hour: 0,
+                    minute: 0
+                },
+                event: Event::BeginsShift(10)
+            }
+        );
+        assert_eq!(log[1].event, Event::FallsAsleep);
+        assert_eq!(log[2].event, Event::WakesUp);
+        // the shift that starts at 23:58 the night before
+        assert_eq!(log[5].event, Event::BeginsShift(99));
+        assert_eq!(log[5].time.hour, 23);
+    }
+
+    #[test]
+    fn bad_logs() {
+        assert_eq!(
+            parse_log("[1518-11-01 00:05] falls asleep\n1518-11-01 00:00 Guard #10"),
+            Err(LogError::BadLine("1518-11-01 00:00 Guard #10".to_string()))
+        );
+        assert!(matches!(
+            parse_log("[1518-11-01 00:05] naps"),
+            Err(LogError::BadLine(_))
+        ));
+        assert!(matches!(
+            parse_log("[1518-11-01 00:65] wakes up"),
+            Err(LogError::BadLine(_))
+        ));
+
+        // nobody on shift
+        assert!(matches!(
+            parse_log("[1518-11-01 00:05] falls asleep"),
+            Err(LogError::OutOfOrder(_))
+        ));
+        // woken up twice
+        assert!(matches!(
+            parse_log(
+                "[1518-11-01 00:00] Guard #10 begins shift
+                 [1518-11-01 00:05] falls asleep
+                 [1518-11-01 00:10] wakes up
+                 [1518-11-01 00:15] wakes up"
+            ),
+            Err(LogError::OutOfOrder(_))
+        ));
+        // handed over while asleep
+        assert!(matches!(
+            parse_log(
+                "[1518-11-01 00:00] Guard #10 begins shift
+                 [1518-11-01 00:05] falls asleep
+                 [1518-11-02 00:00] Guard #11 begins shift"
+            ),
+            Err(LogError::OutOfOrder(_))
+        ));
+        assert!(matches!(
+            parse_log(
+                "[1518-11-01 23:50] Guard #10 begins shift
+                 [1518-11-01 23:55] falls asleep
+                 [1518-11-02 00:10] wakes up"
+            ),
+            Err(LogError::OutsideMidnight(_))
+        ));
+        assert!(matches!(
+            parse_log(
+                "[1518-11-01 00:00] Guard #10 begins shift
+                 [1518-11-01 00:05] falls asleep"
+            ),
+            Err(LogError::Unfinished(_))
+        ));
+    }
+
+    #[test]
+    fn histograms() {
+        let guards = Guards::new(&parse_log(&input::read!("test_input.txt").unwrap()).unwrap());
+
+        assert_eq!(guards.histograms[&10].total(), 50);
+        assert_eq!(guards.histograms[&10].sleepiest_minute(), (24, 2));
+        assert_eq!(guards.histograms[&99].total(), 30);
+        assert_eq!(guards.histograms[&99].sleepiest_minute(), (45, 3));
+
+        assert_eq!(guards.strategy1(), Some((10, 24)));
+        assert_eq!(guards.strategy2(), Some((99, 45)));
+    }
+
+    #[test]
+    fn ties() {
+        // both guards sleep for 10 minutes and at most twice on the same minute, so
+        // the lower id wins.  Guard 7 sleeps through 03 and 04 twice, 03 comes first.
+        let guards = Guards::new(
+            &parse_log(
+                "[1518-11-01 23:58] Guard #7 begins shift
+                 [1518-11-02 00:01] falls asleep
+                 [1518-11-02 00:05] wakes up
+                 [1518-11-03 00:00] Guard #7 begins shift
+                 [1518-11-03 00:03] falls asleep
+                 [1518-11-03 00:09] wakes up
+                 [1518-11-04 00:00] Guard #3 begins shift
+                 [1518-11-04 00:20] falls asleep
+                 [1518-11-04 00:29] wakes up
+                 [1518-11-05 00:00] Guard #3 begins shift
+                 [1518-11-05 00:28] falls asleep
+                 [1518-11-05 00:29] wakes up",
+            )
+            .unwrap(),
+        );
+
+        assert_eq!(guards.histograms[&7].sleepiest_minute(), (3, 2));
+        assert_eq!(guards.histograms[&3].sleepiest_minute(), (28, 2));
+        assert_eq!(guards.strategy1(), Some((3, 28)));
+        assert_eq!(guards.strategy2(), Some((3, 28)));
+    }
+
+    #[test]
+    fn example1() {
+        let input = input::read!("test_input.txt").unwrap();
+        assert_eq!(super::part1(&input).unwrap(), 240);
+    }
+
+    #[test]
+    fn part1() {
+        let input = input::read!("input.txt").unwrap();
+        assert_eq!(super::part1(&input).unwrap(), 138280);
+    }
+
+    #[test]
+    fn example2() {
+        let input = input::read!("test_input.txt").unwrap();
+        assert_eq!(super::part2(&input).unwrap(), 4455);
+    }
+
+    #[test]
+    fn part2() {
+        let input = input::read!("input.txt").unwrap();
+        assert_eq!(super::part2(&input).unwrap(), 89347);
+    }
+}
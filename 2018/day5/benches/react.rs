@@ -0,0 +1,20 @@
+use aoc2018_day5::{fully_react, reduce, removal_lengths};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+fn react(c: &mut Criterion) {
+    let polymer = input::embed!("input.txt").trim();
+
+    let mut group = c.benchmark_group("react");
+    group.sample_size(10);
+    group.bench_function("passes", |b| {
+        b.iter(|| fully_react(polymer.chars().collect()).len())
+    });
+    group.bench_function("stack", |b| b.iter(|| reduce(polymer.as_bytes()).len()));
+    group.bench_function("removals", |b| {
+        b.iter(|| removal_lengths(polymer.as_bytes()).len())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, react);
+criterion_main!(benches);
@@ -0,0 +1,189 @@
+use parser::nom::character::complete::alpha1;
+use parser::{parse_all, ParseError};
+use solution::{ParseResult, PartResult, Solution};
+use std::fmt::Display;
+use std::thread;
+
+fn reacts(a: char, b: char) -> bool {
+    a != b && a.eq_ignore_ascii_case(&b)
+}
+
+// One pass over the polymer, removing reacting pairs.  Ok if anything reacted.
+fn react(chars: Vec<char>) -> Result<Vec<char>, Vec<char>> {
+    let mut new_chars: Vec<char> = Vec::with_capacity(chars.len());
+
+    let mut iter = chars.iter().peekable();
+    let mut changed = false;
+
+    loop {
+        match (iter.next(), iter.peek()) {
+            (Some(&this_val), Some(&&that_val)) => {
+                if reacts(this_val, that_val) {
+                    changed = true;
+
+                    //consume the peeked val
+                    iter.next();
+                    continue;
+                }
+                new_chars.push(this_val);
+            }
+            // the last pair may have just reacted, leaving nothing behind
+            (this, _) => {
+                new_chars.extend(this);
+                break;
+            }
+        }
+    }
+    if changed {
+        Ok(new_chars)
+    } else {
+        Err(new_chars)
+    }
+}
+
+// The original approach, kept to benchmark against: sweep the whole polymer,
+// removing pairs, until a sweep changes nothing
+pub fn fully_react(mut chars: Vec<char>) -> Vec<char> {
+    loop {
+        match react(chars) {
+            Ok(c) => chars = c,
+            Err(c) => return c,
+        }
+    }
+}
+
+// One pass with a stack.  A unit either reacts with the unit on top, and both go, or
+// is pushed, so what's left on the stack never reacts with itself.
+pub fn reduce(polymer: &[u8]) -> Vec<u8> {
+    let mut stack: Vec<u8> = Vec::with_capacity(polymer.len());
+    for &unit in polymer {
+        match stack.last() {
+            Some(&top) if top != unit && top.eq_ignore_ascii_case(&unit) => {
+                stack.pop();
+            }
+            _ => stack.push(unit),
+        }
+    }
+    stack
+}
+
+// How long the polymer ends up with each unit type taken out.  Removing a type can't
+// stop an existing reaction, so it starts from the already reduced polymer.
+pub fn removal_lengths(polymer: &[u8]) -> Vec<(char, usize)> {
+    let reduced = reduce(polymer);
+
+    thread::scope(|scope| {
+        let handles: Vec<_> = (b'a'..=b'z')
+            .map(|letter| {
+                let reduced = &reduced;
+                scope.spawn(move || {
+                    let removed: Vec<u8> = reduced
+                        .iter()
+                        .cloned()
+                        .filter(|unit| unit.to_ascii_lowercase() != letter)
+                        .collect();
+                    (letter as char, reduce(&removed).len())
+                })
+            })
+            .collect();
+
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    })
+}
+
+fn parse(input: &str) -> Result<&[u8], ParseError> {
+    parse_all(input, alpha1).map(str::as_bytes)
+}
+
+// The shortest polymer left after taking out every unit of one type
+pub fn shortest_removal(polymer: &[u8]) -> usize {
+    removal_lengths(polymer)
+        .iter()
+        .map(|&(_, length)| length)
+        .min()
+        .unwrap()
+}
+
+pub fn part1(input: &str) -> Result<usize, ParseError> {
+    Ok(reduce(parse(input)?).len())
+}
+
+pub fn part2(input: &str) -> Result<usize, ParseError> {
+    Ok(shortest_removal(parse(input)?))
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = Vec<u8>;
+
+    fn parse(input: &str) -> ParseResult<Vec<u8>> {
+        Ok(parse(input)?.to_vec())
+    }
+
+    fn part1(polymer: &Vec<u8>) -> PartResult<impl Display> {
+        Ok(reduce(polymer).len())
+    }
+
+    fn part2(polymer: &Vec<u8>) -> PartResult<impl Display> {
+        Ok(shortest_removal(polymer))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example1() {
+        let input = input::read!("test_input.txt").unwrap();
+        assert_eq!(super::part1(&input).unwrap(), 10);
+    }
+
+    #[test]
+    fn part1() {
+        let input = input::read!("input.txt").unwrap();
+        assert_eq!(super::part1(&input).unwrap(), 10496);
+    }
+
+    #[test]
+    fn matches_passes() {
+        for input in ["dabAcCaCBAcCcaDA", "aA", "abBA", "abAB", "aabAAB", ""].iter() {
+            let passes: String = fully_react(input.chars().collect()).into_iter().collect();
+            assert_eq!(String::from_utf8(reduce(input.as_bytes())).unwrap(), passes);
+        }
+
+        let input = input::read!("test_input.txt").unwrap();
+        assert_eq!(reduce(input.trim().as_bytes()), b"dabCBAcaDA");
+    }
+
+    #[test]
+    fn removals() {
+        let input = input::read!("test_input.txt").unwrap();
+        let lengths = removal_lengths(input.trim().as_bytes());
+
+        assert_eq!(lengths.len(), 26);
+        assert_eq!(&lengths[..4], &[('a', 6), ('b', 8), ('c', 4), ('d', 6)]);
+        // letters that aren't there leave the reduced polymer as it was
+        assert_eq!(lengths[25], ('z', 10));
+    }
+
+    #[test]
+    fn example2() {
+        let input = input::read!("test_input.txt").unwrap();
+        assert_eq!(super::part2(&input).unwrap(), 4);
+    }
+
+    #[test]
+    fn part2() {
+        let input = input::read!("input.txt").unwrap();
+        assert_eq!(super::part2(&input).unwrap(), 5774);
+    }
+
+    #[test]
+    fn bad_unit() {
+        let error = parse("dabAc1CaCBAcCcaDA").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 6));
+        assert_eq!(error.expected, "end of input");
+    }
+}
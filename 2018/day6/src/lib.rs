@@ -0,0 +1,220 @@
+use parser::nom::{bytes::complete::tag, combinator::map, sequence::separated_pair};
+use parser::{integer, lines, parse_all, ParseError, Parsed};
+use solution::{ParseResult, PartResult, Solution};
+use std::fmt::Display;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Coord {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Coord {
+    fn get_distance(&self, x: i32, y: i32) -> i32 {
+        (self.x - x).abs() + (self.y - y).abs()
+    }
+}
+
+// "1, 6"
+fn coord(input: &str) -> Parsed<'_, Coord> {
+    map(separated_pair(integer, tag(", "), integer), |(x, y)| {
+        Coord { x, y }
+    })(input)
+}
+
+pub fn parse(input: &str) -> Result<Vec<Coord>, ParseError> {
+    parse_all(input, lines(coord))
+}
+
+// The box from the top-left to the bottom-right coord, inclusive
+fn bounds(coords: &[Coord]) -> (Coord, Coord) {
+    let min = Coord {
+        x: coords.iter().map(|c| c.x).min().unwrap(),
+        y: coords.iter().map(|c| c.y).min().unwrap(),
+    };
+    let max = Coord {
+        x: coords.iter().map(|c| c.x).max().unwrap(),
+        y: coords.iter().map(|c| c.y).max().unwrap(),
+    };
+    (min, max)
+}
+
+// Which coord each square in the bounding box is closest to, None on a tie
+pub struct Voronoi {
+    pub min: Coord,
+    pub max: Coord,
+    pub owners: Vec<Vec<Option<usize>>>,
+}
+
+impl Voronoi {
+    pub fn new(coords: &[Coord]) -> Self {
+        let (min, max) = bounds(coords);
+
+        let owners = (min.y..=max.y)
+            .map(|y| {
+                (min.x..=max.x)
+                    .map(|x| {
+                        let mut closest = None;
+                        let mut min_dist = i32::MAX;
+                        for (i, coord) in coords.iter().enumerate() {
+                            let dist = coord.get_distance(x, y);
+                            if dist < min_dist {
+                                min_dist = dist;
+                                closest = Some(i);
+                            } else if dist == min_dist {
+                                closest = None;
+                            }
+                        }
+                        closest
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Voronoi { min, max, owners }
+    }
+
+    // The area of each coord's region, None if it's infinite.  Walking away from the
+    // box from a square on its edge, every coord gets further away at the same rate,
+    // so whoever owns an edge square owns everything out past it too.
+    pub fn areas(&self, coords: &[Coord]) -> Vec<Option<usize>> {
+        let mut areas = vec![Some(0); coords.len()];
+        let last_row = self.owners.len() - 1;
+
+        for (y, row) in self.owners.iter().enumerate() {
+            let last_column = row.len() - 1;
+            for (x, owner) in row.iter().enumerate() {
+                if let Some(i) = *owner {
+                    if y == 0 || y == last_row || x == 0 || x == last_column {
+                        areas[i] = None;
+                    } else if let Some(area) = areas[i].as_mut() {
+                        *area += 1;
+                    }
+                }
+            }
+        }
+        areas
+    }
+}
+
+pub fn largest_finite_area(coords: &[Coord]) -> usize {
+    Voronoi::new(coords)
+        .areas(coords)
+        .into_iter()
+        .flatten()
+        .max()
+        .unwrap_or(0)
+}
+
+// Total distance to every coord along one axis, for each position from `from` on
+fn axis_totals(positions: &[i32], from: i32, to: i32) -> Vec<i32> {
+    (from..=to)
+        .map(|p| positions.iter().map(|q| (p - q).abs()).sum())
+        .collect()
+}
+
+// Squares whose total distance to every coord is under the limit.  Each step past
+// the edge of the box puts a square one further from every coord, so the region
+// can reach limit / coords squares beyond it.  The x and y parts of the distance
+// are independent, so each is worked out once and the pairs counted.
+pub fn safe_region(coords: &[Coord], limit: i32) -> usize {
+    let (min, max) = bounds(coords);
+    let margin = limit / coords.len() as i32 + 1;
+
+    let xs: Vec<i32> = coords.iter().map(|c| c.x).collect();
+    let ys: Vec<i32> = coords.iter().map(|c| c.y).collect();
+    let x_totals = axis_totals(&xs, min.x - margin, max.x + margin);
+    let mut y_totals = axis_totals(&ys, min.y - margin, max.y + margin);
+    y_totals.sort_unstable();
+
+    x_totals
+        .iter()
+        .map(|x_total| y_totals.partition_point(|y_total| x_total + y_total < limit))
+        .sum()
+}
+
+pub fn part1(input: &str) -> Result<usize, ParseError> {
+    Ok(largest_finite_area(&parse(input)?))
+}
+
+pub fn part2(input: &str) -> Result<usize, ParseError> {
+    Ok(safe_region(&parse(input)?, 10000))
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = Vec<Coord>;
+
+    fn parse(input: &str) -> ParseResult<Vec<Coord>> {
+        Ok(parse(input)?)
+    }
+
+    fn part1(coords: &Vec<Coord>) -> PartResult<impl Display> {
+        Ok(largest_finite_area(coords))
+    }
+
+    fn part2(coords: &Vec<Coord>) -> PartResult<impl Display> {
+        Ok(safe_region(coords, 10000))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example1() {
+        let input = input::read!("test_input.txt").unwrap();
+        let coords = parse(&input).unwrap();
+
+        assert_eq!(
+            Voronoi::new(&coords).areas(&coords),
+            vec![None, None, None, Some(9), Some(17), None]
+        );
+        assert_eq!(super::part1(&input).unwrap(), 17);
+    }
+
+    #[test]
+    fn part1() {
+        let input = input::read!("input.txt").unwrap();
+        assert_eq!(super::part1(&input).unwrap(), 3223);
+    }
+
+    #[test]
+    fn example2() {
+        let input = input::read!("test_input.txt").unwrap();
+        assert_eq!(safe_region(&parse(&input).unwrap(), 32), 16);
+    }
+
+    #[test]
+    fn part2() {
+        let input = input::read!("input.txt").unwrap();
+        assert_eq!(super::part2(&input).unwrap(), 40495);
+    }
+
+    #[test]
+    fn safe_region_past_the_coords() {
+        // a diamond of radius 2 around a lone coord, which is its own bounding box
+        let lone = [Coord { x: 5, y: -3 }];
+        assert_eq!(safe_region(&lone, 3), 13);
+
+        // compared with checking every square in a box big enough to hold it all
+        let input = input::read!("test_input.txt").unwrap();
+        let coords = parse(&input).unwrap();
+        for &limit in [32, 50, 100].iter() {
+            let brute = (-60..=70)
+                .flat_map(|y| (-60..=70).map(move |x| (x, y)))
+                .filter(|&(x, y)| coords.iter().map(|c| c.get_distance(x, y)).sum::<i32>() < limit)
+                .count();
+            assert_eq!(safe_region(&coords, limit), brute);
+        }
+    }
+
+    #[test]
+    fn bad_coord() {
+        let error = parse("1, 1\n1,6\n8, 3").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        assert_eq!(error.expected, "tag");
+    }
+}
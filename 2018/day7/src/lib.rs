@@ -0,0 +1,308 @@
+use parser::nom::{
+    bytes::complete::tag,
+    character::complete::satisfy,
+    error::context,
+    sequence::{delimited, preceded, tuple},
+};
+use parser::{lines, parse_all, ParseError, Parsed};
+use solution::{ParseResult, PartResult, Solution};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap};
+use std::error::Error;
+use std::fmt::{self, Display};
+
+// For each step, the steps waiting on it and how many steps it's still waiting on
+#[derive(Debug, Clone, Default)]
+pub struct Instructions {
+    dependents: BTreeMap<char, Vec<char>>,
+    waiting_on: BTreeMap<char, usize>,
+}
+
+// The steps that could never start, being part of or stuck behind a cycle
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cycle {
+    pub steps: Vec<char>,
+}
+
+impl fmt::Display for Cycle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let steps: String = self.steps.iter().collect();
+        write!(f, "steps {} wait on a cycle and can never start", steps)
+    }
+}
+
+impl Error for Cycle {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum InstructionsError {
+    Parse(ParseError),
+    Cycle(Cycle),
+}
+
+impl fmt::Display for InstructionsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InstructionsError::Parse(e) => write!(f, "{}", e),
+            InstructionsError::Cycle(cycle) => write!(f, "{}", cycle),
+        }
+    }
+}
+
+impl Error for InstructionsError {}
+
+impl From<ParseError> for InstructionsError {
+    fn from(e: ParseError) -> Self {
+        InstructionsError::Parse(e)
+    }
+}
+
+impl From<Cycle> for InstructionsError {
+    fn from(cycle: Cycle) -> Self {
+        InstructionsError::Cycle(cycle)
+    }
+}
+
+// Steps are named A to Z, which also sets how long each one takes
+fn step(input: &str) -> Parsed<'_, char> {
+    context("step A to Z", satisfy(|c| c.is_ascii_uppercase()))(input)
+}
+
+// "Step C must be finished before step A can begin."
+fn requirement(input: &str) -> Parsed<'_, (char, char)> {
+    tuple((
+        preceded(tag("Step "), step),
+        delimited(
+            tag(" must be finished before step "),
+            step,
+            tag(" can begin."),
+        ),
+    ))(input)
+}
+
+pub fn parse(input: &str) -> Result<Instructions, InstructionsError> {
+    let mut instructions = Instructions::default();
+    for (depends, name) in parse_all(input, lines(requirement))? {
+        instructions
+            .dependents
+            .entry(depends)
+            .or_insert_with(Vec::new)
+            .push(name);
+        instructions.dependents.entry(name).or_insert_with(Vec::new);
+        *instructions.waiting_on.entry(name).or_insert(0) += 1;
+        instructions.waiting_on.entry(depends).or_insert(0);
+    }
+
+    // Run through the steps once so the parts never meet a cycle
+    let mut ready = Ready::new(&instructions);
+    while let Some(step) = ready.pop() {
+        ready.complete(&instructions, step);
+    }
+    ready.finish()?;
+    Ok(instructions)
+}
+
+// Steps are handed out alphabetically as they become ready
+struct Ready {
+    waiting_on: BTreeMap<char, usize>,
+    heap: BinaryHeap<Reverse<char>>,
+}
+
+impl Ready {
+    fn new(instructions: &Instructions) -> Self {
+        let waiting_on = instructions.waiting_on.clone();
+        let heap = waiting_on
+            .iter()
+            .filter(|(_, &count)| count == 0)
+            .map(|(&step, _)| Reverse(step))
+            .collect();
+        Ready { waiting_on, heap }
+    }
+
+    fn pop(&mut self) -> Option<char> {
+        self.heap.pop().map(|Reverse(step)| step)
+    }
+
+    fn complete(&mut self, instructions: &Instructions, step: char) {
+        self.waiting_on.remove(&step);
+        for next in instructions.dependents[&step].iter() {
+            let count = self.waiting_on.get_mut(next).unwrap();
+            *count -= 1;
+            if *count == 0 {
+                self.heap.push(Reverse(*next));
+            }
+        }
+    }
+
+    // Anything never finished is stuck in a cycle
+    fn finish(self) -> Result<(), Cycle> {
+        if self.waiting_on.is_empty() {
+            Ok(())
+        } else {
+            Err(Cycle {
+                steps: self.waiting_on.keys().cloned().collect(),
+            })
+        }
+    }
+}
+
+// The order one worker does the steps in, always picking the first ready step
+// alphabetically
+pub fn order(instructions: &Instructions) -> String {
+    let mut ready = Ready::new(instructions);
+    let mut order = String::new();
+
+    while let Some(step) = ready.pop() {
+        ready.complete(instructions, step);
+        order.push(step);
+    }
+    order
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Schedule {
+    pub time: u32,
+    // What each worker is doing during each second
+    pub timeline: Vec<Vec<Option<char>>>,
+}
+
+// Step A takes base + 1 seconds, B base + 2 and so on
+fn duration(step: char, base: u32) -> u32 {
+    base + (step as u32 - 'A' as u32 + 1)
+}
+
+pub fn schedule(instructions: &Instructions, workers: usize, base: u32) -> Schedule {
+    let mut ready = Ready::new(instructions);
+    let mut busy: Vec<Option<(char, u32)>> = vec![None; workers]; // (step, seconds left)
+    let mut timeline = Vec::new();
+
+    loop {
+        for worker in busy.iter_mut().filter(|w| w.is_none()) {
+            *worker = ready.pop().map(|step| (step, duration(step, base)));
+        }
+
+        if busy.iter().all(|w| w.is_none()) {
+            break;
+        }
+        timeline.push(busy.iter().map(|w| w.map(|(step, _)| step)).collect());
+
+        for worker in busy.iter_mut() {
+            if let Some((step, left)) = worker {
+                *left -= 1;
+                if *left == 0 {
+                    ready.complete(instructions, *step);
+                    *worker = None;
+                }
+            }
+        }
+    }
+
+    Schedule {
+        time: timeline.len() as u32,
+        timeline,
+    }
+}
+
+pub fn part1(input: &str) -> Result<String, InstructionsError> {
+    Ok(order(&parse(input)?))
+}
+
+pub fn part2(input: &str) -> Result<u32, InstructionsError> {
+    Ok(schedule(&parse(input)?, 5, 60).time)
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Instructions;
+
+    fn parse(input: &str) -> ParseResult<Instructions> {
+        Ok(parse(input)?)
+    }
+
+    fn part1(instructions: &Instructions) -> PartResult<impl Display> {
+        Ok(order(instructions))
+    }
+
+    fn part2(instructions: &Instructions) -> PartResult<impl Display> {
+        Ok(schedule(instructions, 5, 60).time)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example1() {
+        let input = input::read!("test_input.txt").unwrap();
+        assert_eq!(super::part1(&input).unwrap(), "CABDFE");
+    }
+
+    #[test]
+    fn part1() {
+        let input = input::read!("input.txt").unwrap();
+        assert_eq!(super::part1(&input).unwrap(), "BCADPVTJFZNRWXHEKSQLUYGMIO");
+    }
+
+    #[test]
+    fn example2() {
+        let input = input::read!("test_input.txt").unwrap();
+        let schedule = schedule(&parse(&input).unwrap(), 2, 0);
+        assert_eq!(schedule.time, 15);
+
+        let timeline: Vec<String> = schedule
+            .timeline
+            .iter()
+            .map(|second| second.iter().map(|w| w.unwrap_or('.')).collect())
+            .collect();
+        assert_eq!(
+            timeline,
+            vec![
+                "C.", "C.", "C.", "AF", "BF", "BF", "DF", "DF", "DF", "D.", "E.", "E.", "E.", "E.",
+                "E."
+            ]
+        );
+    }
+
+    #[test]
+    fn part2() {
+        let input = input::read!("input.txt").unwrap();
+        assert_eq!(super::part2(&input), Ok(973));
+    }
+
+    #[test]
+    fn cycles() {
+        let input = "Step A must be finished before step B can begin.
+Step B must be finished before step C can begin.
+Step C must be finished before step B can begin.
+Step C must be finished before step D can begin.";
+        let cycle = Cycle {
+            steps: vec!['B', 'C', 'D'],
+        };
+
+        assert_eq!(parse(input).unwrap_err(), InstructionsError::Cycle(cycle));
+        assert_eq!(
+            Day7::parse(input).err().unwrap().to_string(),
+            "steps BCD wait on a cycle and can never start"
+        );
+    }
+
+    #[test]
+    fn bad_step() {
+        let input = "Step A must be finished before step B can begin.
+Step B must be finished before step c can begin.";
+        match parse(input).unwrap_err() {
+            InstructionsError::Parse(e) => {
+                assert_eq!((e.line, e.column), (2, 37));
+                assert_eq!(e.expected, "step A to Z");
+            }
+            e => panic!("Expected a parse error, got {}", e),
+        }
+        assert_eq!(
+            super::part2("Step A must be finished before step 1 can begin.")
+                .unwrap_err()
+                .to_string(),
+            "line 1, column 37: expected step A to Z, found \"1 can begin.\""
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use parser::nom::character::complete::space1;
+use parser::nom::multi::count;
+use parser::nom::sequence::{pair, preceded};
+use parser::{integer, parse_all, ParseError, Parsed};
+use solution::{ParseResult, PartResult, Solution};
+use std::fmt::Display;
+#[derive(Debug)]
+pub struct Node {
+    child_nodes: Vec<Node>,
+    metadata_entries: Vec<i32>,
+    num_child_nodes: i32,
+}
+
+impl Node {
+    fn collect_metadata(&self) -> i32 {
+        let sum: i32 = self.metadata_entries.iter().sum();
+
+        sum + self
+            .child_nodes
+            .iter()
+            .fold(0i32, |sum, v| sum + v.collect_metadata())
+    }
+
+    fn compute_value(&self) -> i32 {
+        let mut value = 0i32;
+
+        if self.num_child_nodes > 0 {
+            for entry in &self.metadata_entries {
+                if *entry == 0 {
+                    continue;
+                }
+                value += match self.child_nodes.get(*entry as usize - 1) {
+                    Some(v) => v.compute_value(),
+                    None => 0,
+                };
+            }
+        } else {
+            value = self.metadata_entries.iter().sum();
+        }
+        value
+    }
+}
+
+// A header with how many children and metadata entries there are, then the
+// children, then the entries
+fn node(input: &str) -> Parsed<'_, Node> {
+    let (input, (num_child_nodes, num_metadata)) =
+        pair(integer::<usize>, preceded(space1, integer::<usize>))(input)?;
+    let (input, child_nodes) = count(preceded(space1, node), num_child_nodes)(input)?;
+    let (input, metadata_entries) = count(preceded(space1, integer), num_metadata)(input)?;
+    let node = Node {
+        child_nodes,
+        metadata_entries,
+        num_child_nodes: num_child_nodes as i32,
+    };
+    Ok((input, node))
+}
+
+fn parse(input: &str) -> Result<Node, ParseError> {
+    parse_all(input, node)
+}
+
+pub fn part1(input: &str) -> Result<i32, ParseError> {
+    Ok(parse(input)?.collect_metadata())
+}
+
+pub fn part2(input: &str) -> Result<i32, ParseError> {
+    Ok(parse(input)?.compute_value())
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = Node;
+
+    fn parse(input: &str) -> ParseResult<Node> {
+        Ok(parse(input)?)
+    }
+
+    fn part1(root: &Node) -> PartResult<impl Display> {
+        Ok(root.collect_metadata())
+    }
+
+    fn part2(root: &Node) -> PartResult<impl Display> {
+        Ok(root.compute_value())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    #[test]
+    fn example1() {
+        let input = input::read!("test_input.txt").unwrap();
+        assert_eq!(super::part1(&input).unwrap(), 138);
+    }
+
+    #[test]
+    fn part1() {
+        let input = input::read!("input.txt").unwrap();
+        assert_eq!(super::part1(&input).unwrap(), 42501);
+    }
+
+    #[test]
+    fn example2() {
+        let input = input::read!("test_input.txt").unwrap();
+        assert_eq!(super::part2(&input).unwrap(), 66);
+    }
+
+    #[test]
+    fn part2() {
+        let input = input::read!("input.txt").unwrap();
+        assert_eq!(super::part2(&input).unwrap(), 30857);
+    }
+
+    #[test]
+    fn bad_tree() {
+        // the second child is missing its last metadata entry
+        let error = super::parse("2 3 0 3 10 11 12 1 1 0 1 99 1 1 2").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 34));
+
+        let error = super::parse("0 1 99 1").unwrap_err();
+        assert_eq!(error.expected, "end of input");
+    }
+}
@@ -1,8 +1,12 @@
-fn calculate_module(mass: i32) -> i32 {
+use parser::{integer, lines, parse_all, ParseError};
+use solution::{ParseResult, PartResult, Solution};
+use std::fmt::Display;
+
+pub fn calculate_module(mass: i32) -> i32 {
     (mass / 3) - 2
 }
 
-fn calculate_fuel(mass: i32) -> i32 {
+pub fn calculate_fuel(mass: i32) -> i32 {
     let mut total_fuel = calculate_module(mass);
     let mut fuel_fuel = calculate_module(total_fuel);
     while fuel_fuel > 0 {
@@ -13,6 +17,31 @@ fn calculate_fuel(mass: i32) -> i32 {
     total_fuel
 }
 
+pub fn parse_masses(input: &str) -> Result<Vec<i32>, ParseError> {
+    parse_all(input, lines(integer))
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<i32>;
+
+    fn parse(input: &str) -> ParseResult<Vec<i32>> {
+        Ok(parse_masses(input)?)
+    }
+
+    fn part1(masses: &Vec<i32>) -> PartResult<impl Display> {
+        Ok(masses
+            .iter()
+            .map(|&mass| calculate_module(mass))
+            .sum::<i32>())
+    }
+
+    fn part2(masses: &Vec<i32>) -> PartResult<impl Display> {
+        Ok(masses.iter().map(|&mass| calculate_fuel(mass)).sum::<i32>())
+    }
+}
+
 #[test]
 fn examples1() {
     assert_eq!(calculate_module(12), 2);
@@ -29,13 +58,8 @@ fn examples2() {
 }
 
 #[test]
-fn part_1() -> Result<(), std::io::Error> {
-    use std::fs::File;
-    use std::io::Read;
-
-    let mut file = File::open("input.txt")?;
-    let mut contents = String::new();
-    file.read_to_string(&mut contents)?;
+fn part_1() -> Result<(), input::InputError> {
+    let contents = input::read!("input.txt")?;
 
     let collection: Vec<&str> = contents.split('\n').collect();
 
@@ -50,13 +74,8 @@ fn part_1() -> Result<(), std::io::Error> {
 }
 
 #[test]
-fn part_2() -> Result<(), std::io::Error> {
-    use std::fs::File;
-    use std::io::Read;
-
-    let mut file = File::open("input.txt")?;
-    let mut contents = String::new();
-    file.read_to_string(&mut contents)?;
+fn part_2() -> Result<(), input::InputError> {
+    let contents = input::read!("input.txt")?;
 
     let collection: Vec<&str> = contents.split('\n').collect();
 
@@ -69,3 +88,12 @@ fn part_2() -> Result<(), std::io::Error> {
 
     Ok(())
 }
+
+#[test]
+fn bad_mass() {
+    let error = parse_masses("12\n14\n19 69").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "line 3, column 3: expected end of line, found \" 69\""
+    );
+}
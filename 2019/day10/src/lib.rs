@@ -1,93 +1,152 @@
-use noisy_float::prelude::*;
+use grid::{Grid, GridError};
+use solution::{ParseResult, PartResult, Solution};
+use std::cmp::Ordering;
 use std::collections::BTreeMap;
 use std::collections::HashSet;
+use std::fmt::Display;
+
+type Position = (usize, usize);
 
 pub struct Map {
     pub asteroids: Vec<(usize, usize)>, // (x, y)
     pub max_x: usize,
     pub max_y: usize,
 }
-const PI: f64 = std::f64::consts::PI;
 
-impl Map {
-    pub fn new(input: &str) -> Self {
-        let mut this_map = Self {
-            asteroids: Vec::new(),
-            max_x: 0,
-            max_y: 0,
-        };
-
-        let lines = input.split('\n');
-
-        for (y, line) in lines.enumerate() {
-            for (x, val) in line.chars().filter(|v| *v != ' ').enumerate() {
-                if val == '#' {
-                    this_map.asteroids.push((x, y));
-                }
-                this_map.max_x = x;
-            }
-            this_map.max_y = y;
+fn gcd(a: isize, b: isize) -> isize {
+    if b == 0 {
+        a.abs()
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+// A direction reduced by the gcd of its components, so every asteroid along the
+// same line of sight shares one.  Ordered clockwise starting from straight up
+// (remembering y grows downwards).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Direction {
+    pub dx: isize,
+    pub dy: isize,
+}
+
+impl Direction {
+    // Returns the direction and how many steps along it `(dx, dy)` is
+    fn reduce(dx: isize, dy: isize) -> (Self, isize) {
+        let steps = gcd(dx, dy);
+        (
+            Self {
+                dx: dx / steps,
+                dy: dy / steps,
+            },
+            steps,
+        )
+    }
+
+    // 0 for straight up through to just before straight down, 1 for the rest
+    fn half(&self) -> u8 {
+        if self.dx > 0 || (self.dx == 0 && self.dy < 0) {
+            0
+        } else {
+            1
         }
+    }
+}
 
-        this_map
+impl Ord for Direction {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.half().cmp(&other.half()).then_with(|| {
+            // within a half-turn, a positive cross product means `other` is further clockwise
+            let cross = self.dx * other.dy - self.dy * other.dx;
+            0.cmp(&cross)
+        })
     }
+}
 
-    pub fn vaporize_asteroids(&self, position: (usize, usize)) {
+impl PartialOrd for Direction {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Map {
+    // Lines are trimmed, and blank ones skipped, so the map can be indented
+    pub fn new(input: &str) -> Result<Self, GridError> {
+        let rows: String = input
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(|line| line.to_string() + "\n")
+            .collect();
+        let grid = Grid::try_parse(&rows, |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })?;
+
+        Ok(Self {
+            asteroids: grid.points().filter(|&p| grid[p]).collect(),
+            max_x: grid.width().saturating_sub(1),
+            max_y: grid.height().saturating_sub(1),
+        })
+    }
+
+    pub fn vaporize_asteroids(&self, position: (usize, usize)) -> Vec<(usize, usize)> {
         let mut relative_positions = self.get_relative_positions(position);
-        dbg!(&relative_positions);
 
-        for (_angle, distances) in &mut relative_positions {
-            distances.sort_unstable();
+        // furthest first, so the closest can be popped off the end
+        for asteroids in relative_positions.values_mut() {
+            asteroids.sort_unstable_by_key(|&(steps, _)| std::cmp::Reverse(steps));
         }
 
-        let mut deletion = 1;
-        // loop {
-        for (angle, distances) in &mut relative_positions {
-            let d = distances.get(0).unwrap();
-            println!(
-                "({})\tZapping {},{}\t({})",
-                deletion,
-                n64((position.0 as f32).into()) + (*d * angle.cos()).round(),
-                n64((position.1 as f32).into()) + (*d * angle.sin()).round(),
-                angle
-            );
-            deletion += 1;
+        let mut vaporized = Vec::new();
+        while !relative_positions.is_empty() {
+            for asteroids in relative_positions.values_mut() {
+                if let Some((_steps, asteroid)) = asteroids.pop() {
+                    vaporized.push(asteroid);
+                }
+            }
+            relative_positions.retain(|_, asteroids| !asteroids.is_empty());
         }
-        // }
+
+        vaporized
     }
 
-    fn get_relative_positions(&self, position: (usize, usize)) -> BTreeMap<N64, Vec<N64>> {
-        let mut angles = BTreeMap::new();
+    fn get_relative_positions(
+        &self,
+        position: (usize, usize),
+    ) -> BTreeMap<Direction, Vec<(isize, Position)>> {
+        let mut directions = BTreeMap::new();
         for asteroid in &self.asteroids {
-            let dx = n64((asteroid.0 as isize - position.0 as isize) as f64);
-            let dy = n64((asteroid.1 as isize - position.1 as isize) as f64);
-
-            let mut angle = dy.atan2(dx);
-            if angle < -PI / 2.0 {
-                angle += 2.0 * PI;
+            if *asteroid == position {
+                continue;
             }
 
-            // println!("dx: {}, dy: {}, angle: {}", &dx, &dy, &angle);
+            let dx = asteroid.0 as isize - position.0 as isize;
+            let dy = asteroid.1 as isize - position.1 as isize;
 
-            let r = (dy * dy + dx * dx).sqrt();
-            if r > 0.0 {
-                let e = angles.entry(angle).or_insert(Vec::new());
-                e.push(r);
-            }
+            let (direction, steps) = Direction::reduce(dx, dy);
+            directions
+                .entry(direction)
+                .or_insert_with(Vec::new)
+                .push((steps, *asteroid));
         }
-        angles
+        directions
     }
 
     pub fn count_asteroids(&self, position: (usize, usize)) -> usize {
-        let mut angles = HashSet::new();
+        let mut directions = HashSet::new();
         for asteroid in &self.asteroids {
-            let dx = n64((asteroid.0 as isize - position.0 as isize) as f64);
+            if *asteroid == position {
+                continue;
+            }
 
-            let dy = n64((asteroid.1 as isize - position.1 as isize) as f64);
+            let dx = asteroid.0 as isize - position.0 as isize;
+            let dy = asteroid.1 as isize - position.1 as isize;
 
-            angles.insert(dy.atan2(dx));
+            directions.insert(Direction::reduce(dx, dy).0);
         }
-        angles.len()
+        directions.len()
     }
 
     pub fn find_best_location(&self) -> (usize, usize) {
@@ -107,9 +166,34 @@ impl Map {
     }
 }
 
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Map;
+
+    fn parse(input: &str) -> ParseResult<Map> {
+        Ok(Map::new(input)?)
+    }
+
+    // Asteroids seen from the best place for the station
+    fn part1(map: &Map) -> PartResult<impl Display> {
+        Ok(map.count_asteroids(map.find_best_location()))
+    }
+
+    // 100 * x + y of the 200th asteroid to be vaporized
+    fn part2(map: &Map) -> PartResult<impl Display> {
+        let (x, y) = *map
+            .vaporize_asteroids(map.find_best_location())
+            .get(199)
+            .ok_or("There aren't 200 asteroids to vaporize")?;
+        Ok(x * 100 + y)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::Map;
+    use grid::GridError;
 
     #[test]
     fn example2_2() {
@@ -121,12 +205,28 @@ mod tests {
             ..#.#.....#....##
             
             ",
-        );
+        )
+        .unwrap();
 
         let position = grid.find_best_location();
         assert_eq!(position, (8, 3));
 
-        grid.vaporize_asteroids(position);
+        let vaporized = grid.vaporize_asteroids(position);
+        assert_eq!(
+            &vaporized[..9],
+            &[
+                (8, 1),
+                (9, 0),
+                (9, 1),
+                (10, 0),
+                (9, 2),
+                (11, 1),
+                (12, 1),
+                (11, 2),
+                (15, 1)
+            ]
+        );
+        assert_eq!(vaporized.len(), grid.asteroids.len() - 1);
     }
 
     #[test]
@@ -153,54 +253,32 @@ mod tests {
             #.#.#.#####.####.###
             ###.##.####.##.#..##
             ",
-        );
+        )
+        .unwrap();
 
         let position = grid.find_best_location();
         assert_eq!(position, (11, 13));
 
         assert_eq!(210, grid.count_asteroids(position));
 
-        grid.vaporize_asteroids(position);
+        let vaporized = grid.vaporize_asteroids(position);
+        assert_eq!(vaporized[0], (11, 12));
+        assert_eq!(vaporized[1], (12, 1));
+        assert_eq!(vaporized[2], (12, 2));
+        assert_eq!(vaporized[9], (12, 8));
+        assert_eq!(vaporized[19], (16, 0));
+        assert_eq!(vaporized[49], (16, 9));
+        assert_eq!(vaporized[99], (10, 16));
+        assert_eq!(vaporized[198], (9, 6));
+        assert_eq!(vaporized[199], (8, 2));
+        assert_eq!(vaporized[200], (10, 9));
+        assert_eq!(vaporized[298], (11, 1));
+        assert_eq!(vaporized.len(), 299);
     }
 
     #[test]
     fn part1_2() {
-        let grid = Map::new(
-            ".#......##.#..#.......#####...#..
-            ...#.....##......###....#.##.....
-            ..#...#....#....#............###.
-            .....#......#.##......#.#..###.#.
-            #.#..........##.#.#...#.##.#.#.#.
-            ..#.##.#...#.......#..##.......##
-            ..#....#.....#..##.#..####.#.....
-            #.............#..#.........#.#...
-            ........#.##..#..#..#.#.....#.#..
-            .........#...#..##......###.....#
-            ##.#.###..#..#.#.....#.........#.
-            .#.###.##..##......#####..#..##..
-            .........#.......#.#......#......
-            ..#...#...#...#.#....###.#.......
-            #..#.#....#...#.......#..#.#.##..
-            #.....##...#.###..#..#......#..##
-            ...........#...#......#..#....#..
-            #.#.#......#....#..#.....##....##
-            ..###...#.#.##..#...#.....#...#.#
-            .......#..##.#..#.............##.
-            ..###........##.#................
-            ###.#..#...#......###.#........#.
-            .......#....#.#.#..#..#....#..#..
-            .#...#..#...#......#....#.#..#...
-            #.#.........#.....#....#.#.#.....
-            .#....#......##.##....#........#.
-            ....#..#..#...#..##.#.#......#.#.
-            ..###.##.#.....#....#.#......#...
-            #.##...#............#..#.....#..#
-            .#....##....##...#......#........
-            ...#...##...#.......#....##.#....
-            .#....#.#...#.#...##....#..##.#.#
-            .#.#....##.......#.....##.##.#.##
-            ",
-        );
+        let grid = Map::new(&input::read!("input.txt").unwrap()).unwrap();
 
         let position = grid.find_best_location();
 
@@ -210,8 +288,8 @@ mod tests {
         assert_eq!(256, grid.count_asteroids(position));
 
         // Part-2
-        grid.vaporize_asteroids(position);
-        // guessed 39,12 (too high)
+        let (x, y) = grid.vaporize_asteroids(position)[199];
+        assert_eq!(1707, x * 100 + y);
     }
 
     #[test]
@@ -237,7 +315,8 @@ mod tests {
             .#.#.###########.###
             #.#.#.#####.####.###
             ###.##.####.##.#..##",
-        );
+        )
+        .unwrap();
 
         let position = grid.find_best_location();
         assert_eq!(position, (11, 13));
@@ -258,7 +337,8 @@ mod tests {
             #..#.#.###
             .##...##.#
             .....#.#..",
-        );
+        )
+        .unwrap();
 
         let position = grid.find_best_location();
         assert_eq!(position, (6, 3));
@@ -279,7 +359,8 @@ mod tests {
             ..##....##
             ......#...
             .####.###.",
-        );
+        )
+        .unwrap();
 
         let position = grid.find_best_location();
         assert_eq!(position, (1, 2));
@@ -300,7 +381,8 @@ mod tests {
             .##.#..###
             ##...#..#.
             .#....####",
-        );
+        )
+        .unwrap();
 
         let position = grid.find_best_location();
         assert_eq!(position, (5, 8));
@@ -316,7 +398,8 @@ mod tests {
             #####
             ....#
             ...##",
-        );
+        )
+        .unwrap();
 
         assert_eq!(10, grid.asteroids.len());
         assert_eq!(
@@ -342,4 +425,16 @@ mod tests {
         assert_eq!(4, grid.max_y);
         assert_eq!(8, grid.count_asteroids(position));
     }
+
+    #[test]
+    fn bad_map() {
+        assert_eq!(
+            Map::new(".#..#\n.....\n#####\n....#\n...O#").err(),
+            Some(GridError::BadCell {
+                line: 5,
+                column: 4,
+                found: 'O'
+            })
+        );
+    }
 }
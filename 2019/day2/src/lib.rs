@@ -1,41 +1,84 @@
-#[cfg(test)]
-mod tests {
+use parser::{integers, parse_all, ParseError};
+use solution::{ParseResult, PartResult, Solution};
+use std::fmt::Display;
 
-    fn add(a: i32, b: i32) -> i32 {
-        a + b
-    }
+fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
 
-    fn multiply(a: i32, b: i32) -> i32 {
-        a * b
-    }
+fn multiply(a: i32, b: i32) -> i32 {
+    a * b
+}
 
-    fn calculate(input: &[i32]) -> Vec<i32> {
-        let mut output = input.clone().to_vec();
-        let mut pos = 0;
-        loop {
-            match output[pos] {
-                1 => {
-                    let output_index = output[pos + 3] as usize;
-                    let a_index = output[pos + 1] as usize;
-                    let b_index = output[pos + 2] as usize;
-
-                    output[output_index] = add(output[a_index], output[b_index])
-                }
-                2 => {
-                    let output_index = output[pos + 3] as usize;
-                    let a_index = output[pos + 1] as usize;
-                    let b_index = output[pos + 2] as usize;
+pub fn calculate(input: &[i32]) -> Vec<i32> {
+    let mut output = input.to_vec();
+    let mut pos = 0;
+    loop {
+        match output[pos] {
+            1 => {
+                let output_index = output[pos + 3] as usize;
+                let a_index = output[pos + 1] as usize;
+                let b_index = output[pos + 2] as usize;
 
-                    output[output_index] = multiply(output[a_index], output[b_index])
-                }
-                99 => break,
-                _ => panic!("Invalid opcode"),
+                output[output_index] = add(output[a_index], output[b_index])
             }
-            pos += 4;
+            2 => {
+                let output_index = output[pos + 3] as usize;
+                let a_index = output[pos + 1] as usize;
+                let b_index = output[pos + 2] as usize;
+
+                output[output_index] = multiply(output[a_index], output[b_index])
+            }
+            99 => break,
+            _ => panic!("Invalid opcode"),
         }
+        pos += 4;
+    }
+
+    output
+}
+
+// The program's output, left in position 0, for a noun and verb
+pub fn run(program: &[i32], noun: i32, verb: i32) -> i32 {
+    let mut program = program.to_vec();
+    program[1] = noun;
+    program[2] = verb;
+    calculate(&program)[0]
+}
+
+// A comma separated Intcode program
+// Comma separated, like "1,9,10,3,2,3,11,0,99,30,40,50"
+pub fn parse_program(input: &str) -> Result<Vec<i32>, ParseError> {
+    parse_all(input, integers(","))
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<i32>;
+
+    fn parse(input: &str) -> ParseResult<Vec<i32>> {
+        Ok(parse_program(input)?)
+    }
+
+    fn part1(program: &Vec<i32>) -> PartResult<impl Display> {
+        Ok(run(program, 12, 2))
+    }
 
-        output
+    // 100 * noun + verb for the noun and verb that output 19690720
+    fn part2(program: &Vec<i32>) -> PartResult<impl Display> {
+        Ok((0..=99)
+            .flat_map(|noun| (0..=99).map(move |verb| (noun, verb)))
+            .find(|&(noun, verb)| run(program, noun, verb) == 19690720)
+            .map(|(noun, verb)| 100 * noun + verb)
+            .ok_or("No noun and verb give 19690720")?)
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
     #[test]
     fn examples1() {
         assert_eq!(
@@ -55,15 +98,7 @@ mod tests {
 
     #[test]
     fn part_1() {
-        let mut program = [
-            1, 0, 0, 3, 1, 1, 2, 3, 1, 3, 4, 3, 1, 5, 0, 3, 2, 1, 9, 19, 1, 5, 19, 23, 1, 6, 23,
-            27, 1, 27, 10, 31, 1, 31, 5, 35, 2, 10, 35, 39, 1, 9, 39, 43, 1, 43, 5, 47, 1, 47, 6,
-            51, 2, 51, 6, 55, 1, 13, 55, 59, 2, 6, 59, 63, 1, 63, 5, 67, 2, 10, 67, 71, 1, 9, 71,
-            75, 1, 75, 13, 79, 1, 10, 79, 83, 2, 83, 13, 87, 1, 87, 6, 91, 1, 5, 91, 95, 2, 95, 9,
-            99, 1, 5, 99, 103, 1, 103, 6, 107, 2, 107, 13, 111, 1, 111, 10, 115, 2, 10, 115, 119,
-            1, 9, 119, 123, 1, 123, 9, 127, 1, 13, 127, 131, 2, 10, 131, 135, 1, 135, 5, 139, 1, 2,
-            139, 143, 1, 143, 5, 0, 99, 2, 0, 14, 0,
-        ];
+        let mut program = parse_program(&input::read!("input.txt").unwrap()).unwrap();
         program[1] = 12;
         program[2] = 2;
 
@@ -74,15 +109,7 @@ mod tests {
 
     #[test]
     fn part_2() {
-        let program = [
-            1, 0, 0, 3, 1, 1, 2, 3, 1, 3, 4, 3, 1, 5, 0, 3, 2, 1, 9, 19, 1, 5, 19, 23, 1, 6, 23,
-            27, 1, 27, 10, 31, 1, 31, 5, 35, 2, 10, 35, 39, 1, 9, 39, 43, 1, 43, 5, 47, 1, 47, 6,
-            51, 2, 51, 6, 55, 1, 13, 55, 59, 2, 6, 59, 63, 1, 63, 5, 67, 2, 10, 67, 71, 1, 9, 71,
-            75, 1, 75, 13, 79, 1, 10, 79, 83, 2, 83, 13, 87, 1, 87, 6, 91, 1, 5, 91, 95, 2, 95, 9,
-            99, 1, 5, 99, 103, 1, 103, 6, 107, 2, 107, 13, 111, 1, 111, 10, 115, 2, 10, 115, 119,
-            1, 9, 119, 123, 1, 123, 9, 127, 1, 13, 127, 131, 2, 10, 131, 135, 1, 135, 5, 139, 1, 2,
-            139, 143, 1, 143, 5, 0, 99, 2, 0, 14, 0,
-        ];
+        let program = parse_program(&input::read!("input.txt").unwrap()).unwrap();
 
         for noun in 0..=99 {
             for verb in 0..=99 {
@@ -94,9 +121,20 @@ mod tests {
                 if result[0] == 19690720 {
                     println!("Noun:{}\nVerb:{}", noun, verb);
                     println!("100*noun + verb == {}", 100 * noun + verb);
+                    assert_eq!(100 * noun + verb, 8250);
                     return;
                 }
             }
         }
+        panic!("No noun and verb give 19690720");
+    }
+
+    #[test]
+    fn bad_program() {
+        assert_eq!(parse_program("1,0,0,3,99\n").unwrap(), [1, 0, 0, 3, 99]);
+
+        let error = parse_program("1,0,0,three,99").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 6));
+        assert_eq!(error.expected, "end of input");
     }
 }
@@ -1,142 +1,302 @@
-#[cfg(test)]
-mod tests {
-    use std::collections::HashMap;
-    #[derive(Debug)]
-    struct Record {
-        used: bool,
-        collision: bool,
-    }
-    impl Record {
-        fn new() -> Self {
-            Record {
-                collision: false,
-                used: false,
+use solution::{ParseResult, PartResult, Solution};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+type Position = (i32, i32);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseError {
+    Empty,
+    BadDirection(String),
+    BadLength(String),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::Empty => write!(f, "empty wire instruction"),
+            ParseError::BadDirection(token) => {
+                write!(f, "expected U, D, L or R to start \"{}\"", token)
             }
+            ParseError::BadLength(token) => write!(f, "bad length in \"{}\"", token),
         }
     }
+}
 
-    fn assign_dir(dir: u8) -> [i32; 2] {
-        match dir {
-            b'R' => [0, 1],
-            b'D' => [-1, 0],
-            b'U' => [1, 0],
-            b'L' => [0, -1],
-            _ => panic!("Bad input"),
-        }
+impl Error for ParseError {}
+
+// A straight run of wire, and how many steps along the wire it starts
+#[derive(Debug, Clone, PartialEq)]
+pub struct Segment {
+    pub start: Position,
+    pub end: Position,
+    pub steps: i32,
+}
+
+impl Segment {
+    fn min(&self) -> Position {
+        (self.start.0.min(self.end.0), self.start.1.min(self.end.1))
     }
 
-    fn get_len(len: &[u8]) -> i32 {
-        let len_str = std::str::from_utf8(len).expect("bad");
-        len_str.parse().expect("NAN")
+    fn max(&self) -> Position {
+        (self.start.0.max(self.end.0), self.start.1.max(self.end.1))
     }
 
-    fn explore(
-        memory: &mut HashMap<[i32; 2], Record>,
-        wire: &str,
-        endpoint: Option<&[i32; 2]>,
-    ) -> i32 {
-        let mut position = [0, 0];
-        let collection: Vec<&str> = wire.split(',').collect();
+    fn steps_to(&self, position: Position) -> i32 {
+        self.steps + (position.0 - self.start.0).abs() + (position.1 - self.start.1).abs()
+    }
+
+    // Both segments are axis aligned, so what they share is the overlap of their
+    // bounding boxes: nothing, a point, or a run of points if they're collinear
+    fn shared_points(&self, other: &Segment) -> Vec<Position> {
+        let (min_a, max_a) = (self.min(), self.max());
+        let (min_b, max_b) = (other.min(), other.max());
+
+        let x = (min_a.0.max(min_b.0), max_a.0.min(max_b.0));
+        let y = (min_a.1.max(min_b.1), max_a.1.min(max_b.1));
+
+        let mut points = Vec::new();
+        for px in x.0..=x.1 {
+            for py in y.0..=y.1 {
+                points.push((px, py));
+            }
+        }
+        points
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Wire {
+    pub segments: Vec<Segment>,
+}
+
+impl FromStr for Wire {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut segments = Vec::new();
+        let mut position = (0, 0);
         let mut steps = 0;
-        for c in collection {
-            let raw = c.as_bytes();
-
-            let step = assign_dir(raw[0]);
-            let len = get_len(&raw[1..]);
-
-            for _ in 0..len {
-                steps += 1;
-                position[0] += step[0];
-                position[1] += step[1];
-
-                if endpoint.is_some() {
-                    let endpoint = endpoint.unwrap();
-                    if position == *endpoint {
-                        return steps;
-                    }
-                }
 
-                let e = memory.entry(position).or_insert(Record::new());
-                if e.used == true {
-                    e.collision = true;
-                } else {
-                    e.used = true;
+        for token in input.trim().split(',').map(|t| t.trim()) {
+            let mut chars = token.chars();
+            let step = match chars.next() {
+                Some('R') => (1, 0),
+                Some('L') => (-1, 0),
+                Some('U') => (0, 1),
+                Some('D') => (0, -1),
+                Some(_) => return Err(ParseError::BadDirection(token.to_string())),
+                None => return Err(ParseError::Empty),
+            };
+            let length: i32 = match chars.as_str().parse() {
+                Ok(length) if length >= 0 => length,
+                _ => return Err(ParseError::BadLength(token.to_string())),
+            };
+
+            let end = (position.0 + step.0 * length, position.1 + step.1 * length);
+            segments.push(Segment {
+                start: position,
+                end,
+                steps,
+            });
+            position = end;
+            steps += length;
+        }
+
+        Ok(Wire { segments })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Crossing {
+    pub position: Position,
+    pub distance: i32,
+    pub delay: i32,
+}
+
+// Every point (other than the origin) where the two wires meet, with its Manhattan
+// distance and the fewest combined steps to get there.  A wire crossing itself
+// doesn't count, and when a wire passes a point more than once the first visit is
+// the one that counts.
+pub fn crossings(wire1: &Wire, wire2: &Wire) -> Vec<Crossing> {
+    let mut delays: BTreeMap<Position, i32> = BTreeMap::new();
+
+    for a in &wire1.segments {
+        for b in &wire2.segments {
+            for position in a.shared_points(b) {
+                if position == (0, 0) {
+                    continue;
                 }
+                let delay = a.steps_to(position) + b.steps_to(position);
+                let e = delays.entry(position).or_insert(delay);
+                *e = (*e).min(delay);
             }
         }
-        steps
     }
 
-    fn calculate(wire1: &str, wire2: &str) -> (i32, i32) {
-        let mut memory = HashMap::new();
-        memory.entry([0, 0]).or_insert(Record::new()).used = true;
+    delays
+        .into_iter()
+        .map(|(position, delay)| Crossing {
+            position,
+            distance: position.0.abs() + position.1.abs(),
+            delay,
+        })
+        .collect()
+}
 
-        explore(&mut memory, &wire1, None);
-        explore(&mut memory, &wire2, None);
+// The distance to the nearest crossing and the smallest combined signal delay
+pub fn calculate(wire1: &str, wire2: &str) -> Result<Option<(i32, i32)>, ParseError> {
+    let wire1: Wire = wire1.parse()?;
+    let wire2: Wire = wire2.parse()?;
 
-        // Sort out _only_ the collisions
-        let mut collisions = HashMap::new();
-        for (key, val) in memory.iter() {
-            if val.collision == true {
-                collisions
-                    .entry(key)
-                    .or_insert(i32::abs(key[0]) + i32::abs(key[1]));
-            }
+    let crossings = crossings(&wire1, &wire2);
+    let distance = crossings.iter().map(|c| c.distance).min();
+    let delay = crossings.iter().map(|c| c.delay).min();
+
+    Ok(distance.zip(delay))
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Vec<Crossing>;
+
+    fn parse(input: &str) -> ParseResult<Vec<Crossing>> {
+        let wires = input
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| line.parse().map_err(|e| format!("Invalid wire: {}", e)))
+            .collect::<Result<Vec<Wire>, _>>()?;
+        if wires.len() != 2 {
+            return Err(format!("Expected two wires, found {}", wires.len()).into());
         }
+        Ok(crossings(&wires[0], &wires[1]))
+    }
+
+    fn part1(crossings: &Vec<Crossing>) -> PartResult<impl Display> {
+        Ok(crossings
+            .iter()
+            .map(|c| c.distance)
+            .min()
+            .ok_or("The wires never cross")?)
+    }
 
-        // Find the _nearest_ collision
-        let min_dist = *collisions.values().min().expect("Fail");
+    fn part2(crossings: &Vec<Crossing>) -> PartResult<impl Display> {
+        Ok(crossings
+            .iter()
+            .map(|c| c.delay)
+            .min()
+            .ok_or("The wires never cross")?)
+    }
+}
 
-        // Now retrace the steps leading up to the nearest collision...
-        let mut min_steps = std::i32::MAX;
-        let mut memory = HashMap::new();
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        for collision in collisions.keys() {
-            let steps1 = explore(&mut memory, &wire1, Some(collision));
-            let steps2 = explore(&mut memory, &wire2, Some(collision));
-            if (steps2 + steps1) < min_steps {
-                min_steps = steps1 + steps2;
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            "R8,X5".parse::<Wire>(),
+            Err(ParseError::BadDirection("X5".to_string()))
+        );
+        assert_eq!(
+            "R8,U".parse::<Wire>(),
+            Err(ParseError::BadLength("U".to_string()))
+        );
+        assert_eq!(
+            "R8,U-3".parse::<Wire>(),
+            Err(ParseError::BadLength("U-3".to_string()))
+        );
+        assert_eq!("R8,,U3".parse::<Wire>(), Err(ParseError::Empty));
+        assert!(calculate("R8,U5", "U7,Rx").is_err());
+    }
+
+    #[test]
+    fn segments() {
+        let wire: Wire = "R8,U5,L5".parse().unwrap();
+        assert_eq!(
+            wire.segments[1],
+            Segment {
+                start: (8, 0),
+                end: (8, 5),
+                steps: 8
             }
-            println!("Collision at {}+{}=\t{}", steps1, steps2, steps1 + steps2);
-        }
-        (min_dist, min_steps)
+        );
+    }
+
+    #[test]
+    fn self_crossing() {
+        // the first wire loops back over itself at (2, 0), that's not a crossing
+        let wire1: Wire = "R4,U2,L2,D4".parse().unwrap();
+        let wire2: Wire = "U1,R6".parse().unwrap();
+
+        let found = crossings(&wire1, &wire2);
+        assert_eq!(
+            found,
+            vec![
+                Crossing {
+                    position: (2, 1),
+                    distance: 3,
+                    delay: 9 + 3
+                },
+                Crossing {
+                    position: (4, 1),
+                    distance: 5,
+                    delay: 5 + 5
+                },
+            ]
+        );
+        assert_eq!(calculate("R4,U2,L2,D4", "U1,R6"), Ok(Some((3, 10))));
+        assert_eq!(calculate("R4", "L4"), Ok(None));
+    }
+
+    #[test]
+    fn overlapping() {
+        // running along the same line crosses at every point of the overlap
+        let wire1: Wire = "R5".parse().unwrap();
+        let wire2: Wire = "U1,R3,D1,R4".parse().unwrap();
+
+        let positions: Vec<Position> = crossings(&wire1, &wire2)
+            .iter()
+            .map(|c| c.position)
+            .collect();
+        assert_eq!(positions, vec![(3, 0), (4, 0), (5, 0)]);
     }
 
     #[test]
     fn examples() {
-        assert_eq!(calculate("R8,U5,L5,D3", "U7,R6,D4,L4"), (6, 30));
-        println!();
+        assert_eq!(calculate("R8,U5,L5,D3", "U7,R6,D4,L4"), Ok(Some((6, 30))));
         assert_eq!(
             calculate(
                 "R98,U47,R26,D63,R33,U87,L62,D20,R33,U53,R51",
                 "U98,R91,D20,R16,D67,R40,U7,R15,U6,R7"
             ),
-            (135, 410)
+            Ok(Some((135, 410)))
         );
-        println!();
 
         assert_eq!(
             calculate(
                 "R75,D30,R83,U83,L12,D49,R71,U7,L72",
                 "U62,R66,U55,R34,D71,R55,D58,R83"
             ),
-            (159, 610)
+            Ok(Some((159, 610)))
         );
     }
 
     #[test]
     fn part1() {
-        assert_eq!(calculate(
-            "R1004,D53,L10,U126,R130,U533,R48,D185,L768,U786,L445,U694,L659,D237,R432,U147,R590,U200,R878,D970,L308,D134,R617,U431,L631,D548,L300,D509,R660,U698,L958,U170,R572,U514,R387,D385,L670,D374,R898,U870,L545,D262,L699,D110,R58,D84,R77,D58,L891,U9,R320,D914,L161,D148,L266,D334,R442,D855,R349,D618,R272,U514,R584,D269,R608,U542,L335,U855,L646,D678,R720,U325,L792,U60,L828,D915,L487,D253,L911,U907,R392,D981,R965,D725,R308,D574,L997,D332,L927,D855,R122,D5,L875,D336,L395,U697,R806,U420,R718,D575,L824,U397,L308,D988,L855,U332,R838,U853,L91,U778,R265,U549,L847,D665,L804,D768,L736,D201,L825,U87,L747,D375,L162,U336,R375,U754,R468,U507,R256,D107,L79,U871,L155,D667,L448,D847,L193,U263,R154,U859,R696,D222,R189,D307,R332,U522,L345,D961,L161,U274,L122,U931,L812,D852,R906,D269,R612,D723,L304,U944,R64,D20,R401,D260,L95,U278,R128,U637,L554,D650,L116,D720,R12,D434,R514,U379,L899,D359,R815,D843,L994,U775,R63,D942,R655,D91,L236,U175,L813,D572,R520,U812,L657,D935,L886,D178,R618,U260,R7,D953,L158,D471,R309,D858,R25,U746,R40,U832,L544,D311,R122,D224,L281,D699,R147,D310,R659,D662,L990,U160,L969,D335,L923,U201,R336,D643,R226,D91,R88,U350,L303,U20,L157,U987,L305,U766,R253,D790,R977,U482,R283,U793,R785,D799,L511,D757,L689,D841,L233,U742,L551,D466,R66,U579,L18,U838,R554,D143,L996,U557,L783,D799,R36,D563,L244,U440,L8,D945,L346,D747,L769,U661,L485,U965,L569,U952,R57,U773,L267,U453,R424,U66,R763,U105,R285,D870,L179,U548,L46,U914,L251,U194,L559,U736,R768,D917,R617,D55,R185,D464,L244",
-            "L1005,D527,R864,D622,R482,D647,R29,U459,R430,D942,R550,D163,L898,U890,L271,D216,L52,U731,R715,U925,L614,U19,R687,D832,L381,U192,L293,D946,L642,D2,L124,U66,R492,U281,R181,U624,R294,U767,R443,U424,R241,D225,R432,D419,L647,U290,L647,D985,L694,D777,L382,D231,R809,D467,L917,D217,R422,U490,L873,D537,R176,U856,L944,D875,L485,D49,R333,D220,L354,U789,R256,D73,R905,U146,R798,D429,R111,D585,L275,D471,R220,D619,L680,U757,R580,U497,L620,U753,R58,U574,L882,U484,R297,D899,L95,D186,R619,D622,R65,U714,L402,U950,R647,D60,L659,U101,L917,D736,L531,U398,R26,U134,R837,U294,R364,D55,R254,D999,R868,U978,R434,U661,R362,D158,L50,D576,L146,D249,L562,D433,R206,D376,L650,U285,L427,D406,L526,D597,R557,U554,L463,D157,L811,U961,R648,D184,L962,U695,R138,U661,L999,U806,L413,U54,L865,U931,L319,U235,L794,D12,L456,D918,L456,U214,L739,D772,R90,D478,R23,D658,R919,D990,L307,D534,L40,D324,L4,U805,L605,U534,R727,U452,R733,D416,L451,U598,R215,D545,L563,D222,L295,D669,R706,U11,R44,D392,L518,D437,L634,U874,L641,U240,L11,D279,L153,U601,L238,U924,L292,D406,L360,D203,R874,D506,R806,U9,R713,D891,L587,U538,L867,D637,R889,U186,R728,D672,R573,U461,R222,D703,R178,U336,L896,D924,L445,D365,L648,U3,L734,U959,R344,U314,R331,D929,L364,D937,L896,D191,R218,U256,L975,D506,R510,D392,R878,U896,L177,U4,R516,D873,R57,D530,R140,D827,L263,U848,L88,U309,L801,U670,R874,D358,L49,D259,L188,U419,R705,D498,R496,U576,R808,D959,L861,U437,L618,D112,R725,D546,R338,U879,R522,U892,R230,D367,R901,D737,L942,D689,R976,D369,R157"
-        ).0, 266);
+        let input = input::read!("input.txt").unwrap();
+        let (wire1, wire2) = input.trim().split_once('\n').unwrap();
+        assert_eq!(calculate(wire1, wire2).unwrap().unwrap().0, 266);
     }
 
     #[test]
     fn part2() {
-        assert_eq!(calculate(
-            "R1004,D53,L10,U126,R130,U533,R48,D185,L768,U786,L445,U694,L659,D237,R432,U147,R590,U200,R878,D970,L308,D134,R617,U431,L631,D548,L300,D509,R660,U698,L958,U170,R572,U514,R387,D385,L670,D374,R898,U870,L545,D262,L699,D110,R58,D84,R77,D58,L891,U9,R320,D914,L161,D148,L266,D334,R442,D855,R349,D618,R272,U514,R584,D269,R608,U542,L335,U855,L646,D678,R720,U325,L792,U60,L828,D915,L487,D253,L911,U907,R392,D981,R965,D725,R308,D574,L997,D332,L927,D855,R122,D5,L875,D336,L395,U697,R806,U420,R718,D575,L824,U397,L308,D988,L855,U332,R838,U853,L91,U778,R265,U549,L847,D665,L804,D768,L736,D201,L825,U87,L747,D375,L162,U336,R375,U754,R468,U507,R256,D107,L79,U871,L155,D667,L448,D847,L193,U263,R154,U859,R696,D222,R189,D307,R332,U522,L345,D961,L161,U274,L122,U931,L812,D852,R906,D269,R612,D723,L304,U944,R64,D20,R401,D260,L95,U278,R128,U637,L554,D650,L116,D720,R12,D434,R514,U379,L899,D359,R815,D843,L994,U775,R63,D942,R655,D91,L236,U175,L813,D572,R520,U812,L657,D935,L886,D178,R618,U260,R7,D953,L158,D471,R309,D858,R25,U746,R40,U832,L544,D311,R122,D224,L281,D699,R147,D310,R659,D662,L990,U160,L969,D335,L923,U201,R336,D643,R226,D91,R88,U350,L303,U20,L157,U987,L305,U766,R253,D790,R977,U482,R283,U793,R785,D799,L511,D757,L689,D841,L233,U742,L551,D466,R66,U579,L18,U838,R554,D143,L996,U557,L783,D799,R36,D563,L244,U440,L8,D945,L346,D747,L769,U661,L485,U965,L569,U952,R57,U773,L267,U453,R424,U66,R763,U105,R285,D870,L179,U548,L46,U914,L251,U194,L559,U736,R768,D917,R617,D55,R185,D464,L244",
-            "L1005,D527,R864,D622,R482,D647,R29,U459,R430,D942,R550,D163,L898,U890,L271,D216,L52,U731,R715,U925,L614,U19,R687,D832,L381,U192,L293,D946,L642,D2,L124,U66,R492,U281,R181,U624,R294,U767,R443,U424,R241,D225,R432,D419,L647,U290,L647,D985,L694,D777,L382,D231,R809,D467,L917,D217,R422,U490,L873,D537,R176,U856,L944,D875,L485,D49,R333,D220,L354,U789,R256,D73,R905,U146,R798,D429,R111,D585,L275,D471,R220,D619,L680,U757,R580,U497,L620,U753,R58,U574,L882,U484,R297,D899,L95,D186,R619,D622,R65,U714,L402,U950,R647,D60,L659,U101,L917,D736,L531,U398,R26,U134,R837,U294,R364,D55,R254,D999,R868,U978,R434,U661,R362,D158,L50,D576,L146,D249,L562,D433,R206,D376,L650,U285,L427,D406,L526,D597,R557,U554,L463,D157,L811,U961,R648,D184,L962,U695,R138,U661,L999,U806,L413,U54,L865,U931,L319,U235,L794,D12,L456,D918,L456,U214,L739,D772,R90,D478,R23,D658,R919,D990,L307,D534,L40,D324,L4,U805,L605,U534,R727,U452,R733,D416,L451,U598,R215,D545,L563,D222,L295,D669,R706,U11,R44,D392,L518,D437,L634,U874,L641,U240,L11,D279,L153,U601,L238,U924,L292,D406,L360,D203,R874,D506,R806,U9,R713,D891,L587,U538,L867,D637,R889,U186,R728,D672,R573,U461,R222,D703,R178,U336,L896,D924,L445,D365,L648,U3,L734,U959,R344,U314,R331,D929,L364,D937,L896,D191,R218,U256,L975,D506,R510,D392,R878,U896,L177,U4,R516,D873,R57,D530,R140,D827,L263,U848,L88,U309,L801,U670,R874,D358,L49,D259,L188,U419,R705,D498,R496,U576,R808,D959,L861,U437,L618,D112,R725,D546,R338,U879,R522,U892,R230,D367,R901,D737,L942,D689,R976,D369,R157"
-        ).1,19242);
+        let input = input::read!("input.txt").unwrap();
+        let (wire1, wire2) = input.trim().split_once('\n').unwrap();
+        assert_eq!(calculate(wire1, wire2).unwrap().unwrap().1, 19242);
     }
 }
@@ -1,73 +1,103 @@
-#[cfg(test)]
-mod tests {
-    use std::collections::HashMap;
-    fn is_six_digits(val: i32) -> bool {
-        val > 99999 && val < 1000000
+use solution::{ParseResult, PartResult, Solution};
+use std::collections::HashMap;
+use std::fmt::Display;
+
+pub fn is_six_digits(val: i32) -> bool {
+    val > 99999 && val < 1000000
+}
+
+pub fn has_adj_dup(val: i32) -> bool {
+    let str_val = val.to_string();
+    let bytes_val = str_val.as_bytes();
+
+    for i in 0..5 {
+        if bytes_val[i] == bytes_val[i + 1] {
+            return true;
+        }
     }
+    false
+}
+
+pub fn has_2adj_dup(val: i32) -> bool {
+    let str_val = val.to_string();
+    let bytes_val = str_val.as_bytes().to_vec();
+    let mut observed = HashMap::new();
 
-    fn has_adj_dup(val: i32) -> bool {
-        let str_val = val.to_string();
-        let bytes_val = str_val.as_bytes();
+    for byte in bytes_val {
+        let e = observed.entry(byte).or_insert(0);
+        *e += 1;
+    }
 
-        for i in 0..5 {
-            if bytes_val[i] == bytes_val[i + 1] {
-                return true;
-            }
+    for o in observed.values() {
+        if *o == 2 {
+            return true;
         }
-        false
     }
+    false
+}
 
-    fn has_2adj_dup(val: i32) -> bool {
-        let str_val = val.to_string();
-        let bytes_val = str_val.as_bytes().to_vec();
-        let mut observed = HashMap::new();
+pub fn never_decreases(val: i32) -> bool {
+    let str_val = val.to_string();
+    let bytes_val = str_val.as_bytes();
 
-        for byte in bytes_val {
-            let e = observed.entry(byte).or_insert(0);
-            *e += 1;
+    for i in 0..5 {
+        if bytes_val[i + 1] < bytes_val[i] {
+            return false;
         }
+    }
+    true
+}
 
-        for o in observed.values() {
-            if *o == 2 {
-                return true;
-            }
+pub fn crack_code1(min: i32, max: i32) -> i32 {
+    let mut victory = 0;
+    for val in min..=max {
+        if is_six_digits(val) && has_adj_dup(val) && never_decreases(val) {
+            victory += 1;
         }
-        false
     }
 
-    fn never_decreases(val: i32) -> bool {
-        let str_val = val.to_string();
-        let bytes_val = str_val.as_bytes();
+    victory
+}
 
-        for i in 0..5 {
-            if bytes_val[i + 1] < bytes_val[i] {
-                return false;
-            }
+pub fn crack_code2(min: i32, max: i32) -> i32 {
+    let mut victory = 0;
+    for val in min..=max {
+        if is_six_digits(val) && has_2adj_dup(val) && never_decreases(val) {
+            victory += 1;
         }
-        true
     }
 
-    fn crack_code1(min: i32, max: i32) -> i32 {
-        let mut victory = 0;
-        for val in min..=max {
-            if is_six_digits(val) && has_adj_dup(val) && never_decreases(val) {
-                victory += 1;
-            }
-        }
+    victory
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = (i32, i32);
 
-        victory
+    // "108457-562041"
+    fn parse(input: &str) -> ParseResult<(i32, i32)> {
+        let (min, max) = input.trim().split_once('-').ok_or("Expected a range")?;
+        Ok((
+            min.parse()
+                .map_err(|e| format!("Invalid range start: {}", e))?,
+            max.parse()
+                .map_err(|e| format!("Invalid range end: {}", e))?,
+        ))
     }
 
-    fn crack_code2(min: i32, max: i32) -> i32 {
-        let mut victory = 0;
-        for val in min..=max {
-            if is_six_digits(val) && has_2adj_dup(val) && never_decreases(val) {
-                victory += 1;
-            }
-        }
+    fn part1(&(min, max): &(i32, i32)) -> PartResult<impl Display> {
+        Ok(crack_code1(min, max))
+    }
 
-        victory
+    fn part2(&(min, max): &(i32, i32)) -> PartResult<impl Display> {
+        Ok(crack_code2(min, max))
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
     #[test]
     fn tests() {
@@ -102,10 +132,12 @@ mod tests {
 
     #[test]
     fn part1() {
-        assert_eq!(crack_code1(108457, 562041), 2779);
+        let (min, max) = Day4::parse(&input::read!("input.txt").unwrap()).unwrap();
+        assert_eq!(crack_code1(min, max), 2779);
     }
     #[test]
     fn part2() {
-        assert_eq!(crack_code2(108457, 562041), 1972);
+        let (min, max) = Day4::parse(&input::read!("input.txt").unwrap()).unwrap();
+        assert_eq!(crack_code2(min, max), 1972);
     }
 }
@@ -1,3 +1,6 @@
+use parser::{integers, parse_all, ParseError};
+use solution::{ParseResult, PartResult, Solution};
+use std::fmt::Display;
 use OpCode::*;
 use ParameterMode::*;
 
@@ -34,22 +37,12 @@ use Status::*;
 
 fn decode_opcode(input: isize) -> (isize, ParameterMode, ParameterMode, ParameterMode) {
     let opcode = input % 100;
-    let parameter1 = if (input / 100) % 10 == 1 {
-        Immediate
-    } else {
-        Position
+    let parameter = |place: isize| match (input / place) % 10 {
+        1 => Immediate,
+        2 => Relative,
+        _ => Position,
     };
-    let parameter2 = if (input / 1000) % 10 == 1 {
-        Immediate
-    } else {
-        Position
-    };
-    let parameter3 = if (input / 10000) % 10 == 1 {
-        Immediate
-    } else {
-        Position
-    };
-    (opcode, parameter1, parameter2, parameter3)
+    (opcode, parameter(100), parameter(1000), parameter(10000))
 }
 
 fn get_instruction_size(input: isize) -> usize {
@@ -64,7 +57,7 @@ fn get_instruction_size(input: isize) -> usize {
         x if x == LessThan as isize => 4,
         x if x == Equals as isize => 4,
         x if x == RelativeBaseOffset as isize => 2,
-        _ => panic!(format!("Invalid OpCode ({})", input)),
+        _ => panic!("Invalid OpCode ({})", input),
     }
 }
 
@@ -87,8 +80,8 @@ impl IntcodeComputer {
             x if x == Add as isize => {
                 assert!(p3_mode == Position);
 
-                let p1 = self.get_parameter(p1_mode, instruction[1], &memory);
-                let p2 = self.get_parameter(p2_mode, instruction[2], &memory);
+                let p1 = self.get_parameter(p1_mode, instruction[1], memory);
+                let p2 = self.get_parameter(p2_mode, instruction[2], memory);
                 let p3 = instruction[3];
 
                 memory[p3 as usize] = p2 + p1;
@@ -98,8 +91,8 @@ impl IntcodeComputer {
             x if x == Multiply as isize => {
                 assert!(p3_mode == Position);
 
-                let p1 = self.get_parameter(p1_mode, instruction[1], &memory);
-                let p2 = self.get_parameter(p2_mode, instruction[2], &memory);
+                let p1 = self.get_parameter(p1_mode, instruction[1], memory);
+                let p2 = self.get_parameter(p2_mode, instruction[2], memory);
                 let p3 = instruction[3];
 
                 memory[p3 as usize] = p2 * p1;
@@ -112,7 +105,7 @@ impl IntcodeComputer {
                 assert!(p3_mode == Position);
 
                 let p1 = instruction[1] as usize;
-                if input.len() == 0 {
+                if input.is_empty() {
                     (Paused, None, None)
                 } else {
                     let val = input.pop().expect("Unable to pop");
@@ -126,7 +119,7 @@ impl IntcodeComputer {
                 assert!(p2_mode == Position);
                 assert!(p3_mode == Position);
 
-                let p1 = self.get_parameter(p1_mode, instruction[1], &memory);
+                let p1 = self.get_parameter(p1_mode, instruction[1], memory);
 
                 println!("Output Value: {}", p1);
 
@@ -136,8 +129,8 @@ impl IntcodeComputer {
             x if x == JumpIfTrue as isize => {
                 assert!(p3_mode == Position);
 
-                let p1 = self.get_parameter(p1_mode, instruction[1], &memory);
-                let p2 = self.get_parameter(p2_mode, instruction[2], &memory) as usize;
+                let p1 = self.get_parameter(p1_mode, instruction[1], memory);
+                let p2 = self.get_parameter(p2_mode, instruction[2], memory) as usize;
 
                 if p1 != 0 {
                     (Running, Some(p2), None)
@@ -149,8 +142,8 @@ impl IntcodeComputer {
             x if x == JumpIfFalse as isize => {
                 assert!(p3_mode == Position);
 
-                let p1 = self.get_parameter(p1_mode, instruction[1], &memory);
-                let p2 = self.get_parameter(p2_mode, instruction[2], &memory) as usize;
+                let p1 = self.get_parameter(p1_mode, instruction[1], memory);
+                let p2 = self.get_parameter(p2_mode, instruction[2], memory) as usize;
 
                 if p1 == 0 {
                     (Running, Some(p2), None)
@@ -162,8 +155,8 @@ impl IntcodeComputer {
             x if x == LessThan as isize => {
                 assert!(p3_mode == Position);
 
-                let p1 = self.get_parameter(p1_mode, instruction[1], &memory);
-                let p2 = self.get_parameter(p2_mode, instruction[2], &memory);
+                let p1 = self.get_parameter(p1_mode, instruction[1], memory);
+                let p2 = self.get_parameter(p2_mode, instruction[2], memory);
                 let p3 = instruction[3] as usize;
 
                 memory[p3] = if p1 < p2 { 1 } else { 0 };
@@ -174,8 +167,8 @@ impl IntcodeComputer {
             x if x == Equals as isize => {
                 assert!(p3_mode == Position);
 
-                let p1 = self.get_parameter(p1_mode, instruction[1], &memory);
-                let p2 = self.get_parameter(p2_mode, instruction[2], &memory);
+                let p1 = self.get_parameter(p1_mode, instruction[1], memory);
+                let p2 = self.get_parameter(p2_mode, instruction[2], memory);
                 let p3 = instruction[3] as usize;
 
                 memory[p3] = if p1 == p2 { 1 } else { 0 };
@@ -184,7 +177,7 @@ impl IntcodeComputer {
             }
 
             x if x == RelativeBaseOffset as isize => {
-                let p1 = self.get_parameter(p1_mode, instruction[1], &memory) as usize;
+                let p1 = self.get_parameter(p1_mode, instruction[1], memory) as usize;
                 println!("Congrats! youve hit the RelativeBaseOffset!");
                 self.relative_base += p1;
                 (Running, None, None)
@@ -197,11 +190,11 @@ impl IntcodeComputer {
 
                 (Halted, None, None)
             }
-            _ => panic!(format!("Invalid OpCode ({})", opcode)),
+            _ => panic!("Invalid OpCode ({})", opcode),
         }
     }
 
-    pub fn read_program(memory: &mut [isize], mut input: &mut Vec<isize>) -> Result<isize, isize> {
+    pub fn read_program(memory: &mut [isize], input: &mut Vec<isize>) -> Result<isize, isize> {
         color_backtrace::install();
 
         let mut intcode = IntcodeComputer { relative_base: 0 };
@@ -216,7 +209,7 @@ impl IntcodeComputer {
 
         loop {
             let (status, addr_override, output_var) =
-                &mut intcode.process_instruction(memory, &instruction, &mut input);
+                &mut intcode.process_instruction(memory, &instruction, input);
 
             match status {
                 Halted => return Ok(output),
@@ -224,10 +217,7 @@ impl IntcodeComputer {
                 Running => {}
             };
 
-            match output_var {
-                Some(v) => output = *v,
-                _ => (),
-            };
+            if let Some(v) = output_var { output = *v };
 
             match addr_override {
                 Some(v) => {
@@ -245,11 +235,41 @@ impl IntcodeComputer {
     }
 }
 
+// A comma separated Intcode program
+// Comma separated, like "1,9,10,3,2,3,11,0,99,30,40,50"
+pub fn parse_program(input: &str) -> Result<Vec<isize>, ParseError> {
+    parse_all(input, integers(","))
+}
+
+// The diagnostic code the program outputs for a system id
+fn diagnostic_code(program: &[isize], system_id: isize) -> isize {
+    IntcodeComputer::read_program(&mut program.to_vec(), &mut vec![system_id])
+        .expect("Program waiting on more input")
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = Vec<isize>;
+
+    fn parse(input: &str) -> ParseResult<Vec<isize>> {
+        Ok(parse_program(input)?)
+    }
+
+    fn part1(program: &Vec<isize>) -> PartResult<impl Display> {
+        Ok(diagnostic_code(program, 1))
+    }
+
+    fn part2(program: &Vec<isize>) -> PartResult<impl Display> {
+        Ok(diagnostic_code(program, 5))
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
     use crate::ParameterMode::{Immediate, Position};
-    use crate::{decode_opcode, IntcodeComputer};
+    use crate::{decode_opcode, parse_program, IntcodeComputer};
 
     #[test]
     fn part2_example1() {
@@ -358,46 +378,7 @@ mod tests {
 
     #[test]
     fn part1_2() {
-        let memory = [
-            3, 225, 1, 225, 6, 6, 1100, 1, 238, 225, 104, 0, 1101, 32, 43, 225, 101, 68, 192, 224,
-            1001, 224, -160, 224, 4, 224, 102, 8, 223, 223, 1001, 224, 2, 224, 1, 223, 224, 223,
-            1001, 118, 77, 224, 1001, 224, -87, 224, 4, 224, 102, 8, 223, 223, 1001, 224, 6, 224,
-            1, 223, 224, 223, 1102, 5, 19, 225, 1102, 74, 50, 224, 101, -3700, 224, 224, 4, 224,
-            1002, 223, 8, 223, 1001, 224, 1, 224, 1, 223, 224, 223, 1102, 89, 18, 225, 1002, 14,
-            72, 224, 1001, 224, -3096, 224, 4, 224, 102, 8, 223, 223, 101, 5, 224, 224, 1, 223,
-            224, 223, 1101, 34, 53, 225, 1102, 54, 10, 225, 1, 113, 61, 224, 101, -39, 224, 224, 4,
-            224, 102, 8, 223, 223, 101, 2, 224, 224, 1, 223, 224, 223, 1101, 31, 61, 224, 101, -92,
-            224, 224, 4, 224, 102, 8, 223, 223, 1001, 224, 4, 224, 1, 223, 224, 223, 1102, 75, 18,
-            225, 102, 48, 87, 224, 101, -4272, 224, 224, 4, 224, 102, 8, 223, 223, 1001, 224, 7,
-            224, 1, 224, 223, 223, 1101, 23, 92, 225, 2, 165, 218, 224, 101, -3675, 224, 224, 4,
-            224, 1002, 223, 8, 223, 101, 1, 224, 224, 1, 223, 224, 223, 1102, 8, 49, 225, 4, 223,
-            99, 0, 0, 0, 677, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1105, 0, 99999, 1105, 227, 247,
-            1105, 1, 99999, 1005, 227, 99999, 1005, 0, 256, 1105, 1, 99999, 1106, 227, 99999, 1106,
-            0, 265, 1105, 1, 99999, 1006, 0, 99999, 1006, 227, 274, 1105, 1, 99999, 1105, 1, 280,
-            1105, 1, 99999, 1, 225, 225, 225, 1101, 294, 0, 0, 105, 1, 0, 1105, 1, 99999, 1106, 0,
-            300, 1105, 1, 99999, 1, 225, 225, 225, 1101, 314, 0, 0, 106, 0, 0, 1105, 1, 99999,
-            1107, 226, 226, 224, 1002, 223, 2, 223, 1005, 224, 329, 1001, 223, 1, 223, 1007, 677,
-            226, 224, 1002, 223, 2, 223, 1006, 224, 344, 1001, 223, 1, 223, 108, 677, 226, 224,
-            102, 2, 223, 223, 1006, 224, 359, 1001, 223, 1, 223, 7, 226, 226, 224, 1002, 223, 2,
-            223, 1005, 224, 374, 101, 1, 223, 223, 107, 677, 677, 224, 1002, 223, 2, 223, 1006,
-            224, 389, 1001, 223, 1, 223, 1007, 677, 677, 224, 1002, 223, 2, 223, 1006, 224, 404,
-            1001, 223, 1, 223, 1107, 677, 226, 224, 1002, 223, 2, 223, 1005, 224, 419, 1001, 223,
-            1, 223, 108, 226, 226, 224, 102, 2, 223, 223, 1006, 224, 434, 1001, 223, 1, 223, 1108,
-            226, 677, 224, 1002, 223, 2, 223, 1006, 224, 449, 1001, 223, 1, 223, 1108, 677, 226,
-            224, 102, 2, 223, 223, 1005, 224, 464, 1001, 223, 1, 223, 107, 226, 226, 224, 102, 2,
-            223, 223, 1006, 224, 479, 1001, 223, 1, 223, 1008, 226, 226, 224, 102, 2, 223, 223,
-            1005, 224, 494, 101, 1, 223, 223, 7, 677, 226, 224, 1002, 223, 2, 223, 1005, 224, 509,
-            101, 1, 223, 223, 8, 226, 677, 224, 1002, 223, 2, 223, 1006, 224, 524, 1001, 223, 1,
-            223, 1007, 226, 226, 224, 1002, 223, 2, 223, 1006, 224, 539, 101, 1, 223, 223, 1008,
-            677, 677, 224, 1002, 223, 2, 223, 1006, 224, 554, 101, 1, 223, 223, 1108, 677, 677,
-            224, 102, 2, 223, 223, 1006, 224, 569, 101, 1, 223, 223, 1107, 226, 677, 224, 102, 2,
-            223, 223, 1005, 224, 584, 1001, 223, 1, 223, 8, 677, 226, 224, 1002, 223, 2, 223, 1006,
-            224, 599, 101, 1, 223, 223, 1008, 677, 226, 224, 102, 2, 223, 223, 1006, 224, 614,
-            1001, 223, 1, 223, 7, 226, 677, 224, 1002, 223, 2, 223, 1005, 224, 629, 101, 1, 223,
-            223, 107, 226, 677, 224, 102, 2, 223, 223, 1005, 224, 644, 101, 1, 223, 223, 8, 677,
-            677, 224, 102, 2, 223, 223, 1005, 224, 659, 1001, 223, 1, 223, 108, 677, 677, 224,
-            1002, 223, 2, 223, 1005, 224, 674, 101, 1, 223, 223, 4, 223, 99, 226,
-        ];
+        let memory = parse_program(&input::read!("input.txt").unwrap()).unwrap();
         assert_eq!(
             Ok(5821753),
             IntcodeComputer::read_program(&mut memory.clone(), &mut vec![1])
@@ -410,18 +391,18 @@ mod tests {
 
     #[test]
     fn misc() {
-        println!("{}", (1002 / 1000) % 10 == 1);
+        println!("{}", (1002 / 1000) == 1);
         assert_eq!(decode_opcode(1002), (2, Position, Immediate, Position));
         assert_eq!(decode_opcode(11002), (2, Position, Immediate, Immediate));
         assert_eq!(decode_opcode(11102), (2, Immediate, Immediate, Immediate));
-        assert_eq!(decode_opcode(02), (2, Position, Position, Position));
+        assert_eq!(decode_opcode(2), (2, Position, Position, Position));
     }
 
     #[test]
     fn example1() {
         let mut memory = [1002, 4, 3, 4, 33];
 
-        IntcodeComputer::read_program(&mut memory, &mut vec![0]);
+        IntcodeComputer::read_program(&mut memory, &mut vec![0]).unwrap();
         assert_eq!(memory, [1002, 4, 3, 4, 99]);
     }
 
@@ -429,7 +410,7 @@ mod tests {
     fn example2() {
         let mut memory = [1101, 100, -1, 4, 0];
 
-        IntcodeComputer::read_program(&mut memory, &mut vec![0]);
+        IntcodeComputer::read_program(&mut memory, &mut vec![0]).unwrap();
         assert_eq!(memory, [1101, 100, -1, 4, 99]);
     }
 
@@ -437,14 +418,14 @@ mod tests {
     fn example3() {
         let mut memory = [3, 0, 4, 0, 99];
 
-        IntcodeComputer::read_program(&mut memory, &mut vec![0]);
+        IntcodeComputer::read_program(&mut memory, &mut vec![0]).unwrap();
     }
 
     #[test]
     fn example_input() {
         let mut memory = [3, 0, 99];
 
-        IntcodeComputer::read_program(&mut memory, &mut vec![122]);
+        IntcodeComputer::read_program(&mut memory, &mut vec![122]).unwrap();
         assert_eq!(memory, [122, 0, 99]);
     }
 
@@ -452,7 +433,7 @@ mod tests {
     fn example_output() {
         let mut memory = [4, 1, 99];
 
-        IntcodeComputer::read_program(&mut memory, &mut vec![0]);
+        IntcodeComputer::read_program(&mut memory, &mut vec![0]).unwrap();
         assert_eq!(memory, [4, 1, 99]);
     }
 
@@ -460,7 +441,7 @@ mod tests {
     fn example_add() {
         let mut memory = [1, 0, 0, 0, 99];
 
-        IntcodeComputer::read_program(&mut memory, &mut vec![0]);
+        IntcodeComputer::read_program(&mut memory, &mut vec![0]).unwrap();
         assert_eq!(memory, [2, 0, 0, 0, 99]);
     }
 
@@ -468,7 +449,7 @@ mod tests {
     fn example_multiply() {
         let mut memory = [2, 3, 0, 3, 99];
 
-        IntcodeComputer::read_program(&mut memory, &mut vec![0]);
+        IntcodeComputer::read_program(&mut memory, &mut vec![0]).unwrap();
         assert_eq!(memory, [2, 3, 0, 6, 99]);
     }
 
@@ -476,7 +457,16 @@ mod tests {
     fn example_day2() {
         let mut memory = [1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50];
 
-        IntcodeComputer::read_program(&mut memory, &mut vec![0]);
+        IntcodeComputer::read_program(&mut memory, &mut vec![0]).unwrap();
         assert_eq!(memory, [3500, 9, 10, 70, 2, 3, 11, 0, 99, 30, 40, 50]);
     }
+
+    #[test]
+    fn bad_program() {
+        let error = parse_program("3,0,4,0,99,").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 11: expected end of input, found \",\""
+        );
+    }
 }
@@ -1,122 +1,138 @@
-#[cfg(test)]
-mod tests {
-    use std::collections::HashMap;
+use parser::nom::{
+    character::complete::{alphanumeric1, char, space0},
+    sequence::{preceded, separated_pair},
+};
+use parser::{lines, parse_all, ParseError, Parsed};
+use solution::{ParseResult, PartResult, Solution};
+use std::collections::HashMap;
+use std::fmt::Display;
+
+// Each object mapped to the one it orbits
+pub type Orbits = HashMap<String, String>;
+
+// "COM)B", for B orbiting COM
+fn orbit(input: &str) -> Parsed<'_, (&str, &str)> {
+    preceded(
+        space0,
+        separated_pair(alphanumeric1, char(')'), alphanumeric1),
+    )(input)
+}
 
-    fn construct_map(input: &'static str, memory: &mut HashMap<&str, &'static str>) {
-        let relationships = input.split('\n');
-        for relationship in relationships {
-            let v: Vec<&str> = relationship.trim().split_terminator(')').collect();
+pub fn construct_map(input: &str) -> Result<Orbits, ParseError> {
+    let mut memory = HashMap::new();
+    for (centre, object) in parse_all(input, lines(orbit))? {
+        memory
+            .entry(object.to_string())
+            .or_insert_with(|| centre.to_string());
+    }
+    Ok(memory)
+}
 
-            memory.entry(v[1].into()).or_insert(v[0]);
+pub fn measure_map(memory: &Orbits) -> i32 {
+    let mut distance = 0;
+    for parent in memory.values() {
+        let mut parent = parent;
+        distance += 1;
+        while parent != "COM" {
+            distance += 1;
+            parent = &memory[parent];
         }
     }
 
-    fn measure_map(memory: &HashMap<&str, &'static str>) -> i32 {
-        let mut distance = 0;
-        for (_child, parent) in memory {
-            let mut parent = parent;
-            distance += 1;
-            while parent != &"COM" {
-                distance += 1;
-                parent = &memory[parent];
-            }
+    distance
+}
+
+// Implement a simple simultaneous BFS from YOU and SAN,
+// where they intersect, measure your distance.
+pub fn measure_transfers(memory: &Orbits) -> i32 {
+    let mut you_exp = Vec::new();
+    let mut san_exp = Vec::new();
+    let mut you = &memory["YOU"];
+    let mut san = &memory["SAN"];
+    let mut distance = 0;
+
+    you_exp.push(you);
+    san_exp.push(san);
+    let intersection;
+    loop {
+        if san != "COM" {
+            san = &memory[san];
+            san_exp.push(san);
         }
 
-        distance
-    }
+        if you != "COM" {
+            you = &memory[you];
+            you_exp.push(you);
+        }
 
-    // Implement a simple simultaneous BFS from YOU and SAN,
-    // where they intersect, measure your distance.
-    fn measure_transfers(memory: &HashMap<&str, &'static str>) -> i32 {
-        let mut you_exp = Vec::new();
-        let mut san_exp = Vec::new();
-        let mut you = &memory[&"YOU"];
-        let mut san = &memory[&"SAN"];
-        let mut distance = 0;
-
-        you_exp.push(you);
-        san_exp.push(san);
-        let intersection;
-        loop {
-            if san != &"COM" {
-                san = &memory[san];
-                san_exp.push(san);
-            }
-
-            if you != &"COM" {
-                you = &memory[you];
-                you_exp.push(you);
-            }
-
-            // Check if the exploration lists have overlapped
-            if san_exp.contains(&you) {
-                intersection = you;
-                break;
-            } else if you_exp.contains(&san) {
-                intersection = san;
-                break;
-            }
+        // Check if the exploration lists have overlapped
+        if san_exp.contains(&you) {
+            intersection = you;
+            break;
+        } else if you_exp.contains(&san) {
+            intersection = san;
+            break;
         }
+    }
 
-        for thing in you_exp {
-            distance += 1;
-            if thing == intersection {
-                break;
-            }
+    for thing in you_exp {
+        distance += 1;
+        if thing == intersection {
+            break;
         }
-        for thing in san_exp {
-            distance += 1;
-            if thing == intersection {
-                break;
-            }
+    }
+    for thing in san_exp {
+        distance += 1;
+        if thing == intersection {
+            break;
         }
+    }
+
+    distance - 2
+}
 
-        distance - 2
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = Orbits;
+
+    fn parse(input: &str) -> ParseResult<Orbits> {
+        Ok(construct_map(input)?)
     }
 
-    // Unforunately need to _coerce_ this String into a 'static String interpretation...
-    fn string_to_static_str(s: String) -> &'static str {
-        Box::leak(s.into_boxed_str())
+    fn part1(memory: &Orbits) -> PartResult<impl Display> {
+        Ok(measure_map(memory))
     }
 
-    #[test]
-    fn part1() {
-        use std::fs::File;
-        use std::io::prelude::*;
+    fn part2(memory: &Orbits) -> PartResult<impl Display> {
+        Ok(measure_transfers(memory))
+    }
+}
 
-        let mut file = File::open("input.txt").expect("Unable to open file");
-        let mut contents = String::new();
-        let mut memory = HashMap::new();
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        file.read_to_string(&mut contents)
-            .expect("Cannot read file");
+    #[test]
+    fn part1() {
+        let contents = input::read!("input.txt").unwrap();
 
-        construct_map(string_to_static_str(contents), &mut memory);
+        let memory = construct_map(&contents).unwrap();
 
         assert_eq!(254447, measure_map(&memory));
     }
 
     #[test]
     fn part2() {
-        use std::fs::File;
-        use std::io::prelude::*;
-
-        let mut file = File::open("input.txt").expect("Unable to open file");
-        let mut contents = String::new();
-        let mut memory = HashMap::new();
+        let contents = input::read!("input.txt").unwrap();
 
-        file.read_to_string(&mut contents)
-            .expect("Cannot read file");
-
-        construct_map(string_to_static_str(contents), &mut memory);
+        let memory = construct_map(&contents).unwrap();
 
         assert_eq!(445, measure_transfers(&memory));
     }
     #[test]
     fn example1() {
-        let mut memory = HashMap::new();
-
-        construct_map(
+        let memory = construct_map(
             "COM)B
         B)C
         C)D
@@ -128,17 +144,15 @@ mod tests {
         E)J
         J)K
         K)L",
-            &mut memory,
-        );
+        )
+        .unwrap();
 
         assert_eq!(42, measure_map(&memory));
     }
 
     #[test]
     fn example2() {
-        let mut memory = HashMap::new();
-
-        construct_map(
+        let memory = construct_map(
             "COM)B
             B)C
             C)D
@@ -152,9 +166,16 @@ mod tests {
             K)L
             K)YOU
             I)SAN",
-            &mut memory,
-        );
+        )
+        .unwrap();
 
         assert_eq!(4, measure_transfers(&memory));
     }
+
+    #[test]
+    fn bad_orbit() {
+        let error = construct_map("COM)B\nB)C\nC-D").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 2));
+        assert_eq!(error.expected, "')'");
+    }
 }
@@ -1,126 +1,251 @@
-#[cfg(test)]
-mod tests {
-    use std::fs;
+use solution::{ParseResult, PartResult, Solution};
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::fs;
+use std::io;
 
-    type Pixel = u8;
-    #[derive(Debug)]
-    struct Layer {
-        pixels: Vec<Vec<Pixel>>,
-    }
+pub type Pixel = u8;
 
-    #[derive(Debug)]
-    struct Image {
-        layers: Vec<Layer>,
-        height: usize,
+pub const BLACK: Pixel = 0;
+pub const WHITE: Pixel = 1;
+pub const TRANSPARENT: Pixel = 2;
+
+#[derive(Debug)]
+pub struct Layer {
+    pub pixels: Vec<Vec<Pixel>>,
+}
+
+#[derive(Debug)]
+pub struct Image {
+    pub layers: Vec<Layer>,
+    pub height: usize,
+    pub width: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImageError {
+    // Counted from 1 along the line of digits
+    BadPixel {
+        column: usize,
+        found: char,
+    },
+    // The pixels don't split into a whole number of layers, or into any at all
+    Ragged {
+        pixels: usize,
         width: usize,
-    }
+        height: usize,
+    },
+}
 
-    impl Layer {
-        fn new(width: usize, _height: usize, data: &[u8]) -> Self {
-            let mut new_layer = Self { pixels: Vec::new() };
-            let rows = data.chunks_exact(width);
-            for row in rows {
-                new_layer.pixels.push(row.into());
+impl fmt::Display for ImageError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ImageError::BadPixel { column, found } => {
+                write!(
+                    f,
+                    "column {}: expected a pixel digit, found {:?}",
+                    column, found
+                )
             }
+            ImageError::Ragged {
+                pixels,
+                width,
+                height,
+            } => write!(
+                f,
+                "{} pixels don't make whole {}x{} layers",
+                pixels, width, height
+            ),
+        }
+    }
+}
 
-            new_layer
+impl Error for ImageError {}
+
+impl Layer {
+    fn new(width: usize, _height: usize, data: &[Pixel]) -> Self {
+        let mut new_layer = Self { pixels: Vec::new() };
+        let rows = data.chunks_exact(width);
+        for row in rows {
+            new_layer.pixels.push(row.into());
         }
 
-        fn count_char(&self, input: &str) -> usize {
-            let digit = input.as_bytes()[0];
+        new_layer
+    }
+
+    pub fn count(&self, digit: Pixel) -> usize {
+        self.pixels
+            .iter()
+            .flatten()
+            .filter(|&&v| v == digit)
+            .count()
+    }
+}
 
-            let val = self.pixels.iter().flatten().fold(0, |mut acc, &v| {
-                if v == digit {
-                    acc += 1;
-                }
-                acc
+impl Image {
+    pub fn new(input: &str, width: usize, height: usize) -> Result<Self, ImageError> {
+        let mut new_image = Image {
+            layers: Vec::new(),
+            height,
+            width,
+        };
+
+        let digits = input
+            .trim()
+            .chars()
+            .enumerate()
+            .map(|(i, c)| match c.to_digit(10) {
+                Some(d) => Ok(d as Pixel),
+                None => Err(ImageError::BadPixel {
+                    column: i + 1,
+                    found: c,
+                }),
+            })
+            .collect::<Result<Vec<Pixel>, _>>()?;
+
+        let size = width * height;
+        if digits.is_empty() || size == 0 || !digits.len().is_multiple_of(size) {
+            return Err(ImageError::Ragged {
+                pixels: digits.len(),
+                width,
+                height,
             });
+        }
 
-            val
+        for chunk in digits.chunks_exact(size) {
+            new_image.layers.push(Layer::new(width, height, chunk));
         }
+        Ok(new_image)
     }
 
-    impl Image {
-        fn new(input: &str, width: usize, height: usize) -> Self {
-            let mut new_image = Image {
-                layers: Vec::new(),
-                height,
-                width,
-            };
-            let chars = input.as_bytes();
-            let chunks = chars.chunks_exact(width * height);
+    pub fn fewest(&self, digit: Pixel) -> &Layer {
+        self.layers
+            .iter()
+            .min_by_key(|l| l.count(digit))
+            .expect("Image has no layers")
+    }
 
-            for chunk in chunks {
-                let new_layer = Layer::new(width, height, chunk);
+    // Number of 1s times number of 2s on the layer with the fewest 0s
+    pub fn checksum(&self) -> usize {
+        let layer = self.fewest(BLACK);
+        layer.count(WHITE) * layer.count(TRANSPARENT)
+    }
 
-                new_image.layers.push(new_layer);
-            }
-            println!("Found {} layers", new_image.layers.len());
-            new_image
-        }
+    fn get_pixel(&self, w: usize, h: usize) -> Pixel {
+        self.layers
+            .iter()
+            .map(|l| l.pixels[h][w])
+            .find(|&v| v != TRANSPARENT)
+            .unwrap_or(TRANSPARENT)
+    }
 
-        fn get_pixel(&self, w: usize, h: usize) -> char {
-            for l in self.layers.iter() {
-                let v = l.pixels[h][w];
-                match v {
-                    x if x == '0' as u8 => return ' ',
-                    x if x == '1' as u8 => return '.',
-                    _ => (),
-                }
-            }
-            ' '
+    // Stack the layers, the first non-transparent pixel from the top wins
+    pub fn composite(&self) -> Vec<Vec<Pixel>> {
+        (0..self.height)
+            .map(|h| (0..self.width).map(|w| self.get_pixel(w, h)).collect())
+            .collect()
+    }
+
+    // Plain PBM, where 1 is black ink.  Anything left transparent is treated as white.
+    pub fn to_pbm(&self) -> String {
+        let mut out = format!("P1\n{} {}\n", self.width, self.height);
+        for row in self.composite() {
+            let row: Vec<&str> = row
+                .iter()
+                .map(|&p| if p == BLACK { "1" } else { "0" })
+                .collect();
+            out += &row.join(" ");
+            out.push('\n');
         }
+        out
+    }
 
-        fn render(&self) {
-            //To do
-            for h in 0..self.height {
-                for w in 0..self.width {
-                    print!("{}", self.get_pixel(w, h));
-                }
-                println!();
-            }
-            println!();
+    // Plain PGM with black 0, transparent 1 and white 2
+    pub fn to_pgm(&self) -> String {
+        let mut out = format!("P2\n{} {}\n2\n", self.width, self.height);
+        for row in self.composite() {
+            let row: Vec<&str> = row
+                .iter()
+                .map(|&p| match p {
+                    BLACK => "0",
+                    WHITE => "2",
+                    _ => "1",
+                })
+                .collect();
+            out += &row.join(" ");
+            out.push('\n');
         }
+        out
+    }
+
+    pub fn write_pbm(&self, filename: &str) -> io::Result<()> {
+        fs::write(filename, self.to_pbm())
+    }
+
+    pub fn write_pgm(&self, filename: &str) -> io::Result<()> {
+        fs::write(filename, self.to_pgm())
     }
 
+    // Read the message out of the white pixels
+    pub fn render(&self) -> String {
+        let lit: Vec<Vec<bool>> = self
+            .composite()
+            .iter()
+            .map(|row| row.iter().map(|&p| p == WHITE).collect())
+            .collect();
+        ocr::read_grid(&lit)
+    }
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = Image;
+
+    fn parse(input: &str) -> ParseResult<Image> {
+        Ok(Image::new(input, 25, 6)?)
+    }
+
+    fn part1(image: &Image) -> PartResult<impl Display> {
+        Ok(image.checksum())
+    }
+
+    fn part2(image: &Image) -> PartResult<impl Display> {
+        Ok(image.render())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
     #[test]
     fn example2() {
-        let image = Image::new("0222112222120000", 2, 2);
-        image.render();
+        let image = Image::new("0222112222120000", 2, 2).unwrap();
+        assert_eq!(image.composite(), vec![vec![0, 1], vec![1, 0]]);
+        assert_eq!(image.to_pbm(), "P1\n2 2\n1 0\n0 1\n");
+        assert_eq!(image.to_pgm(), "P2\n2 2\n2\n0 2\n2 0\n");
     }
 
     #[test]
     fn part1_2() {
-        let data = fs::read_to_string("input.txt").expect("Unable to read file");
-        let image = Image::new(&data, 25, 6);
-
-        let mut min = 999;
-        let mut min_layer = 0;
-        for (i, l) in image.layers.iter().enumerate() {
-            let count = l.count_char("0");
-            println!("Layer:{} Count:{}", i, count);
-            if count < min {
-                min = count;
-                min_layer = i;
-            }
-        }
-
-        println!("Min layer is {}", min_layer);
+        let data = input::read!("input.txt").expect("Unable to read file");
+        let image = Image::new(&data, 25, 6).unwrap();
 
-        let ones = image.layers[min_layer].count_char("1");
-        let twos = image.layers[min_layer].count_char("2");
-
-        println!("{}*{}={}", ones, twos, ones * twos);
+        let layer = image.fewest(BLACK);
+        let ones = layer.count(WHITE);
+        let twos = layer.count(TRANSPARENT);
 
         assert_eq!(ones, 24);
         assert_eq!(twos, 121);
+        assert_eq!(image.checksum(), 2904);
 
-        image.render();
+        assert_eq!(image.render(), "HGBCF");
     }
 
     #[test]
     fn example1() {
-        let image = Image::new("123456789012", 3, 2);
+        let image = Image::new("123456789012", 3, 2).unwrap();
 
         assert_eq!(image.layers.len(), 2);
         assert_eq!(image.layers[0].pixels.len(), 2);
@@ -130,8 +255,31 @@ mod tests {
         assert_eq!(image.layers[1].pixels[0].len(), 3);
         assert_eq!(image.layers[1].pixels[1].len(), 3);
 
-        for (i, l) in image.layers.iter().enumerate() {
-            println!("Layer:{} Count:{}", i, l.count_char("0"));
-        }
+        assert_eq!(image.layers[0].count(0), 0);
+        assert_eq!(image.layers[1].count(0), 1);
+    }
+    #[test]
+    fn bad_image() {
+        assert_eq!(
+            Image::new("1234x6", 3, 2).unwrap_err(),
+            ImageError::BadPixel {
+                column: 5,
+                found: 'x'
+            }
+        );
+        assert_eq!(
+            Image::new("1234567", 3, 2).unwrap_err(),
+            ImageError::Ragged {
+                pixels: 7,
+                width: 3,
+                height: 2
+            }
+        );
+        assert!(Image::new("", 3, 2).is_err());
+        assert!(Image::new("123456", 0, 2).is_err());
+        assert_eq!(
+            Day8::parse("0222112222120000").err().unwrap().to_string(),
+            "16 pixels don't make whole 25x6 layers"
+        );
     }
 }
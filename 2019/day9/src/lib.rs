@@ -1,12 +1,16 @@
 use OpCode::*;
 use ParameterMode::*;
 
+use parser::{integers, parse_all, ParseError};
+use solution::{ParseResult, PartResult, Solution};
 use std::collections::HashMap;
+use std::fmt::Display;
 
 const VERBOSE: bool = false;
 
 pub struct IntcodeComputer {
     relative_base: isize,
+    instruction_pointer: usize,
     memory: HashMap<usize, isize>,
 }
 
@@ -30,7 +34,8 @@ enum ParameterMode {
     Relative = 2,
 }
 
-enum Status {
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Status {
     Running,
     Halted,
     Paused,
@@ -77,7 +82,7 @@ fn get_instruction_size(input: isize) -> usize {
         x if x == LessThan as isize => 4,
         x if x == Equals as isize => 4,
         x if x == RelativeBaseOffset as isize => 2,
-        _ => panic!(format!("Invalid OpCode ({})", input)),
+        _ => panic!("Invalid OpCode ({})", input),
     }
 }
 
@@ -85,10 +90,17 @@ impl IntcodeComputer {
     fn new() -> Self {
         Self {
             relative_base: 0,
+            instruction_pointer: 0,
             memory: HashMap::new(),
         }
     }
 
+    pub fn load(program: &[isize]) -> Self {
+        let mut computer = IntcodeComputer::new();
+        computer.load_program_into_memory(program);
+        computer
+    }
+
     fn get_parameter(&mut self, mode: ParameterMode, instruction: isize) -> isize {
         match mode {
             Immediate => instruction,
@@ -155,8 +167,10 @@ impl IntcodeComputer {
 
                 let p1 = self.get_output_parameter(p1_mode, instruction[1]);
 
-                if input.len() == 0 {
-                    println!("Pausing evaluation, out of inputs");
+                if input.is_empty() {
+                    if VERBOSE {
+                        println!("Pausing evaluation, out of inputs");
+                    }
                     (Paused, None, None)
                 } else {
                     let val = input.pop().expect("Unable to pop");
@@ -174,9 +188,9 @@ impl IntcodeComputer {
             x if x == Output as isize => {
                 let p1 = self.get_parameter(p1_mode, instruction[1]);
 
-                // if VERBOSE {
-                println!("Out: {}", p1);
-                // }
+                if VERBOSE {
+                    println!("Out: {}", p1);
+                }
 
                 (Running, None, Some(p1))
             }
@@ -265,7 +279,7 @@ impl IntcodeComputer {
 
                 (Halted, None, None)
             }
-            _ => panic!(format!("Invalid OpCode ({})", opcode)),
+            _ => panic!("Invalid OpCode ({})", opcode),
         }
     }
 
@@ -278,63 +292,93 @@ impl IntcodeComputer {
     fn get_next_chunk(&mut self, memory_location: usize, instruction_size: usize) -> [isize; 4] {
         let mut chunk = [0, 0, 0, 0];
 
-        for i in 0..instruction_size {
-            chunk[i] = *self.memory.entry(memory_location + i).or_insert(0);
+        for (i, value) in chunk.iter_mut().enumerate().take(instruction_size) {
+            *value = *self.memory.entry(memory_location + i).or_insert(0);
         }
         chunk
     }
 
-    pub fn read_program(program: &[isize], mut input: &mut Vec<isize>) -> Result<isize, isize> {
-        color_backtrace::install();
-
-        let mut computer = IntcodeComputer::new();
-        computer.load_program_into_memory(&program);
-
-        let mut output = 0;
-        let mut memory_location = 0;
-        let mut chunk;
-
-        let mut instruction_size = get_instruction_size(computer.memory[&memory_location]);
-        chunk = computer.get_next_chunk(memory_location, instruction_size);
+    // Run until the program halts or needs an input that isn't available, returning
+    // every value output along the way.  A paused computer picks up where it left off
+    // on the next call, so inputs can be supplied interactively.
+    pub fn resume(&mut self, input: &mut Vec<isize>) -> (Status, Vec<isize>) {
+        let mut outputs = Vec::new();
 
         loop {
+            let instruction_size = get_instruction_size(self.memory[&self.instruction_pointer]);
+            let chunk = self.get_next_chunk(self.instruction_pointer, instruction_size);
+
             let (status, addr_override, output_var) =
-                &mut computer.process_instruction(&chunk, &mut input);
+                self.process_instruction(&chunk, input);
 
             match status {
-                Halted => return Ok(output),
-                Paused => return Err(output),
+                Halted | Paused => return (status, outputs),
                 Running => {}
             };
 
-            match output_var {
-                Some(v) => output = *v,
-                _ => (),
-            };
+            if let Some(v) = output_var {
+                outputs.push(v);
+            }
 
             match addr_override {
-                Some(v) => {
-                    memory_location = *v;
-                }
-                None => {
-                    memory_location += instruction_size;
-                }
+                Some(v) => self.instruction_pointer = v,
+                None => self.instruction_pointer += instruction_size,
             };
+        }
+    }
+
+    pub fn read_program(program: &[isize], input: &mut Vec<isize>) -> Result<isize, isize> {
+        color_backtrace::install();
 
-            instruction_size = get_instruction_size(computer.memory[&memory_location]);
-            chunk = computer.get_next_chunk(memory_location, instruction_size);
+        let mut computer = IntcodeComputer::load(program);
+        let (status, outputs) = computer.resume(input);
+        let output = outputs.last().copied().unwrap_or(0);
+
+        match status {
+            Halted => Ok(output),
+            _ => Err(output),
         }
     }
 }
 
+// A comma separated Intcode program
+// Comma separated, like "1,9,10,3,2,3,11,0,99,30,40,50"
+pub fn parse_program(input: &str) -> Result<Vec<isize>, ParseError> {
+    parse_all(input, integers(","))
+}
+
+// The BOOST keycode in test mode (1) or the distress signal coordinates (2)
+fn boost(program: &[isize], mode: isize) -> isize {
+    IntcodeComputer::read_program(program, &mut vec![mode]).expect("BOOST waiting on input")
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = Vec<isize>;
+
+    fn parse(input: &str) -> ParseResult<Vec<isize>> {
+        Ok(parse_program(input)?)
+    }
+
+    fn part1(program: &Vec<isize>) -> PartResult<impl Display> {
+        Ok(boost(program, 1))
+    }
+
+    fn part2(program: &Vec<isize>) -> PartResult<impl Display> {
+        Ok(boost(program, 2))
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::IntcodeComputer;
+    use crate::Status::*;
+    use crate::{parse_program, IntcodeComputer};
 
     #[test]
     fn web_test_1() {
         // sum-of-primes: This program takes a single input and produces a single output, the sum of all primes up to the input.
-        let mut memory = [
+        let memory = [
             3, 100, 1007, 100, 2, 7, 1105, -1, 87, 1007, 100, 1, 14, 1105, -1, 27, 101, -2, 100,
             100, 101, 1, 101, 101, 1105, 1, 9, 101, 105, 101, 105, 101, 2, 104, 104, 101, 1, 102,
             102, 1, 102, 102, 103, 101, 1, 103, 103, 7, 102, 101, 52, 1106, -1, 87, 101, 105, 102,
@@ -343,70 +387,18 @@ mod tests {
         ];
         assert_eq!(
             Ok(17),
-            IntcodeComputer::read_program(&mut memory, &mut vec![10])
+            IntcodeComputer::read_program(&memory, &mut vec![10])
         );
 
         assert_eq!(
             Ok(142913828922),
-            IntcodeComputer::read_program(&mut memory, &mut vec![2000000])
+            IntcodeComputer::read_program(&memory, &mut vec![2000000])
         );
     }
 
     #[test]
     fn part_1() {
-        let memory = [
-            1102, 34463338, 34463338, 63, 1007, 63, 34463338, 63, 1005, 63, 53, 1101, 0, 3, 1000,
-            109, 988, 209, 12, 9, 1000, 209, 6, 209, 3, 203, 0, 1008, 1000, 1, 63, 1005, 63, 65,
-            1008, 1000, 2, 63, 1005, 63, 904, 1008, 1000, 0, 63, 1005, 63, 58, 4, 25, 104, 0, 99,
-            4, 0, 104, 0, 99, 4, 17, 104, 0, 99, 0, 0, 1101, 0, 36, 1015, 1102, 1, 387, 1028, 1101,
-            24, 0, 1016, 1101, 0, 23, 1008, 1102, 1, 35, 1012, 1102, 1, 554, 1023, 1101, 29, 0,
-            1003, 1101, 27, 0, 1011, 1101, 25, 0, 1000, 1101, 0, 38, 1018, 1102, 20, 1, 1019, 1102,
-            28, 1, 1005, 1102, 1, 619, 1026, 1102, 1, 22, 1004, 1101, 0, 0, 1020, 1101, 0, 31,
-            1009, 1102, 1, 783, 1024, 1102, 1, 33, 1001, 1102, 616, 1, 1027, 1102, 1, 21, 1006,
-            1101, 32, 0, 1013, 1102, 39, 1, 1014, 1102, 1, 378, 1029, 1101, 774, 0, 1025, 1102, 1,
-            1, 1021, 1102, 30, 1, 1007, 1102, 37, 1, 1002, 1102, 1, 26, 1017, 1101, 0, 557, 1022,
-            1102, 1, 34, 1010, 109, 13, 2101, 0, -5, 63, 1008, 63, 23, 63, 1005, 63, 203, 4, 187,
-            1105, 1, 207, 1001, 64, 1, 64, 1002, 64, 2, 64, 109, -14, 2107, 28, 4, 63, 1005, 63,
-            225, 4, 213, 1106, 0, 229, 1001, 64, 1, 64, 1002, 64, 2, 64, 109, 10, 1207, -3, 20, 63,
-            1005, 63, 245, 1106, 0, 251, 4, 235, 1001, 64, 1, 64, 1002, 64, 2, 64, 109, 8, 1205, 3,
-            263, 1105, 1, 269, 4, 257, 1001, 64, 1, 64, 1002, 64, 2, 64, 109, -9, 1207, -7, 34, 63,
-            1005, 63, 287, 4, 275, 1105, 1, 291, 1001, 64, 1, 64, 1002, 64, 2, 64, 109, -4, 2102,
-            1, -3, 63, 1008, 63, 32, 63, 1005, 63, 311, 1105, 1, 317, 4, 297, 1001, 64, 1, 64,
-            1002, 64, 2, 64, 109, 21, 21101, 40, 0, -6, 1008, 1019, 43, 63, 1005, 63, 337, 1106, 0,
-            343, 4, 323, 1001, 64, 1, 64, 1002, 64, 2, 64, 109, -26, 1202, 7, 1, 63, 1008, 63, 21,
-            63, 1005, 63, 365, 4, 349, 1106, 0, 369, 1001, 64, 1, 64, 1002, 64, 2, 64, 109, 26,
-            2106, 0, 3, 4, 375, 1001, 64, 1, 64, 1105, 1, 387, 1002, 64, 2, 64, 109, -9, 21108, 41,
-            40, 3, 1005, 1019, 407, 1001, 64, 1, 64, 1106, 0, 409, 4, 393, 1002, 64, 2, 64, 109,
-            13, 1205, -8, 423, 4, 415, 1106, 0, 427, 1001, 64, 1, 64, 1002, 64, 2, 64, 109, -19,
-            21107, 42, 41, 5, 1005, 1015, 447, 1001, 64, 1, 64, 1106, 0, 449, 4, 433, 1002, 64, 2,
-            64, 109, -3, 2102, 1, -5, 63, 1008, 63, 37, 63, 1005, 63, 471, 4, 455, 1105, 1, 475,
-            1001, 64, 1, 64, 1002, 64, 2, 64, 109, -2, 1201, 0, 0, 63, 1008, 63, 28, 63, 1005, 63,
-            497, 4, 481, 1105, 1, 501, 1001, 64, 1, 64, 1002, 64, 2, 64, 109, 8, 2107, 29, -8, 63,
-            1005, 63, 521, 1001, 64, 1, 64, 1106, 0, 523, 4, 507, 1002, 64, 2, 64, 109, -3, 1208,
-            -3, 30, 63, 1005, 63, 541, 4, 529, 1106, 0, 545, 1001, 64, 1, 64, 1002, 64, 2, 64, 109,
-            4, 2105, 1, 9, 1105, 1, 563, 4, 551, 1001, 64, 1, 64, 1002, 64, 2, 64, 109, 9, 1206,
-            -3, 581, 4, 569, 1001, 64, 1, 64, 1106, 0, 581, 1002, 64, 2, 64, 109, -8, 1201, -9, 0,
-            63, 1008, 63, 23, 63, 1005, 63, 605, 1001, 64, 1, 64, 1106, 0, 607, 4, 587, 1002, 64,
-            2, 64, 109, 21, 2106, 0, -9, 1106, 0, 625, 4, 613, 1001, 64, 1, 64, 1002, 64, 2, 64,
-            109, -35, 2108, 31, 8, 63, 1005, 63, 647, 4, 631, 1001, 64, 1, 64, 1105, 1, 647, 1002,
-            64, 2, 64, 109, 2, 1202, 0, 1, 63, 1008, 63, 30, 63, 1005, 63, 667, 1105, 1, 673, 4,
-            653, 1001, 64, 1, 64, 1002, 64, 2, 64, 109, 17, 21108, 43, 43, -4, 1005, 1016, 691, 4,
-            679, 1106, 0, 695, 1001, 64, 1, 64, 1002, 64, 2, 64, 109, -14, 1208, -1, 30, 63, 1005,
-            63, 711, 1106, 0, 717, 4, 701, 1001, 64, 1, 64, 1002, 64, 2, 64, 109, 6, 21101, 44, 0,
-            -1, 1008, 1011, 44, 63, 1005, 63, 739, 4, 723, 1105, 1, 743, 1001, 64, 1, 64, 1002, 64,
-            2, 64, 109, -15, 2108, 30, 8, 63, 1005, 63, 759, 1106, 0, 765, 4, 749, 1001, 64, 1, 64,
-            1002, 64, 2, 64, 109, 27, 2105, 1, 0, 4, 771, 1001, 64, 1, 64, 1105, 1, 783, 1002, 64,
-            2, 64, 109, -9, 1206, 6, 795, 1105, 1, 801, 4, 789, 1001, 64, 1, 64, 1002, 64, 2, 64,
-            109, 4, 21102, 45, 1, -7, 1008, 1012, 45, 63, 1005, 63, 823, 4, 807, 1105, 1, 827,
-            1001, 64, 1, 64, 1002, 64, 2, 64, 109, -14, 21102, 46, 1, 5, 1008, 1010, 43, 63, 1005,
-            63, 851, 1001, 64, 1, 64, 1105, 1, 853, 4, 833, 1002, 64, 2, 64, 109, -1, 2101, 0, 1,
-            63, 1008, 63, 25, 63, 1005, 63, 873, 1105, 1, 879, 4, 859, 1001, 64, 1, 64, 1002, 64,
-            2, 64, 109, 9, 21107, 47, 48, -3, 1005, 1010, 897, 4, 885, 1105, 1, 901, 1001, 64, 1,
-            64, 4, 64, 99, 21101, 0, 27, 1, 21101, 915, 0, 0, 1106, 0, 922, 21201, 1, 57526, 1,
-            204, 1, 99, 109, 3, 1207, -2, 3, 63, 1005, 63, 964, 21201, -2, -1, 1, 21101, 942, 0, 0,
-            1106, 0, 922, 21201, 1, 0, -1, 21201, -2, -3, 1, 21101, 957, 0, 0, 1106, 0, 922, 22201,
-            1, -1, -2, 1105, 1, 968, 21202, -2, 1, -2, 109, -3, 2106, 0, 0,
-        ];
+        let memory = parse_program(&input::read!("input.txt").unwrap()).unwrap();
 
         assert_eq!(
             Ok(3380552333),
@@ -419,6 +411,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn resume_after_pause() {
+        // echo a single input back out
+        let memory = [3, 0, 4, 0, 99];
+        let mut computer = IntcodeComputer::load(&memory);
+
+        assert_eq!(computer.resume(&mut vec![]), (Paused, vec![]));
+        assert_eq!(computer.resume(&mut vec![42]), (Halted, vec![42]));
+    }
+
     #[test]
     fn example1_1() {
         //takes no input and produces a copy of itself as output.
@@ -452,46 +454,7 @@ mod tests {
 
     #[test]
     fn day5_part1_2() {
-        let memory = [
-            3, 225, 1, 225, 6, 6, 1100, 1, 238, 225, 104, 0, 1101, 32, 43, 225, 101, 68, 192, 224,
-            1001, 224, -160, 224, 4, 224, 102, 8, 223, 223, 1001, 224, 2, 224, 1, 223, 224, 223,
-            1001, 118, 77, 224, 1001, 224, -87, 224, 4, 224, 102, 8, 223, 223, 1001, 224, 6, 224,
-            1, 223, 224, 223, 1102, 5, 19, 225, 1102, 74, 50, 224, 101, -3700, 224, 224, 4, 224,
-            1002, 223, 8, 223, 1001, 224, 1, 224, 1, 223, 224, 223, 1102, 89, 18, 225, 1002, 14,
-            72, 224, 1001, 224, -3096, 224, 4, 224, 102, 8, 223, 223, 101, 5, 224, 224, 1, 223,
-            224, 223, 1101, 34, 53, 225, 1102, 54, 10, 225, 1, 113, 61, 224, 101, -39, 224, 224, 4,
-            224, 102, 8, 223, 223, 101, 2, 224, 224, 1, 223, 224, 223, 1101, 31, 61, 224, 101, -92,
-            224, 224, 4, 224, 102, 8, 223, 223, 1001, 224, 4, 224, 1, 223, 224, 223, 1102, 75, 18,
-            225, 102, 48, 87, 224, 101, -4272, 224, 224, 4, 224, 102, 8, 223, 223, 1001, 224, 7,
-            224, 1, 224, 223, 223, 1101, 23, 92, 225, 2, 165, 218, 224, 101, -3675, 224, 224, 4,
-            224, 1002, 223, 8, 223, 101, 1, 224, 224, 1, 223, 224, 223, 1102, 8, 49, 225, 4, 223,
-            99, 0, 0, 0, 677, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1105, 0, 99999, 1105, 227, 247,
-            1105, 1, 99999, 1005, 227, 99999, 1005, 0, 256, 1105, 1, 99999, 1106, 227, 99999, 1106,
-            0, 265, 1105, 1, 99999, 1006, 0, 99999, 1006, 227, 274, 1105, 1, 99999, 1105, 1, 280,
-            1105, 1, 99999, 1, 225, 225, 225, 1101, 294, 0, 0, 105, 1, 0, 1105, 1, 99999, 1106, 0,
-            300, 1105, 1, 99999, 1, 225, 225, 225, 1101, 314, 0, 0, 106, 0, 0, 1105, 1, 99999,
-            1107, 226, 226, 224, 1002, 223, 2, 223, 1005, 224, 329, 1001, 223, 1, 223, 1007, 677,
-            226, 224, 1002, 223, 2, 223, 1006, 224, 344, 1001, 223, 1, 223, 108, 677, 226, 224,
-            102, 2, 223, 223, 1006, 224, 359, 1001, 223, 1, 223, 7, 226, 226, 224, 1002, 223, 2,
-            223, 1005, 224, 374, 101, 1, 223, 223, 107, 677, 677, 224, 1002, 223, 2, 223, 1006,
-            224, 389, 1001, 223, 1, 223, 1007, 677, 677, 224, 1002, 223, 2, 223, 1006, 224, 404,
-            1001, 223, 1, 223, 1107, 677, 226, 224, 1002, 223, 2, 223, 1005, 224, 419, 1001, 223,
-            1, 223, 108, 226, 226, 224, 102, 2, 223, 223, 1006, 224, 434, 1001, 223, 1, 223, 1108,
-            226, 677, 224, 1002, 223, 2, 223, 1006, 224, 449, 1001, 223, 1, 223, 1108, 677, 226,
-            224, 102, 2, 223, 223, 1005, 224, 464, 1001, 223, 1, 223, 107, 226, 226, 224, 102, 2,
-            223, 223, 1006, 224, 479, 1001, 223, 1, 223, 1008, 226, 226, 224, 102, 2, 223, 223,
-            1005, 224, 494, 101, 1, 223, 223, 7, 677, 226, 224, 1002, 223, 2, 223, 1005, 224, 509,
-            101, 1, 223, 223, 8, 226, 677, 224, 1002, 223, 2, 223, 1006, 224, 524, 1001, 223, 1,
-            223, 1007, 226, 226, 224, 1002, 223, 2, 223, 1006, 224, 539, 101, 1, 223, 223, 1008,
-            677, 677, 224, 1002, 223, 2, 223, 1006, 224, 554, 101, 1, 223, 223, 1108, 677, 677,
-            224, 102, 2, 223, 223, 1006, 224, 569, 101, 1, 223, 223, 1107, 226, 677, 224, 102, 2,
-            223, 223, 1005, 224, 584, 1001, 223, 1, 223, 8, 677, 226, 224, 1002, 223, 2, 223, 1006,
-            224, 599, 101, 1, 223, 223, 1008, 677, 226, 224, 102, 2, 223, 223, 1006, 224, 614,
-            1001, 223, 1, 223, 7, 226, 677, 224, 1002, 223, 2, 223, 1005, 224, 629, 101, 1, 223,
-            223, 107, 226, 677, 224, 102, 2, 223, 223, 1005, 224, 644, 101, 1, 223, 223, 8, 677,
-            677, 224, 102, 2, 223, 223, 1005, 224, 659, 1001, 223, 1, 223, 108, 677, 677, 224,
-            1002, 223, 2, 223, 1005, 224, 674, 101, 1, 223, 223, 4, 223, 99, 226,
-        ];
+        let memory = parse_program(&input::read!("../day5/input.txt").unwrap()).unwrap();
         assert_eq!(
             Ok(5821753),
             IntcodeComputer::read_program(&memory.clone(), &mut vec![1])
@@ -606,4 +569,11 @@ mod tests {
             IntcodeComputer::read_program(&memory.clone(), &mut vec![9])
         );
     }
+
+    #[test]
+    fn bad_program() {
+        let error = parse_program("104,1125899906842624,99\n109;1").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.found, "109;1");
+    }
 }
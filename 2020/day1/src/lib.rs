@@ -1,21 +1,15 @@
+use input::InputError;
+use parser::{integer, lines, parse_all, ParseError};
+use solution::{ParseResult, PartResult, Solution};
 use std::collections::BTreeSet;
-use std::fs::File;
-use std::io::Read;
-
-pub fn parse_input() -> Vec<i32> {
-    let mut contents = String::new();
-    let mut file = File::open("input.txt").unwrap();
-    file.read_to_string(&mut contents).unwrap();
-
-    let collection: Vec<&str> = contents.split('\n').collect();
-    let data = || {
-        let mut data = Vec::new();
-        for c in collection {
-            data.push(c.parse().unwrap());
-        }
-        data
-    };
-    data()
+use std::fmt::Display;
+
+pub fn parse_input() -> Result<Vec<i32>, InputError> {
+    input::parse!("input.txt", parse_entries)
+}
+
+pub fn parse_entries(contents: &str) -> Result<Vec<i32>, ParseError> {
+    parse_all(contents, lines(integer))
 }
 
 pub fn find_pairs(data: Vec<i32>) -> (i32, i32) {
@@ -58,6 +52,26 @@ pub fn find_triple(data: Vec<i32>) -> (i32, i32, i32) {
     panic!("unable to solve!")
 }
 
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<i32>;
+
+    fn parse(input: &str) -> ParseResult<Vec<i32>> {
+        Ok(parse_entries(input)?)
+    }
+
+    fn part1(data: &Vec<i32>) -> PartResult<impl Display> {
+        let pair = find_pairs(data.clone());
+        Ok(pair.0 * pair.1)
+    }
+
+    fn part2(data: &Vec<i32>) -> PartResult<impl Display> {
+        let triple = find_triple(data.clone());
+        Ok(triple.0 * triple.1 * triple.2)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -76,7 +90,7 @@ mod tests {
 
     #[test]
     fn part_1_solution() {
-        let data = parse_input();
+        let data = parse_input().unwrap();
         let pair = find_pairs(data);
 
         println!(
@@ -103,7 +117,7 @@ mod tests {
 
     #[test]
     fn part_2_solution() {
-        let data = parse_input();
+        let data = parse_input().unwrap();
         let triple = find_triple(data);
 
         println!(
@@ -114,4 +128,11 @@ mod tests {
             triple.0 * triple.1 * triple.2
         );
     }
+
+    #[test]
+    fn bad_entry() {
+        let error = parse_entries("1721\n979\n366x\n299").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 4));
+        assert_eq!(error.expected, "end of line");
+    }
 }
@@ -1,19 +1,20 @@
 #![recursion_limit = "8192"]
-use std::fs::File;
-use std::io::Read;
+use input::InputError;
+use parser::{integer, lines, parse_all, ParseError};
+use solution::{ParseResult, PartResult, Solution};
+use std::fmt::Display;
 
-pub fn read_adapters(filename: &str) -> Vec<i32> {
-    let mut contents = String::new();
-    let mut file = File::open(filename).unwrap();
-    file.read_to_string(&mut contents).unwrap();
+pub fn read_adapters(filename: &str) -> Result<Vec<i32>, InputError> {
+    input::parse!(filename, parse_adapters)
+}
 
-    let collection: Vec<i32> = contents.split("\n").map(|x| x.parse().unwrap()).collect();
-    collection
+pub fn parse_adapters(contents: &str) -> Result<Vec<i32>, ParseError> {
+    parse_all(contents, lines(integer))
 }
 
-pub fn order_adapters(adapters: &Vec<i32>) -> Vec<i32> {
+pub fn order_adapters(adapters: &[i32]) -> Vec<i32> {
     let mut ordered_adapters = vec![0];
-    ordered_adapters.append(&mut adapters.clone());
+    ordered_adapters.extend_from_slice(adapters);
     ordered_adapters.sort();
 
     ordered_adapters.push(ordered_adapters.iter().max().unwrap() + 3);
@@ -21,7 +22,7 @@ pub fn order_adapters(adapters: &Vec<i32>) -> Vec<i32> {
     ordered_adapters
 }
 
-pub fn get_n_jolt_differences(joltages: &Vec<i32>, diff_size: i32) -> i32 {
+pub fn get_n_jolt_differences(joltages: &[i32], diff_size: i32) -> i32 {
     let mut diff = 0;
 
     for i in 0..joltages.len() - 1 {
@@ -33,13 +34,12 @@ pub fn get_n_jolt_differences(joltages: &Vec<i32>, diff_size: i32) -> i32 {
 }
 
 // This algorithmically works, but might take the age of the universe to complete...
-fn explore(joltages: &Vec<i32>) -> usize {
+pub fn explore(joltages: &[i32]) -> usize {
     let mut solutions = 0;
     let mut exploration: Vec<Vec<i32>> = vec![vec![joltages[0]]];
 
     let max_joltage = joltages.iter().max().unwrap();
-    while exploration.len() > 0 {
-        let instance = exploration.pop().unwrap();
+    while let Some(instance) = exploration.pop() {
         let instance_max = instance.iter().max().unwrap();
         let idx = joltages.iter().position(|x| x == instance_max).unwrap();
 
@@ -61,8 +61,7 @@ fn explore(joltages: &Vec<i32>) -> usize {
     solutions
 }
 
-fn exploreDP(joltages: &Vec<i32>) -> usize {
-    let  joltages = joltages.clone();
+pub fn explore_dp(joltages: &[i32]) -> usize {
     let len = joltages.len();
 
     let mut pow2 = 0;
@@ -80,9 +79,27 @@ fn exploreDP(joltages: &Vec<i32>) -> usize {
     2_usize.pow(pow2) * 7_usize.pow(pow7)
 }
 
-pub fn count_arrangements(joltages: &Vec<i32>) -> usize {
+pub fn count_arrangements(joltages: &[i32]) -> usize {
     // explore(joltages) // caveat emptor!
-    exploreDP(joltages)
+    explore_dp(joltages)
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Vec<i32>;
+
+    fn parse(input: &str) -> ParseResult<Vec<i32>> {
+        Ok(order_adapters(&parse_adapters(input)?))
+    }
+
+    fn part1(joltages: &Vec<i32>) -> PartResult<impl Display> {
+        Ok(get_n_jolt_differences(joltages, 1) * get_n_jolt_differences(joltages, 3))
+    }
+
+    fn part2(joltages: &Vec<i32>) -> PartResult<impl Display> {
+        Ok(count_arrangements(joltages))
+    }
 }
 
 #[cfg(test)]
@@ -91,7 +108,7 @@ mod tests {
 
     #[test]
     fn part_1_example() {
-        let adapters = read_adapters("example1.txt");
+        let adapters = read_adapters("example1.txt").unwrap();
         let ordered_adapters = order_adapters(&adapters);
 
         let n1 = get_n_jolt_differences(&ordered_adapters, 1);
@@ -103,7 +120,7 @@ mod tests {
 
     #[test]
     fn part_1_solution() {
-        let adapters = read_adapters("input.txt");
+        let adapters = read_adapters("input.txt").unwrap();
         let ordered_adapters = order_adapters(&adapters);
 
         let n1 = get_n_jolt_differences(&ordered_adapters, 1);
@@ -117,7 +134,7 @@ mod tests {
 
     #[test]
     fn part_2_example() {
-        let adapters = read_adapters("example1.txt");
+        let adapters = read_adapters("example1.txt").unwrap();
         let ordered_adapters = order_adapters(&adapters);
 
         let arrangements = count_arrangements(&ordered_adapters);
@@ -127,7 +144,7 @@ mod tests {
 
     #[test]
     fn part_2_solution() {
-        let adapters = read_adapters("input.txt");
+        let adapters = read_adapters("input.txt").unwrap();
         let ordered_adapters = order_adapters(&adapters);
 
         let arrangements = count_arrangements(&ordered_adapters);
@@ -135,4 +152,10 @@ mod tests {
 
         assert_eq!(arrangements, 5289227976704);
     }
+
+    #[test]
+    fn bad_adapter() {
+        let error = parse_adapters("16\n10\n15\n5 1").unwrap_err();
+        assert_eq!((error.line, error.column), (4, 2));
+    }
 }
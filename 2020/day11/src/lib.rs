@@ -1,12 +1,7 @@
-use std::fs::File;
-use std::io::Read;
-
-#[derive(Debug, Copy, Clone)]
-pub struct Position {
-    x: isize,
-    y: isize,
-    status: Status,
-}
+use grid::{Grid, GridError, Point, SURROUNDING};
+use input::InputError;
+use solution::{ParseResult, PartResult, Solution};
+use std::fmt::Display;
 
 #[derive(Debug, PartialEq, Copy, Clone)]
 pub enum Status {
@@ -15,169 +10,107 @@ pub enum Status {
     Floor,
 }
 
-pub fn read_input(filename: &str) -> Vec<String> {
-    let mut contents = String::new();
-    let mut file = File::open(filename).unwrap();
-    file.read_to_string(&mut contents).unwrap();
-
-    let collection: Vec<String> = contents.split("\n").map(|x| x.to_string()).collect();
-    collection
+pub fn read_input(filename: &str) -> Result<Grid<Status>, InputError> {
+    input::parse!(filename, parse_positions)
 }
 
-pub fn input_to_positions(input: &Vec<String>) -> Vec<Position> {
-    let mut positions = Vec::with_capacity(input.len());
-
-    for (y, line) in input.iter().enumerate() {
-        for (x, v) in line.chars().enumerate() {
-            positions.push(match v {
-                'L' => Position {
-                    x: x as isize,
-                    y: y as isize,
-                    status: Status::Empty,
-                },
-                '.' => Position {
-                    x: x as isize,
-                    y: y as isize,
-                    status: Status::Floor,
-                },
-                '#' => Position {
-                    x: x as isize,
-                    y: y as isize,
-                    status: Status::Occupied,
-                },
-                _ => panic!("invalid status"),
-            })
-        }
+fn status(v: char) -> Option<Status> {
+    match v {
+        'L' => Some(Status::Empty),
+        '.' => Some(Status::Floor),
+        '#' => Some(Status::Occupied),
+        _ => None,
     }
+}
+
+pub fn parse_positions(contents: &str) -> Result<Grid<Status>, GridError> {
+    Grid::try_parse(contents, status)
+}
+
+pub fn count_occupied(positions: &Grid<Status>) -> isize {
     positions
+        .values()
+        .filter(|&&status| status == Status::Occupied)
+        .count() as isize
 }
 
-pub fn count_occupied(positions: &Vec<Position>) -> isize {
+fn count_adjacent_occupied(positions: &Grid<Status>, x: usize, y: usize) -> isize {
     positions
-        .iter()
-        .filter(|&p| p.status == Status::Occupied)
+        .neighbours8((x, y))
+        .filter(|&p| positions[p] == Status::Occupied)
         .count() as isize
 }
 
-fn get_position_status(positions: &Vec<Position>, x: isize, y: isize) -> Option<Status> {
+// Whether the first seat seen looking along the axis is occupied
+fn visible_along_axis(positions: &Grid<Status>, p: Point, axis: (isize, isize)) -> bool {
     positions
+        .ray(p, axis)
+        .map(|q| positions[q])
+        .find(|&status| status != Status::Floor)
+        == Some(Status::Occupied)
+}
+
+fn count_visible_occupied(positions: &Grid<Status>, x: usize, y: usize) -> isize {
+    SURROUNDING
         .iter()
-        .find(|&p| p.x == x && p.y == y)
-        .map(|p| p.status)
+        .filter(|&&axis| visible_along_axis(positions, (x, y), axis))
+        .count() as isize
 }
 
-// a nice example of abused syntax because I was feeling "clever"... Ew!
-fn count_adjacent_occupied(positions: &Vec<Position>, x: isize, y: isize) -> isize {
-    [
-        if x > 0 {
-            get_position_status(&positions, x - 1, y)
-        } else {
-            None
+pub fn evaluate_rules(positions: &Grid<Status>) -> Grid<Status> {
+    Grid::from_fn(
+        positions.width(),
+        positions.height(),
+        |(x, y)| match positions[(x, y)] {
+            Status::Empty if count_adjacent_occupied(positions, x, y) == 0 => Status::Occupied,
+            Status::Occupied if count_adjacent_occupied(positions, x, y) >= 4 => Status::Empty,
+            status => status,
         },
-        get_position_status(&positions, x + 1, y),
-        get_position_status(&positions, x, y + 1),
-        if y > 0 {
-            get_position_status(&positions, x, y - 1)
-        } else {
-            None
-        },
-        get_position_status(&positions, x + 1, y + 1),
-        if x > 0 {
-            get_position_status(&positions, x - 1, y + 1)
-        } else {
-            None
-        },
-        if y > 0 {
-            get_position_status(&positions, x + 1, y - 1)
-        } else {
-            None
-        },
-        if x > 0 && y > 0 {
-            get_position_status(&positions, x - 1, y - 1)
-        } else {
-            None
+    )
+}
+
+pub fn evaluate_new_rules(positions: &Grid<Status>) -> Grid<Status> {
+    Grid::from_fn(
+        positions.width(),
+        positions.height(),
+        |(x, y)| match positions[(x, y)] {
+            Status::Empty if count_visible_occupied(positions, x, y) == 0 => Status::Occupied,
+            Status::Occupied if count_visible_occupied(positions, x, y) >= 5 => Status::Empty,
+            status => status,
         },
-    ]
-    .iter()
-    .fold(0, |x, &y| {
-        if y == Some(Status::Occupied) {
-            x + 1
-        } else {
-            x
-        }
-    })
+    )
 }
 
-fn visible_along_axis(
-    positions: &Vec<Position>,
-    x: isize,
-    y: isize,
-    dx: isize,
-    dy: isize,
-) -> isize {
-    let mut ds = 0;
+// Apply a rule until the number of occupied seats stops changing
+pub fn settle(positions: &Grid<Status>, rule: fn(&Grid<Status>) -> Grid<Status>) -> isize {
+    let mut positions = positions.clone();
+    let mut count = 0;
     loop {
-        ds += 1;
-        match get_position_status(positions, x + dx * ds, y + dy * ds) {
-            None => return 0,
-            Some(Status::Occupied) => return 1,
-            Some(Status::Empty) => return 0,
-            _ => {}
+        positions = rule(&positions);
+        let new_count = count_occupied(&positions);
+        if count == new_count {
+            return count;
         }
+        count = new_count;
     }
 }
 
-fn count_visible_occupied(positions: &Vec<Position>, x: isize, y: isize) -> isize {
-    visible_along_axis(positions, x, y, 1, 0)
-        + visible_along_axis(positions, x, y, -1, 0)
-        + visible_along_axis(positions, x, y, 0, 1)
-        + visible_along_axis(positions, x, y, 0, -1)
-        + visible_along_axis(positions, x, y, 1, 1)
-        + visible_along_axis(positions, x, y, 1, -1)
-        + visible_along_axis(positions, x, y, -1, 1)
-        + visible_along_axis(positions, x, y, -1, -1)
-}
-
-pub fn evaluate_rules(positions: &Vec<Position>) -> Vec<Position> {
-    let mut new_positions = Vec::with_capacity(positions.len());
+pub struct Day11;
 
-    for position in positions {
-        let mut position = position.clone();
+impl Solution for Day11 {
+    type Input = Grid<Status>;
 
-        if position.status == Status::Empty
-            && count_adjacent_occupied(&positions, position.x, position.y) == 0
-        {
-            position.status = Status::Occupied;
-        } else if position.status == Status::Occupied
-            && count_adjacent_occupied(&positions, position.x, position.y) >= 4
-        {
-            position.status = Status::Empty;
-        }
-
-        new_positions.push(position)
+    fn parse(input: &str) -> ParseResult<Grid<Status>> {
+        Ok(parse_positions(input)?)
     }
-    new_positions
-}
-
-pub fn evaluate_new_rules(positions: &Vec<Position>) -> Vec<Position> {
-    let mut new_positions = Vec::with_capacity(positions.len());
 
-    for position in positions {
-        let mut position = position.clone();
-
-        if position.status == Status::Empty
-            && count_visible_occupied(&positions, position.x, position.y) == 0
-        {
-            position.status = Status::Occupied;
-        } else if position.status == Status::Occupied
-            && count_visible_occupied(&positions, position.x, position.y) >= 5
-        {
-            position.status = Status::Empty;
-        }
+    fn part1(positions: &Grid<Status>) -> PartResult<impl Display> {
+        Ok(settle(positions, evaluate_rules))
+    }
 
-        new_positions.push(position)
+    fn part2(positions: &Grid<Status>) -> PartResult<impl Display> {
+        Ok(settle(positions, evaluate_new_rules))
     }
-    new_positions
 }
 
 #[cfg(test)]
@@ -186,8 +119,7 @@ mod tests {
 
     #[test]
     fn part_1_example() {
-        let input = read_input("example1.txt");
-        let mut positions = input_to_positions(&input);
+        let mut positions = read_input("example1.txt").unwrap();
         let mut count = 0;
 
         positions = evaluate_rules(&positions);
@@ -206,8 +138,7 @@ mod tests {
 
     #[test]
     fn part_1_solution() {
-        let input = read_input("input.txt");
-        let mut positions = input_to_positions(&input);
+        let mut positions = read_input("input.txt").unwrap();
         let mut count = 0;
 
         positions = evaluate_rules(&positions);
@@ -226,8 +157,7 @@ mod tests {
 
     #[test]
     fn part_2_example_a() {
-        let input = read_input("example_of_8.txt");
-        let positions = input_to_positions(&input);
+        let positions = read_input("example_of_8.txt").unwrap();
         let count = count_visible_occupied(&positions, 3, 4);
 
         assert_eq!(count, 8);
@@ -235,8 +165,7 @@ mod tests {
 
     #[test]
     fn part_2_example_b() {
-        let input = read_input("example_of_0_tricky.txt");
-        let positions = input_to_positions(&input);
+        let positions = read_input("example_of_0_tricky.txt").unwrap();
         let count = count_visible_occupied(&positions, 1, 1);
 
         assert_eq!(count, 0);
@@ -244,8 +173,7 @@ mod tests {
 
     #[test]
     fn part_2_example_c() {
-        let input = read_input("example_of_0.txt");
-        let positions = input_to_positions(&input);
+        let positions = read_input("example_of_0.txt").unwrap();
         let count = count_visible_occupied(&positions, 3, 3);
 
         assert_eq!(count, 0);
@@ -253,8 +181,7 @@ mod tests {
 
     #[test]
     fn part_2_example() {
-        let input = read_input("example1.txt");
-        let mut positions = input_to_positions(&input);
+        let mut positions = read_input("example1.txt").unwrap();
         let mut count = 0;
 
         positions = evaluate_rules(&positions);
@@ -275,8 +202,7 @@ mod tests {
 
     #[test]
     fn part_2_solution() {
-        let input = read_input("input.txt");
-        let mut positions = input_to_positions(&input);
+        let mut positions = read_input("input.txt").unwrap();
         let mut count = 0;
 
         positions = evaluate_rules(&positions);
@@ -292,4 +218,16 @@ mod tests {
 
         assert_eq!(count, 2134);
     }
+
+    #[test]
+    fn bad_seat() {
+        assert_eq!(
+            parse_positions("L.LL.LL.LL\nLLLLLLL.LL\nL.L.L..l..").unwrap_err(),
+            GridError::BadCell {
+                line: 3,
+                column: 8,
+                found: 'l'
+            }
+        );
+    }
 }
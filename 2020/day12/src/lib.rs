@@ -1,5 +1,14 @@
-use std::fs::File;
-use std::io::Read;
+use input::InputError;
+use parser::nom::{
+    branch::alt,
+    character::complete::char,
+    combinator::{cut, map},
+    error::context,
+    sequence::preceded,
+};
+use parser::{integer, lines, parse_all, ParseError, Parsed};
+use solution::{ParseResult, PartResult, Solution};
+use std::fmt::Display;
 
 #[derive(Debug)]
 pub enum Action {
@@ -45,6 +54,12 @@ pub struct ShipActor {
     y_pos: i32,
 }
 
+impl Default for ShipActor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ShipActor {
     pub fn new() -> Self {
         ShipActor {
@@ -90,6 +105,12 @@ pub struct WaypointActor {
     pub y_pos: i32,
 }
 
+impl Default for WaypointActor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl WaypointActor {
     pub fn new() -> Self {
         WaypointActor {
@@ -146,31 +167,54 @@ impl WaypointActor {
     }
 }
 
-pub fn read_input(filename: &str) -> Vec<Action> {
-    let mut contents = String::new();
-    let mut file = File::open(filename).unwrap();
-    file.read_to_string(&mut contents).unwrap();
-
-    let collection: Vec<&str> = contents.split("\n").collect();
-
-    collection
-        .iter()
-        .map(|x| {
-            let mut c = x.chars();
-            let t = c.next().unwrap();
-            let v = c.as_str();
-            match t {
-                'N' => Action::N(v.parse().unwrap()),
-                'S' => Action::S(v.parse().unwrap()),
-                'E' => Action::E(v.parse().unwrap()),
-                'W' => Action::W(v.parse().unwrap()),
-                'L' => Action::L(v.parse().unwrap()),
-                'R' => Action::R(v.parse().unwrap()),
-                'F' => Action::F(v.parse().unwrap()),
-                _ => panic!(""),
-            }
-        })
-        .collect()
+pub fn read_input(filename: &str) -> Result<Vec<Action>, InputError> {
+    input::parse!(filename, parse_input)
+}
+
+// "F10"
+fn action(input: &str) -> Parsed<'_, Action> {
+    context(
+        "action",
+        alt((
+            map(preceded(char('N'), cut(integer)), Action::N),
+            map(preceded(char('S'), cut(integer)), Action::S),
+            map(preceded(char('E'), cut(integer)), Action::E),
+            map(preceded(char('W'), cut(integer)), Action::W),
+            map(preceded(char('L'), cut(integer)), Action::L),
+            map(preceded(char('R'), cut(integer)), Action::R),
+            map(preceded(char('F'), cut(integer)), Action::F),
+        )),
+    )(input)
+}
+
+pub fn parse_input(contents: &str) -> Result<Vec<Action>, ParseError> {
+    parse_all(contents, lines(action))
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Vec<Action>;
+
+    fn parse(input: &str) -> ParseResult<Vec<Action>> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(actions: &Vec<Action>) -> PartResult<impl Display> {
+        let mut actor = ShipActor::new();
+        for action in actions {
+            actor.apply_action(action);
+        }
+        Ok(actor.distance())
+    }
+
+    fn part2(actions: &Vec<Action>) -> PartResult<impl Display> {
+        let mut actor = WaypointActor::new();
+        for action in actions {
+            actor.apply_action(action);
+        }
+        Ok(actor.distance())
+    }
 }
 
 #[cfg(test)]
@@ -179,7 +223,7 @@ mod tests {
 
     #[test]
     fn part_1_example() {
-        let instructions = read_input("example1.txt");
+        let instructions = read_input("example1.txt").unwrap();
 
         let mut actor = ShipActor::new();
         for inst in &instructions {
@@ -190,7 +234,7 @@ mod tests {
 
     #[test]
     fn part_1_solution() {
-        let instructions = read_input("input.txt");
+        let instructions = read_input("input.txt").unwrap();
 
         let mut actor = ShipActor::new();
         for inst in &instructions {
@@ -201,7 +245,7 @@ mod tests {
 
     #[test]
     fn part_2_example_a() {
-        let instructions = read_input("example1.txt");
+        let instructions = read_input("example1.txt").unwrap();
 
         let mut actor = WaypointActor::new();
         for inst in &instructions {
@@ -212,7 +256,7 @@ mod tests {
     }
     #[test]
     fn part_2_example_b() {
-        let instructions = read_input("example1_alt.txt");
+        let instructions = read_input("example1_alt.txt").unwrap();
 
         let mut actor = WaypointActor::new();
         for inst in &instructions {
@@ -223,7 +267,7 @@ mod tests {
     }
     #[test]
     fn part_2_solution() {
-        let instructions = read_input("input.txt");
+        let instructions = read_input("input.txt").unwrap();
 
         let mut actor = WaypointActor::new();
         for inst in &instructions {
@@ -232,4 +276,20 @@ mod tests {
 
         assert_eq!(actor.distance(), 52069);
     }
+
+    #[test]
+    fn bad_action() {
+        let error = parse_input("F10\nN3\nF7\nR9O\nF11").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 4, column 3: expected end of line, found \"O\""
+        );
+        let error = parse_input("F10\nN\nF7").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        assert_eq!(error.expected, "integer");
+
+        let error = parse_input("F10\nU3").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.expected, "action");
+    }
 }
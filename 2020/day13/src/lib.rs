@@ -1,5 +1,23 @@
-use std::fs::File;
-use std::io::Read;
+use input::InputError;
+use parser::nom::{
+    branch::alt,
+    character::complete::{char, line_ending},
+    combinator::{cut, map, opt, value, verify},
+    error::context,
+    multi::separated_list1,
+    sequence::separated_pair,
+};
+use parser::{integer, parse_all, ParseError, Parsed};
+use solution::{ParseResult, PartResult, Solution};
+use std::fmt::Display;
+
+// The earliest time to leave, which the part 2 examples go without, and the bus
+// ids with None for an x
+#[derive(Debug, PartialEq)]
+pub struct Notes {
+    pub time: Option<i32>,
+    pub buses: Vec<Option<i32>>,
+}
 
 #[derive(Debug)]
 pub struct Bus {
@@ -36,16 +54,17 @@ pub struct Schedule {
 }
 
 impl Schedule {
-    pub fn new(input: &[String]) -> Self {
-        let time = input[0].parse().unwrap();
-
-        let busses: Vec<Bus> = input[1]
-            .split(",")
-            .filter_map(|x| x.parse::<i32>().ok())
-            .map(|x| Bus::new(x, time))
+    pub fn new(notes: &Notes) -> Option<Self> {
+        let time = notes.time?;
+
+        let busses: Vec<Bus> = notes
+            .buses
+            .iter()
+            .flatten()
+            .map(|&x| Bus::new(x, time))
             .collect();
 
-        Schedule { time, busses }
+        Some(Schedule { time, busses })
     }
 
     pub fn nearest_departure(&self) -> (i32, i32) {
@@ -64,13 +83,29 @@ impl Schedule {
     }
 }
 
-pub fn read_input(filename: &str) -> Vec<String> {
-    let mut contents = String::new();
-    let mut file = File::open(filename).unwrap();
-    file.read_to_string(&mut contents).unwrap();
+pub fn read_input(filename: &str) -> Result<Notes, InputError> {
+    input::parse!(filename, parse_input)
+}
+
+fn bus(input: &str) -> Parsed<'_, Option<i32>> {
+    let id = verify(integer, |&id: &i32| id > 0);
+    context("bus id or x", alt((map(id, Some), value(None, char('x')))))(input)
+}
+
+// "939\n7,13,x,x,59,x,31,19"
+fn notes(input: &str) -> Parsed<'_, Notes> {
+    map(
+        separated_pair(
+            opt(integer),
+            line_ending,
+            separated_list1(char(','), cut(bus)),
+        ),
+        |(time, buses)| Notes { time, buses },
+    )(input)
+}
 
-    let collection: Vec<String> = contents.split("\n").map(|x| x.to_string()).collect();
-    collection
+pub fn parse_input(contents: &str) -> Result<Notes, ParseError> {
+    parse_all(contents, notes)
 }
 
 //
@@ -88,12 +123,12 @@ pub struct CoordinatedSchedule {
 }
 
 impl CoordinatedSchedule {
-    pub fn new(input: &[String]) -> Self {
-        let routes: Vec<Route> = input[1]
-            .split(",")
-            .map(|x| {
-                let freq = if x == "x" { 1 } else { x.parse().unwrap() };
-                Route { freq }
+    pub fn new(notes: &Notes) -> Self {
+        let routes: Vec<Route> = notes
+            .buses
+            .iter()
+            .map(|bus| Route {
+                freq: bus.map_or(1, |id| id as usize),
             })
             .collect();
         CoordinatedSchedule { routes }
@@ -108,7 +143,7 @@ impl CoordinatedSchedule {
             let mut stride = 1;
 
             for (i, route) in self.routes.iter().enumerate() {
-                if (idx + i) % route.freq == 0 {
+                if (idx + i).is_multiple_of(route.freq) {
                     stride *= route.freq;
                 } else {
                     complete = false;
@@ -122,14 +157,35 @@ impl CoordinatedSchedule {
     }
 }
 
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Notes;
+
+    fn parse(input: &str) -> ParseResult<Notes> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(input: &Notes) -> PartResult<impl Display> {
+        let (bus, wait) = Schedule::new(input)
+            .ok_or("No time to leave after")?
+            .nearest_departure();
+        Ok(bus * wait)
+    }
+
+    fn part2(input: &Notes) -> PartResult<impl Display> {
+        Ok(CoordinatedSchedule::new(input).coordinate())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn part_1_example() {
-        let input = read_input("example1.txt");
-        let schedule = Schedule::new(&input);
+        let input = read_input("example1.txt").unwrap();
+        let schedule = Schedule::new(&input).unwrap();
 
         let r = schedule.nearest_departure();
 
@@ -138,9 +194,9 @@ mod tests {
 
     #[test]
     fn part_1_solution() {
-        let input = read_input("input.txt");
+        let input = read_input("input.txt").unwrap();
 
-        let schedule = Schedule::new(&input);
+        let schedule = Schedule::new(&input).unwrap();
 
         let r = schedule.nearest_departure();
 
@@ -149,26 +205,44 @@ mod tests {
 
     #[test]
     fn part_2_example() {
-        let mut schedule = CoordinatedSchedule::new(&read_input("example2a.txt"));
+        let mut schedule = CoordinatedSchedule::new(&read_input("example2a.txt").unwrap());
         assert_eq!(schedule.coordinate(), 3417);
 
-        let mut schedule = CoordinatedSchedule::new(&read_input("example2b.txt"));
+        let mut schedule = CoordinatedSchedule::new(&read_input("example2b.txt").unwrap());
         assert_eq!(schedule.coordinate(), 754018);
 
-        let mut schedule = CoordinatedSchedule::new(&read_input("example2c.txt"));
+        let mut schedule = CoordinatedSchedule::new(&read_input("example2c.txt").unwrap());
         assert_eq!(schedule.coordinate(), 779210);
 
-        let mut schedule = CoordinatedSchedule::new(&read_input("example2d.txt"));
+        let mut schedule = CoordinatedSchedule::new(&read_input("example2d.txt").unwrap());
         assert_eq!(schedule.coordinate(), 1261476);
 
-        let mut schedule = CoordinatedSchedule::new(&read_input("example2e.txt"));
+        let mut schedule = CoordinatedSchedule::new(&read_input("example2e.txt").unwrap());
         assert_eq!(schedule.coordinate(), 1202161486);
     }
 
     #[test]
     fn part_2_solution() {
-        let mut schedule = CoordinatedSchedule::new(&read_input("input.txt"));
+        let mut schedule = CoordinatedSchedule::new(&read_input("input.txt").unwrap());
 
         assert_eq!(schedule.coordinate(), 780601154795940);
     }
+
+    #[test]
+    fn bad_notes() {
+        assert_eq!(
+            parse_input("\n17,x,13,19").unwrap(),
+            Notes {
+                time: None,
+                buses: vec![Some(17), None, Some(13), Some(19)]
+            }
+        );
+
+        let error = parse_input("939\n7,13,x,y,59").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 8: expected bus id or x, found \"y,59\""
+        );
+        assert_eq!(parse_input("939\n7,0,x").unwrap_err().column, 3);
+    }
 }
@@ -1,7 +1,17 @@
+use input::InputError;
+use parser::nom::{
+    bytes::complete::tag,
+    character::complete::{line_ending, one_of},
+    combinator::{cut, map, recognize},
+    error::context,
+    multi::{count, many0, separated_list1},
+    sequence::{delimited, pair, preceded, separated_pair},
+};
+use parser::{integer, parse_all, ParseError, Parsed};
+use solution::{ParseResult, PartResult, Solution};
 use std::collections::HashMap;
 use std::convert::TryInto;
-use std::fs::File;
-use std::io::Read;
+use std::fmt::Display;
 
 #[derive(Debug)]
 pub struct OperationSet {
@@ -31,7 +41,7 @@ impl OperationSet {
 }
 
 // Evaluates the commands using the V1 protocol
-pub fn evaluate_v1(opset: Vec<OperationSet>) -> HashMap<usize, usize> {
+pub fn evaluate_v1(opset: &[OperationSet]) -> HashMap<usize, usize> {
     let mut data = HashMap::new();
     for (num, ops) in opset.iter().enumerate() {
         println!("Solving set: {}", num);
@@ -54,25 +64,20 @@ pub fn evaluate_v1(opset: Vec<OperationSet>) -> HashMap<usize, usize> {
 }
 
 // Evaluates the commands using the V2 protocol
-pub fn evaluate_v2(opset: Vec<OperationSet>) -> HashMap<usize, usize> {
+pub fn evaluate_v2(opset: &[OperationSet]) -> HashMap<usize, usize> {
     let mut data = HashMap::with_capacity(opset.len());
-    for (num, ops) in opset.iter().enumerate() {
+    for ops in opset.iter() {
         assert_eq!(ops.addrs.len(), ops.values.len());
-        println!("Solving set: {}", num);
 
         let on_mask = ops.get_mask('1');
         let floating_mask = ops.get_mask('X');
-        // let masks = get_floating_combinations(floating_mask);
         let masks = get_floating_combinations_v2(&ops.mask_string);
-        for i in 0..ops.addrs.len() {
-            let addr = ops.addrs[i] | on_mask;
-            let value = ops.values[i];
-
-            // for mask in &masks {
-            //     println!("using the floating_mask: {}", floating_mask);
-            //     let local_addr = addr + mask - floating_mask + 1;
-            //     data.insert(local_addr, value);
-            // }
+        for (addr, &value) in ops.addrs.iter().zip(ops.values.iter()) {
+            // floating bits take every combination, so clear them and add each in turn
+            let addr = (addr | on_mask) & !floating_mask;
+            for mask in &masks {
+                data.insert(addr | mask, value);
+            }
         }
     }
     data
@@ -93,60 +98,53 @@ pub fn get_floating_combinations_v2(input: &str) -> Vec<usize> {
     masks
 }
 
-pub fn read_input(filename: &str) -> Vec<String> {
-    let mut contents = String::new();
-    let mut file = File::open(filename).unwrap();
-    file.read_to_string(&mut contents).unwrap();
+pub fn read_input(filename: &str) -> Result<Vec<OperationSet>, InputError> {
+    input::parse!(filename, parse_input)
+}
 
-    contents
-        .split("\n")
-        .map(|x| x.to_string())
-        .collect::<Vec<String>>()
+// "mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X"
+fn mask(input: &str) -> Parsed<'_, String> {
+    let bits = context("36 bit mask", recognize(count(one_of("01X"), 36)));
+    map(preceded(tag("mask = "), cut(bits)), String::from)(input)
 }
 
-// VEERRY ugly parser!
-pub fn input_to_opset(input: &Vec<String>) -> Vec<OperationSet> {
-    let mut collection = Vec::new();
-
-    let mut tmp_mask = String::new();
-    let mut tmp_addrs = Vec::new();
-    let mut tmp_vars = Vec::new();
-
-    let mut first_run = true;
-    for line in input {
-        let line = line.replace("mask = ", "");
-        let line = line.replace("mem[", "");
-        let line = line.replace("] =", "");
-
-        if line.len() == 36 {
-            // Handle a new mask
-            if !first_run {
-                collection.push(OperationSet::new(
-                    tmp_mask.to_string(),
-                    tmp_addrs.clone(),
-                    tmp_vars.clone(),
-                ));
-            }
-            tmp_mask = line;
-            tmp_vars = Vec::new();
-            tmp_addrs = Vec::new();
-
-            first_run = false;
-        } else {
-            // And subsequent operations with that mask
-            let vars = line.split(" ").collect::<Vec<&str>>();
-            tmp_vars.push(vars[1].parse().unwrap());
-            tmp_addrs.push(vars[0].parse().unwrap());
-        }
+// "mem[8] = 11"
+fn write(input: &str) -> Parsed<'_, (usize, usize)> {
+    let addr = delimited(tag("mem["), cut(integer), cut(tag("]")));
+    separated_pair(addr, cut(tag(" = ")), cut(integer))(input)
+}
+
+// A mask and the writes made under it
+fn operation_set(input: &str) -> Parsed<'_, OperationSet> {
+    map(
+        pair(mask, many0(preceded(line_ending, write))),
+        |(mask_string, writes)| {
+            let (addrs, values) = writes.into_iter().unzip();
+            OperationSet::new(mask_string, addrs, values)
+        },
+    )(input)
+}
+
+pub fn parse_input(contents: &str) -> Result<Vec<OperationSet>, ParseError> {
+    parse_all(contents, separated_list1(line_ending, operation_set))
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = Vec<OperationSet>;
+
+    fn parse(input: &str) -> ParseResult<Vec<OperationSet>> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(input: &Vec<OperationSet>) -> PartResult<impl Display> {
+        Ok(evaluate_v1(input).values().sum::<usize>())
+    }
+
+    fn part2(input: &Vec<OperationSet>) -> PartResult<impl Display> {
+        Ok(evaluate_v2(input).values().sum::<usize>())
     }
-    //Special handling for final element
-    collection.push(OperationSet::new(
-        tmp_mask.to_string(),
-        tmp_addrs.clone(),
-        tmp_vars.clone(),
-    ));
-
-    collection
 }
 
 #[cfg(test)]
@@ -155,10 +153,9 @@ mod tests {
 
     #[test]
     fn part_1_example() {
-        let input = read_input("example1.txt");
+        let opset = read_input("example1.txt").unwrap();
 
-        let opset = input_to_opset(&input);
-        let data = evaluate_v1(opset);
+        let data = evaluate_v1(&opset);
 
         let ans: usize = data.iter().fold(0, |acc, (_k, v)| acc + v);
         assert_eq!(ans, 165)
@@ -166,10 +163,9 @@ mod tests {
 
     #[test]
     fn part_1_solution() {
-        let input = read_input("input.txt");
+        let opset = read_input("input.txt").unwrap();
 
-        let opset = input_to_opset(&input);
-        let data = evaluate_v1(opset);
+        let data = evaluate_v1(&opset);
 
         let ans: usize = data.iter().fold(0, |acc, (_k, v)| acc + v);
         assert_eq!(ans, 15172047086292)
@@ -177,10 +173,9 @@ mod tests {
 
     #[test]
     fn part_2_example() {
-        let input = read_input("example2.txt");
+        let opset = read_input("example2.txt").unwrap();
 
-        let opset = input_to_opset(&input);
-        let data = evaluate_v2(opset);
+        let data = evaluate_v2(&opset);
         dbg!(&data);
         let ans: usize = data.iter().fold(0, |acc, (_k, v)| acc + v);
         assert_eq!(ans, 208)
@@ -188,10 +183,9 @@ mod tests {
 
     #[test]
     fn part_2_solution() {
-        let input = read_input("input.txt");
+        let opset = read_input("input.txt").unwrap();
 
-        let opset = input_to_opset(&input);
-        let data = evaluate_v2(opset);
+        let data = evaluate_v2(&opset);
 
         let ans: usize = data.iter().fold(0, |acc, (_k, v)| acc + v);
         assert_eq!(ans, 4197941339968)
@@ -210,4 +204,18 @@ mod tests {
             println!("{}: ({:b})", v, v);
         }
     }
+
+    #[test]
+    fn bad_program() {
+        let error = parse_input("mask = XXXX1XXXX0X\nmem[8] = 11").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 8));
+        assert_eq!(error.expected, "36 bit mask");
+
+        let mask = "X".repeat(36);
+        let error = parse_input(&format!("mask = {}\nmem[8] = eleven", mask)).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 10));
+
+        let error = parse_input(&format!("mem[8] = 11\nmask = {}", mask)).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 1));
+    }
 }
@@ -1,17 +1,18 @@
+use input::InputError;
+use parser::{integers, parse_all, ParseError};
+use solution::{ParseResult, PartResult, Solution};
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::Read;
+use std::fmt::Display;
 
-pub fn read_input(filename: &str) -> Vec<usize> {
-    let mut contents = String::new();
-    let mut file = File::open(filename).unwrap();
-    file.read_to_string(&mut contents).unwrap();
+pub fn read_input(filename: &str) -> Result<Vec<usize>, InputError> {
+    input::parse!(filename, parse_input)
+}
 
-    let collection: Vec<usize> = contents.split(",").map(|x| x.parse().unwrap()).collect();
-    collection
+pub fn parse_input(contents: &str) -> Result<Vec<usize>, ParseError> {
+    parse_all(contents, integers(","))
 }
 
-pub fn run(input_1: &Vec<usize>, num_turns: usize) -> usize {
+pub fn run(input_1: &[usize], num_turns: usize) -> usize {
     // This will be a map of <number_spoken, turn_spoken>
     let mut memory = HashMap::new();
     let mut prev = 0;
@@ -44,6 +45,24 @@ pub fn run(input_1: &Vec<usize>, num_turns: usize) -> usize {
     prev
 }
 
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input = Vec<usize>;
+
+    fn parse(input: &str) -> ParseResult<Vec<usize>> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(input: &Vec<usize>) -> PartResult<impl Display> {
+        Ok(run(input, 2020))
+    }
+
+    fn part2(input: &Vec<usize>) -> PartResult<impl Display> {
+        Ok(run(input, 30000000))
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -51,7 +70,7 @@ mod tests {
 
     #[test]
     fn part_1_example() {
-        let input_1 = read_input("example1.txt");
+        let input_1 = read_input("example1.txt").unwrap();
         let input_2 = vec![1, 3, 2];
         let input_3 = vec![2, 1, 3];
         let input_4 = vec![1, 2, 3];
@@ -78,14 +97,20 @@ mod tests {
 
     #[test]
     fn part_1_solution() {
-        let input_1 = read_input("input.txt");
+        let input_1 = read_input("input.txt").unwrap();
 
         assert_eq!(run(&input_1, 2020), 517);
     }
 
     #[test]
     fn part_2_solution() {
-        let input_1 = read_input("input.txt");
-        assert_eq!(run(&input_1, 30000000), 517);
+        let input_1 = read_input("input.txt").unwrap();
+        assert_eq!(run(&input_1, 30000000), 1047739);
+    }
+
+    #[test]
+    fn bad_numbers() {
+        let error = parse_input("0,3,-6").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 4));
     }
 }
@@ -1,19 +1,22 @@
-use rand::Rng;
-use std::collections::HashMap;
+use input::InputError;
+use parser::nom::{
+    bytes::complete::tag,
+    character::complete::{char, line_ending},
+    combinator::map,
+    multi::separated_list1,
+    sequence::{pair, preceded, separated_pair, tuple},
+};
+use parser::{blank_lines, integer, integers, key_value, lines, parse_all, ParseError, Parsed};
+use solution::{ParseResult, PartResult, Solution};
 use std::collections::HashSet;
-use std::fs::File;
-use std::io::Read;
-
-pub fn read_input(filename: &str) -> Vec<Vec<i32>> {
-    let mut contents = String::new();
-    let mut file = File::open(filename).unwrap();
-    file.read_to_string(&mut contents).unwrap();
-
-    let collection: Vec<Vec<i32>> = contents
-        .split("\n")
-        .map(|x| x.split(",").map(|x| x.parse().unwrap()).collect())
-        .collect();
-    collection
+use std::fmt::Display;
+
+pub fn read_input(filename: &str) -> Result<Vec<Vec<i32>>, InputError> {
+    input::parse!(filename, parse_tickets)
+}
+
+pub fn parse_tickets(contents: &str) -> Result<Vec<Vec<i32>>, ParseError> {
+    parse_all(contents, lines(integers(",")))
 }
 
 #[derive(Debug)]
@@ -50,7 +53,7 @@ pub fn check_constraints(tickets: &Vec<Vec<i32>>, rules: &Vec<Vec<Constraint>>)
 }
 
 // could be replaced by single filter
-pub fn get_valid_tickets(tickets: &Vec<Vec<i32>>, blacklist: &Vec<i32>) -> Vec<Vec<i32>> {
+pub fn get_valid_tickets(tickets: &[Vec<i32>], blacklist: &[i32]) -> Vec<Vec<i32>> {
     let mut valid_tickets = Vec::new();
     'outer: for ticket in tickets {
         for entry in ticket {
@@ -65,11 +68,11 @@ pub fn get_valid_tickets(tickets: &Vec<Vec<i32>>, blacklist: &Vec<i32>) -> Vec<V
 }
 
 pub fn get_constraint_columns(
-    tickets: &Vec<Vec<i32>>,
-    rules: &Vec<Vec<Constraint>>,
-    blacklist: &Vec<i32>,
+    tickets: &[Vec<i32>],
+    rules: &[Vec<Constraint>],
+    blacklist: &[i32],
 ) -> Vec<HashSet<usize>> {
-    let valid_tickets = get_valid_tickets(&tickets, &blacklist);
+    let valid_tickets = get_valid_tickets(tickets, blacklist);
     let all_constraints: HashSet<usize> = (0..rules.len()).collect();
     let mut valid_constraints = Vec::new();
 
@@ -88,78 +91,99 @@ pub fn get_constraint_columns(
                     //     rule_id, ticket_col
                     // );
                     invalid_rules.push(rule_id);
-
-                    break;
-                    // break 'outer;
                 }
             }
         }
-        let x: HashSet<usize> = invalid_rules.iter().map(|x| *x).collect();
-        valid_constraints.push(all_constraints.difference(&x).map(|x| *x).collect());
+        let x: HashSet<usize> = invalid_rules.iter().copied().collect();
+        valid_constraints.push(all_constraints.difference(&x).copied().collect());
     }
 
     valid_constraints
 }
 
-// Eeeew, you know you're off to a ropey solution when you start solving using Rng...
-// But it works
-pub fn solve(constraints: Vec<HashSet<usize>>) {
-    // reduce it as much as is statically determined
-    let (mut solved, constraints) = reduce(constraints);
-
-    // begin guessing
-    while !solved {
-        let mut rng = rand::thread_rng();
-
-        let mut new_guess = Vec::new();
-
-        // pick a random index from the possibly valid solutions and check for success
-        for i in 0..constraints.len() {
-            let constraint = constraints[i].clone();
-            if constraint.len() > 1 {
-                let guessed_idx = rng.gen_range(0, constraints[i].len());
-                let val = constraints[i].iter().nth(guessed_idx).unwrap();
-                new_guess.push([val.clone()].iter().map(|x| *x).collect());
-            } else {
-                new_guess.push(constraint);
-            }
-        }
-        let (success, ans) = reduce(new_guess);
-        solved = success;
-        if success {
-            dbg!(ans);
+// Narrow each column down to one rule, always fixing a column with a single
+// candidate left
+pub fn assign_fields(mut constraints: Vec<HashSet<usize>>) -> Option<Vec<usize>> {
+    let mut assigned = vec![None; constraints.len()];
+    while let Some(col) = constraints.iter().position(|c| c.len() == 1) {
+        let rule = *constraints[col].iter().next().unwrap();
+        assigned[col] = Some(rule);
+        for other in constraints.iter_mut() {
+            other.remove(&rule);
         }
     }
-    println!("solved!");
+    assigned.into_iter().collect()
 }
 
-pub fn reduce(mut constraints: Vec<HashSet<usize>>) -> (bool, Vec<HashSet<usize>>) {
-    let mut solved = false;
-    let mut counter = 0;
-    while !solved && counter < 100 {
-        solved = true;
-
-        for i in 0..constraints.len() {
-            if constraints[i].len() == 1 {
-                let value = constraints[i].iter().next().unwrap().clone();
-                // solved constraint, remove as possible solution for other constraints
-                for j in 0..constraints.len() {
-                    if j == i {
-                        continue;
-                    }
-                    constraints[j] = constraints[j]
-                        .iter()
-                        .cloned()
-                        .filter(|&x| x != value)
-                        .collect();
-                }
-            } else {
-                solved = false
-            }
-            counter += 1;
-        }
+#[derive(Debug)]
+pub struct Notes {
+    pub names: Vec<String>,
+    pub rules: Vec<Vec<Constraint>>,
+    pub ticket: Vec<i32>,
+    pub nearby: Vec<Vec<i32>>,
+}
+
+// "departure location: 34-724 or 735-974"
+fn rule(input: &str) -> Parsed<'_, (&str, Vec<Constraint>)> {
+    let range = map(separated_pair(integer, char('-'), integer), |(min, max)| {
+        Constraint::new(min, max)
+    });
+    key_value(separated_list1(tag(" or "), range))(input)
+}
+
+// A header on its own line with tickets under it
+fn tickets<'a>(header: &'static str) -> impl FnMut(&'a str) -> Parsed<'a, Vec<Vec<i32>>> {
+    preceded(pair(tag(header), line_ending), lines(integers(",")))
+}
+
+pub fn parse_notes(input: &str) -> Result<Notes, ParseError> {
+    let sections = tuple((
+        lines(rule),
+        blank_lines,
+        tickets("your ticket:"),
+        blank_lines,
+        tickets("nearby tickets:"),
+    ));
+    let (rules, _, mut ticket, _, nearby) = parse_all(input, sections)?;
+    let (names, rules) = rules
+        .into_iter()
+        .map(|(name, rule)| (name.to_string(), rule))
+        .unzip();
+
+    Ok(Notes {
+        names,
+        rules,
+        ticket: ticket.remove(0),
+        nearby,
+    })
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input = Notes;
+
+    fn parse(input: &str) -> ParseResult<Notes> {
+        Ok(parse_notes(input)?)
+    }
+
+    fn part1(notes: &Notes) -> PartResult<impl Display> {
+        Ok(check_constraints(&notes.nearby, &notes.rules)
+            .iter()
+            .sum::<i32>())
+    }
+
+    fn part2(notes: &Notes) -> PartResult<impl Display> {
+        let bads = check_constraints(&notes.nearby, &notes.rules);
+        let columns = get_constraint_columns(&notes.nearby, &notes.rules, &bads);
+        let fields = assign_fields(columns).expect("Fields can't be told apart");
+        Ok(fields
+            .iter()
+            .zip(&notes.ticket)
+            .filter(|(&rule, _)| notes.names[rule].starts_with("departure"))
+            .map(|(_, &value)| value as usize)
+            .product::<usize>())
     }
-    (solved, constraints)
 }
 
 #[cfg(test)]
@@ -184,6 +208,21 @@ mod tests {
         assert_eq!(sum.iter().sum::<i32>(), 71);
     }
 
+    #[test]
+    fn notes() {
+        let notes = input::parse!("example1.txt", parse_notes).unwrap();
+        assert_eq!(notes.names, ["class", "row", "seat"]);
+        assert_eq!(notes.rules[1][1].max, 44);
+        assert_eq!(notes.ticket, [7, 1, 14]);
+        assert_eq!(notes.nearby.len(), 4);
+        assert_eq!(notes.nearby[3], [38, 6, 12]);
+
+        let text = input::read!("example1.txt").unwrap();
+        let error = parse_notes(&text.replace("40,4,50", "40,4,,50")).unwrap_err();
+        assert_eq!((error.line, error.column), (10, 5));
+        assert_eq!(error.expected, "end of line");
+    }
+
     #[test]
     fn part_1_solution() {
         let constraints = vec![
@@ -208,7 +247,7 @@ mod tests {
             vec![Constraint::new(37, 797), Constraint::new(810, 973)], // wagon:
             vec![Constraint::new(35, 766), Constraint::new(784, 952)], // zone:
         ];
-        let nearby_tickets = read_input("input_nearby_tickets.txt");
+        let nearby_tickets = read_input("input_nearby_tickets.txt").unwrap();
 
         let sum = check_constraints(&nearby_tickets, &constraints);
         assert_eq!(sum.iter().sum::<i32>(), 24021);
@@ -234,7 +273,7 @@ mod tests {
         assert_eq!(x, &[&[7, 3, 47]]);
 
         let constraints = get_constraint_columns(&nearby_tickets, &constraints, &bads);
-        solve(constraints);
+        assert_eq!(assign_fields(constraints), Some(vec![1, 0, 2]));
     }
 
     #[test]
@@ -252,7 +291,7 @@ mod tests {
         assert_eq!(x, [[3, 9, 18], [15, 1, 5], [5, 14, 9]]);
 
         let constraints = get_constraint_columns(&nearby_tickets, &constraints, &bads);
-        solve(constraints);
+        assert_eq!(assign_fields(constraints), Some(vec![1, 0, 2]));
     }
 
     #[test]
@@ -279,22 +318,23 @@ mod tests {
             vec![Constraint::new(37, 797), Constraint::new(810, 973)], // wagon:
             vec![Constraint::new(35, 766), Constraint::new(784, 952)], // zone:
         ];
-        let nearby_tickets = read_input("input_nearby_tickets.txt");
+        let nearby_tickets = read_input("input_nearby_tickets.txt").unwrap();
 
         let bads = check_constraints(&nearby_tickets, &constraints);
         let constraints = get_constraint_columns(&nearby_tickets, &constraints, &bads);
-        solve(constraints);
+        let fields = assign_fields(constraints).unwrap();
 
         let tickets: Vec<usize> = vec![
             113, 53, 97, 59, 139, 73, 89, 109, 67, 71, 79, 127, 149, 107, 137, 83, 131, 101, 61,
             103,
         ];
-        let ans = tickets[20 - 1]
-            * tickets[17 - 1]
-            * tickets[11 - 1]
-            * tickets[14 - 1]
-            * tickets[12 - 1]
-            * tickets[7 - 1];
+        // the first six rules are the departure fields
+        let ans: usize = fields
+            .iter()
+            .zip(&tickets)
+            .filter(|(&rule, _)| rule < 6)
+            .map(|(_, &value)| value)
+            .product();
         assert_eq!(ans, 1289178686687);
     }
 }
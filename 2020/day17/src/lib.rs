@@ -1,6 +1,8 @@
+use grid::GridError;
+use input::InputError;
+use solution::{ParseResult, PartResult, Solution};
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::Read;
+use std::fmt::Display;
 
 type Grid = HashMap<(i32, i32, i32, i32), Cell>;
 #[derive(Debug, PartialEq)]
@@ -9,13 +11,19 @@ pub enum Cell {
     Active,
 }
 
-pub fn read_input(filename: &str) -> Vec<String> {
-    let mut contents = String::new();
-    let mut file = File::open(filename).unwrap();
-    file.read_to_string(&mut contents).unwrap();
+// The starting slice, true where a cube is active
+pub type Slice = grid::Grid<bool>;
 
-    let collection: Vec<String> = contents.split("\n").map(|x| x.to_string()).collect();
-    collection
+pub fn read_input(filename: &str) -> Result<Slice, InputError> {
+    input::parse!(filename, parse_input)
+}
+
+pub fn parse_input(contents: &str) -> Result<Slice, GridError> {
+    Slice::try_parse(contents, |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })
 }
 
 // Because this algorithm never expands its search space, we can use the same
@@ -27,7 +35,7 @@ fn empty_grid_3d() -> Grid {
     for x in -sz..sz {
         for y in -sz..sz {
             for z in -sz..sz {
-                grid.insert((x as i32, y as i32, z as i32, 0), Cell::Inactive);
+                grid.insert((x, y, z, 0), Cell::Inactive);
             }
         }
     }
@@ -41,7 +49,7 @@ fn empty_grid_4d() -> Grid {
         for y in -sz..sz {
             for z in -sz..sz {
                 for w in -sz..sz {
-                    grid.insert((x as i32, y as i32, z as i32, w), Cell::Inactive);
+                    grid.insert((x, y, z, w), Cell::Inactive);
                 }
             }
         }
@@ -49,39 +57,27 @@ fn empty_grid_4d() -> Grid {
     grid
 }
 
-pub fn input_to_grid_3d(input: &Vec<String>) -> Grid {
+pub fn input_to_grid_3d(input: &Slice) -> Grid {
     let mut grid = empty_grid_3d();
 
     let z = 0;
     let w = 0;
-    for (y, line) in input.iter().enumerate() {
-        for (x, c) in line.chars().enumerate() {
-            let cell = if c == '#' {
-                Cell::Active
-            } else {
-                Cell::Inactive
-            };
-            grid.insert((x as i32, y as i32, z, w), cell);
-        }
+    for ((x, y), &active) in input.iter() {
+        let cell = if active { Cell::Active } else { Cell::Inactive };
+        grid.insert((x as i32, y as i32, z, w), cell);
     }
 
     grid
 }
 
-pub fn input_to_grid_4d(input: &Vec<String>) -> Grid {
+pub fn input_to_grid_4d(input: &Slice) -> Grid {
     let mut grid = empty_grid_4d();
 
     let z = 0;
     let w = 0;
-    for (y, line) in input.iter().enumerate() {
-        for (x, c) in line.chars().enumerate() {
-            let cell = if c == '#' {
-                Cell::Active
-            } else {
-                Cell::Inactive
-            };
-            grid.insert((x as i32, y as i32, z, w), cell);
-        }
+    for ((x, y), &active) in input.iter() {
+        let cell = if active { Cell::Active } else { Cell::Inactive };
+        grid.insert((x as i32, y as i32, z, w), cell);
     }
 
     grid
@@ -121,7 +117,7 @@ pub fn count_active_neighbors(grid: &Grid, location: &(i32, i32, i32, i32)) -> i
                     if (dx, dy, dz, dw) == (0, 0, 0, 0) {
                         continue;
                     }
-                    count += is_active(&grid, &(x + dx, y + dy, z + dz, w + dw));
+                    count += is_active(grid, &(x + dx, y + dy, z + dz, w + dw));
                 }
             }
         }
@@ -139,10 +135,8 @@ pub fn cycle(grid: &Grid) -> Grid {
 
     // Evaluate
     for ((x, y, z, w), cell) in grid {
-        let neighbors = count_active_neighbors(&grid, &(*x, *y, *z, *w));
-        let new_cell = if cell == &Cell::Active && (neighbors == 2 || neighbors == 3) {
-            Cell::Active
-        } else if cell == &Cell::Inactive && neighbors == 3 {
+        let neighbors = count_active_neighbors(grid, &(*x, *y, *z, *w));
+        let new_cell = if (cell == &Cell::Active && neighbors == 2) || neighbors == 3 {
             Cell::Active
         } else {
             Cell::Inactive
@@ -154,12 +148,32 @@ pub fn cycle(grid: &Grid) -> Grid {
     new_grid
 }
 
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input = Slice;
+
+    fn parse(input: &str) -> ParseResult<Slice> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(input: &Slice) -> PartResult<impl Display> {
+        let grid = (0..6).fold(input_to_grid_3d(input), |grid, _| cycle(&grid));
+        Ok(count_active(&grid))
+    }
+
+    fn part2(input: &Slice) -> PartResult<impl Display> {
+        let grid = (0..6).fold(input_to_grid_4d(input), |grid, _| cycle(&grid));
+        Ok(count_active(&grid))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     #[test]
     fn part_1_example() {
-        let input = read_input("example1.txt");
+        let input = read_input("example1.txt").unwrap();
         let grid = input_to_grid_3d(&input);
         assert_eq!(count_active(&grid), 5); //0-cycles
         assert_eq!(count_active_neighbors(&grid, &(1, 1, 0, 0)), 5);
@@ -184,7 +198,7 @@ mod tests {
 
     #[test]
     fn part_1_solution() {
-        let input = read_input("input.txt");
+        let input = read_input("input.txt").unwrap();
         let grid = input_to_grid_3d(&input);
 
         let grid = cycle(&grid); //1
@@ -199,7 +213,7 @@ mod tests {
 
     #[test]
     fn part_2_example() {
-        let input = read_input("example1.txt");
+        let input = read_input("example1.txt").unwrap();
         let grid = input_to_grid_4d(&input);
 
         let grid = cycle(&grid);
@@ -216,7 +230,7 @@ mod tests {
 
     #[test]
     fn part_2_solution() {
-        let input = read_input("input.txt");
+        let input = read_input("input.txt").unwrap();
         let grid = input_to_grid_4d(&input);
 
         let grid = cycle(&grid);
@@ -227,4 +241,16 @@ mod tests {
         let grid = cycle(&grid);
         assert_eq!(count_active(&grid), 1892); //6-cycles
     }
+
+    #[test]
+    fn bad_slice() {
+        assert_eq!(
+            parse_input(".#.\n..#\n#*#").unwrap_err(),
+            GridError::BadCell {
+                line: 3,
+                column: 2,
+                found: '*'
+            }
+        );
+    }
 }
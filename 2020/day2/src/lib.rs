@@ -1,5 +1,10 @@
-extern crate regex;
-use regex::Regex;
+use parser::nom::{
+    character::complete::{alphanumeric1, char, satisfy},
+    sequence::tuple,
+};
+use parser::{integer, lines, parse_all, ParseError, Parsed};
+use solution::{ParseResult, PartResult, Solution};
+use std::fmt::Display;
 
 #[derive(Debug, PartialEq)]
 pub struct Config {
@@ -29,29 +34,58 @@ impl Config {
     }
 }
 
-pub fn deserialize(input: &str) -> Config {
-    // let re = Regex::new(r"^\d{4}-\d{2}-\d{2}$").unwrap();
-    let re = Regex::new(r"^(\d+)-(\d+) (\w): (\w+)$").unwrap();
-    let caps = re.captures(input).unwrap();
+// "1-3 a: abcde"
+fn config(input: &str) -> Parsed<'_, Config> {
+    let (input, (min, _, max, _, val, _, _, password)) = tuple((
+        integer,
+        char('-'),
+        integer,
+        char(' '),
+        satisfy(char::is_alphanumeric),
+        char(':'),
+        char(' '),
+        alphanumeric1,
+    ))(input)?;
+
+    let password = password.to_string();
+    Ok((
+        input,
+        Config {
+            min,
+            max,
+            val,
+            password,
+        },
+    ))
+}
+
+pub fn deserialize(input: &str) -> Result<Config, ParseError> {
+    parse_all(input, config)
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<Config>;
+
+    fn parse(input: &str) -> ParseResult<Vec<Config>> {
+        Ok(parse_all(input, lines(config))?)
+    }
+
+    fn part1(configs: &Vec<Config>) -> PartResult<impl Display> {
+        Ok(configs.iter().filter(|c| c.validate()).count())
+    }
 
-    Config {
-        min: caps[1].parse().unwrap(),
-        max: caps[2].parse().unwrap(),
-        val: caps[3].parse().unwrap(),
-        password: caps[4].to_string(),
+    fn part2(configs: &Vec<Config>) -> PartResult<impl Display> {
+        Ok(configs.iter().filter(|c| c.validate2()).count())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::fs::File;
-    use std::io::Read;
     pub fn parse_input() -> Vec<String> {
-        let mut contents = String::new();
-        let mut file = File::open("input.txt").unwrap();
-        file.read_to_string(&mut contents).unwrap();
-
+        let contents = input::read!("input.txt").unwrap();
         let collection: Vec<String> = contents.split('\n').map(|x| x.to_string()).collect();
         collection
     }
@@ -60,13 +94,17 @@ mod tests {
         let input = "1-3 a: abcde";
         assert_eq!(
             deserialize(input),
-            Config {
+            Ok(Config {
                 min: 1,
                 max: 3,
                 val: 'a',
                 password: "abcde".to_string(),
-            }
+            })
         );
+
+        let error = deserialize("1-3 a abcde").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 6));
+        assert_eq!(error.expected, "':'");
     }
 
     #[test]
@@ -74,7 +112,7 @@ mod tests {
         let input = vec!["1-3 a: abcde", "1-3 b: cdefg", "2-9 c: ccccccccc"];
 
         for item in input {
-            if deserialize(item).validate() {
+            if deserialize(item).unwrap().validate() {
                 println!("pass");
             } else {
                 println!("fail");
@@ -86,9 +124,13 @@ mod tests {
     fn part_1_solution() {
         let input = parse_input();
 
-        let pass = input
-            .iter()
-            .fold(0, |x, y| if deserialize(y).validate() { x + 1 } else { x });
+        let pass = input.iter().fold(0, |x, y| {
+            if deserialize(y).unwrap().validate() {
+                x + 1
+            } else {
+                x
+            }
+        });
 
         println!("Pass = {}", pass);
         assert_eq!(640, pass);
@@ -99,7 +141,7 @@ mod tests {
         let input = vec!["1-3 a: abcde", "1-3 b: cdefg", "2-9 c: ccccccccc"];
 
         for item in input {
-            if deserialize(item).validate2() {
+            if deserialize(item).unwrap().validate2() {
                 println!("pass");
             } else {
                 println!("fail");
@@ -111,9 +153,13 @@ mod tests {
     fn part_2_solution() {
         let input = parse_input();
 
-        let pass = input
-            .iter()
-            .fold(0, |x, y| if deserialize(y).validate2() { x + 1 } else { x });
+        let pass = input.iter().fold(0, |x, y| {
+            if deserialize(y).unwrap().validate2() {
+                x + 1
+            } else {
+                x
+            }
+        });
 
         println!("Pass = {}", pass);
         assert_eq!(472, pass);
@@ -1,35 +1,49 @@
-use std::fs::File;
-use std::io::Read;
+use grid::{Grid, GridError};
+use input::InputError;
+use solution::{ParseResult, PartResult, Solution};
+use std::fmt::Display;
+
+// true where there's a tree
+pub fn parse_input(filename: &str) -> Result<Grid<bool>, InputError> {
+    input::parse!(filename, parse_trees)
+}
 
-pub fn parse_input(filename: &str) -> Vec<String> {
-    let mut contents = String::new();
-    let mut file = File::open(filename).unwrap();
-    file.read_to_string(&mut contents).unwrap();
+pub fn parse_trees(contents: &str) -> Result<Grid<bool>, GridError> {
+    Grid::try_parse(contents, |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })
+}
 
-    let collection: Vec<String> = contents.split('\n').map(|x| x.to_string()).collect();
-    collection
+// The slope repeats to the right forever
+pub fn count_hits(trees: &Grid<bool>, dx: isize, dy: isize) -> usize {
+    (1..)
+        .map(|step| (dx * step, dy * step))
+        .take_while(|&(_, y)| y < trees.height() as isize)
+        .filter(|&(x, y)| *trees.wrapping(x, y))
+        .count()
 }
 
-pub fn count_hits(rows: &Vec<String>, dx: i32, dy: i32) -> usize {
-    let mut x_pos = dx as usize;
-    let mut y_pos = dy as usize;
-    let mut hits = 0;
-
-    while y_pos < rows.len() {
-        let row = &rows[y_pos];
-        if x_pos > row.len() - 1 {
-            x_pos -= row.len()
-        }
-
-        if row.chars().nth(x_pos).unwrap() == '#' {
-            hits += 1;
-        // println!("hits: {}", hits);
-        } else {
-        }
-        y_pos += dy as usize;
-        x_pos += dx as usize;
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Grid<bool>;
+
+    fn parse(input: &str) -> ParseResult<Grid<bool>> {
+        Ok(parse_trees(input)?)
+    }
+
+    fn part1(rows: &Grid<bool>) -> PartResult<impl Display> {
+        Ok(count_hits(rows, 3, 1))
+    }
+
+    fn part2(rows: &Grid<bool>) -> PartResult<impl Display> {
+        Ok([(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)]
+            .iter()
+            .map(|&(dx, dy)| count_hits(rows, dx, dy))
+            .product::<usize>())
     }
-    hits
 }
 
 #[cfg(test)]
@@ -38,25 +52,21 @@ mod tests {
     use super::*;
     #[test]
     fn part_1_example() {
-        let rows = parse_input("example1.txt");
-
-        for r in &rows {
-            println!("{}", r);
-        }
+        let rows = parse_input("example1.txt").unwrap();
 
         assert_eq!(count_hits(&rows, 3, 1), 7);
     }
 
     #[test]
     fn part_1_solution() {
-        let rows = parse_input("input.txt");
+        let rows = parse_input("input.txt").unwrap();
 
         assert_eq!(count_hits(&rows, 3, 1), 244);
     }
 
     #[test]
     fn part_2_example() {
-        let rows = parse_input("example1.txt");
+        let rows = parse_input("example1.txt").unwrap();
 
         assert_eq!(count_hits(&rows, 1, 1), 2);
         assert_eq!(count_hits(&rows, 3, 1), 7);
@@ -67,7 +77,7 @@ mod tests {
 
     #[test]
     fn part_2_solution() {
-        let rows = parse_input("input.txt");
+        let rows = parse_input("input.txt").unwrap();
 
         let ans = count_hits(&rows, 1, 1)
             * count_hits(&rows, 3, 1)
@@ -77,4 +87,20 @@ mod tests {
 
         assert_eq!(ans, 9406609920);
     }
+
+    #[test]
+    fn bad_square() {
+        assert_eq!(
+            parse_trees("..##.\n#...#\n.#..\n").unwrap_err(),
+            GridError::Ragged {
+                line: 3,
+                width: 4,
+                expected: 5
+            }
+        );
+        assert_eq!(
+            parse_trees("..##.\n#.O.#\n").unwrap_err().to_string(),
+            "line 2, column 3: 'O' isn't a grid cell"
+        );
+    }
 }
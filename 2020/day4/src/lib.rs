@@ -1,6 +1,9 @@
+use input::InputError;
+use parser::nom::combinator::map;
+use parser::{blocks, parse_all, record, word, ParseError};
+use solution::{ParseResult, PartResult, Solution};
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::Read;
+use std::fmt::Display;
 
 extern crate regex;
 use regex::Regex;
@@ -8,29 +11,19 @@ use regex::Regex;
 // pub struct Passport {}
 type Passport = HashMap<String, String>;
 
-pub fn read_input(filename: &str) -> Vec<String> {
-    let mut contents = String::new();
-    let mut file = File::open(filename).unwrap();
-    file.read_to_string(&mut contents).unwrap();
-
-    let collection: Vec<String> = contents.split("\n\n").map(|x| x.to_string()).collect();
-    collection
+pub fn read_input(filename: &str) -> Result<Vec<Passport>, InputError> {
+    input::parse!(filename, parse_input)
 }
 
-pub fn parse_input(input: Vec<String>) -> Vec<Passport> {
-    let re = Regex::new(r"(\w+):(\S+)").unwrap();
-
-    let mut output = Vec::new();
-
-    for line in &input {
-        let mut passport = HashMap::new();
-        for cap in re.captures_iter(&line) {
-            passport.insert(cap[1].to_string(), cap[2].to_string());
-        }
-        output.push(passport);
-    }
-
-    output
+// Passports are "key:value" fields with a blank line between each one
+pub fn parse_input(input: &str) -> Result<Vec<Passport>, ParseError> {
+    let passport = map(record(word), |fields| {
+        fields
+            .into_iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    });
+    parse_all(input, blocks(passport))
 }
 
 pub fn validate(passport: &Passport) -> bool {
@@ -48,7 +41,7 @@ fn validate_byr(input: &str) -> bool {
         Ok(v) => v,
         Err(_) => return false,
     };
-    number >= 1920 && number <= 2002 && input.len() == 4
+    (1920..=2002).contains(&number) && input.len() == 4
 }
 
 fn validate_iyr(input: &str) -> bool {
@@ -56,7 +49,7 @@ fn validate_iyr(input: &str) -> bool {
         Ok(v) => v,
         Err(_) => return false,
     };
-    number >= 2010 && number <= 2020 && input.len() == 4
+    (2010..=2020).contains(&number) && input.len() == 4
 }
 
 fn validate_eyr(input: &str) -> bool {
@@ -64,7 +57,7 @@ fn validate_eyr(input: &str) -> bool {
         Ok(v) => v,
         Err(_) => return false,
     };
-    number >= 2020 && number <= 2030 && input.len() == 4
+    (2020..=2030).contains(&number) && input.len() == 4
 }
 
 fn validate_hgt(input: &str) -> bool {
@@ -77,14 +70,14 @@ fn validate_hgt(input: &str) -> bool {
             Ok(v) => v,
             Err(_) => return false,
         };
-        number >= 150 && number <= 193
+        (150..=193).contains(&number)
     } else if input.contains("in") {
         let caps = in_re.captures(input).unwrap();
         let number: i32 = match caps[1].parse() {
             Ok(v) => v,
             Err(_) => return false,
         };
-        number >= 59 && number <= 76
+        (59..=76).contains(&number)
     } else {
         false
     }
@@ -96,16 +89,7 @@ fn validate_hcl(input: &str) -> bool {
 }
 
 fn validate_ecl(input: &str) -> bool {
-    match input {
-        "amb" => true,
-        "blu" => true,
-        "brn" => true,
-        "gry" => true,
-        "grn" => true,
-        "hzl" => true,
-        "oth" => true,
-        _ => false,
-    }
+    matches!(input, "amb" | "blu" | "brn" | "gry" | "grn" | "hzl" | "oth")
 }
 
 fn validate_pid(input: &str) -> bool {
@@ -116,13 +100,13 @@ fn validate_pid(input: &str) -> bool {
 fn extra_validate(passport: &Passport) -> bool {
     for (key, value) in passport {
         if match key.as_str() {
-            "byr" => validate_byr(&value),
-            "iyr" => validate_iyr(&value),
-            "eyr" => validate_eyr(&value),
-            "hgt" => validate_hgt(&value),
-            "hcl" => validate_hcl(&value),
-            "ecl" => validate_ecl(&value),
-            "pid" => validate_pid(&value),
+            "byr" => validate_byr(value),
+            "iyr" => validate_iyr(value),
+            "eyr" => validate_eyr(value),
+            "hgt" => validate_hgt(value),
+            "hcl" => validate_hcl(value),
+            "ecl" => validate_ecl(value),
+            "pid" => validate_pid(value),
             "cid" => true,
             _ => false,
         } {
@@ -160,6 +144,24 @@ pub fn count_extra_valid(passports: Vec<Passport>) -> i32 {
     valid
 }
 
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = Vec<Passport>;
+
+    fn parse(input: &str) -> ParseResult<Vec<Passport>> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(passports: &Vec<Passport>) -> PartResult<impl Display> {
+        Ok(count_valid(passports.clone()))
+    }
+
+    fn part2(passports: &Vec<Passport>) -> PartResult<impl Display> {
+        Ok(count_extra_valid(passports.clone()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -167,63 +169,64 @@ mod tests {
 
     #[test]
     fn part_1_example() {
-        let input = read_input("example1.txt");
-        let parsed_data = parse_input(input);
+        let parsed_data = read_input("example1.txt").unwrap();
         assert_eq!(2, count_valid(parsed_data));
     }
 
     #[test]
     fn part_1_solution() {
-        let input = read_input("input.txt");
-        let parsed_data = parse_input(input);
+        let parsed_data = read_input("input.txt").unwrap();
         assert_eq!(213, count_valid(parsed_data));
     }
 
     #[test]
     fn part_2_example() {
-        assert_eq!(true, validate_byr("2002"));
-        assert_eq!(false, validate_byr("2003"));
-        assert_eq!(false, validate_byr("b"));
+        assert!(validate_byr("2002"));
+        assert!(!validate_byr("2003"));
+        assert!(!validate_byr("b"));
 
-        assert_eq!(true, validate_iyr("2012"));
-        assert_eq!(false, validate_iyr("2a012"));
+        assert!(validate_iyr("2012"));
+        assert!(!validate_iyr("2a012"));
 
-        assert_eq!(true, validate_hgt("60in"));
-        assert_eq!(true, validate_hgt("190cm"));
-        assert_eq!(false, validate_hgt("190in"));
-        assert_eq!(false, validate_hgt("190"));
+        assert!(validate_hgt("60in"));
+        assert!(validate_hgt("190cm"));
+        assert!(!validate_hgt("190in"));
+        assert!(!validate_hgt("190"));
 
-        assert_eq!(true, validate_hcl("#123abc"));
-        assert_eq!(false, validate_hcl("#123abz"));
-        assert_eq!(false, validate_hcl("123abc"));
+        assert!(validate_hcl("#123abc"));
+        assert!(!validate_hcl("#123abz"));
+        assert!(!validate_hcl("123abc"));
 
-        assert_eq!(true, validate_ecl("brn"));
-        assert_eq!(false, validate_ecl("wat"));
+        assert!(validate_ecl("brn"));
+        assert!(!validate_ecl("wat"));
 
-        assert_eq!(true, validate_pid("000000001"));
-        assert_eq!(false, validate_pid("0123456789"));
+        assert!(validate_pid("000000001"));
+        assert!(!validate_pid("0123456789"));
     }
 
     #[test]
     fn part_2_valid_examples() {
-        let input = read_input("example2_valid.txt");
+        let parsed_data = read_input("example2_valid.txt").unwrap();
 
-        let parsed_data = parse_input(input);
         assert_eq!(4, count_extra_valid(parsed_data))
     }
 
     #[test]
     fn part_2_invalid_examples() {
-        let input = read_input("example2_invalid.txt");
+        let parsed_data = read_input("example2_invalid.txt").unwrap();
 
-        let parsed_data = parse_input(input);
         assert_eq!(0, count_extra_valid(parsed_data))
     }
 
     #[test]
     fn part_2_solution() {
-        let input = read_input("input.txt");
-        let parsed_data = parse_input(input);
+        let parsed_data = read_input("input.txt").unwrap();
         assert_eq!(147, count_extra_valid(parsed_data));
     }
+
+    #[test]
+    fn bad_passport() {
+        let error = parse_input("ecl:gry pid:860033327\nhcl #fffffd\n\nbyr:1937").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 4));
+    }
 }
@@ -1,3 +1,12 @@
+use parser::nom::{
+    character::complete::one_of, combinator::recognize, error::context, multi::count,
+    sequence::pair,
+};
+use parser::{lines, parse_all, ParseError, Parsed};
+use solution::{ParseResult, PartResult, Solution};
+use std::collections::BTreeSet;
+use std::fmt::Display;
+
 fn get_search_space(size: i32) -> Vec<i32> {
     let mut v = Vec::new();
     for i in 0..size {
@@ -6,13 +15,13 @@ fn get_search_space(size: i32) -> Vec<i32> {
     v
 }
 
-fn left_half(space: &Vec<i32>) -> Vec<i32> {
+fn left_half(space: &[i32]) -> Vec<i32> {
     let len = space.len();
 
     space[0..(len / 2)].to_vec()
 }
 
-fn right_half(space: &Vec<i32>) -> Vec<i32> {
+fn right_half(space: &[i32]) -> Vec<i32> {
     let len = space.len();
 
     space[(len / 2)..len].to_vec()
@@ -51,16 +60,49 @@ pub fn compute_seat_id(input: &str) -> i32 {
     get_row(input) * 8 + get_column(input)
 }
 
+// Seven of F or B for the row then three of L or R for the column, "FBFBBFFRLR"
+fn boarding_pass(input: &str) -> Parsed<'_, &str> {
+    context(
+        "boarding pass",
+        recognize(pair(count(one_of("FB"), 7), count(one_of("LR"), 3))),
+    )(input)
+}
+
+pub fn parse_passes(input: &str) -> Result<Vec<&str>, ParseError> {
+    parse_all(input, lines(boarding_pass))
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = BTreeSet<i32>;
+
+    fn parse(input: &str) -> ParseResult<BTreeSet<i32>> {
+        Ok(parse_passes(input)?
+            .into_iter()
+            .map(compute_seat_id)
+            .collect())
+    }
+
+    fn part1(ids: &BTreeSet<i32>) -> PartResult<impl Display> {
+        Ok(*ids.iter().next_back().ok_or("No boarding passes")?)
+    }
+
+    // The missing seat with both its neighbours taken
+    fn part2(ids: &BTreeSet<i32>) -> PartResult<impl Display> {
+        Ok(ids
+            .iter()
+            .map(|id| id + 1)
+            .find(|id| !ids.contains(id) && ids.contains(&(id + 1)))
+            .ok_or("No free seat between two taken ones")?)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::fs::File;
-    use std::io::Read;
     pub fn parse_input() -> Vec<String> {
-        let mut contents = String::new();
-        let mut file = File::open("input.txt").unwrap();
-        file.read_to_string(&mut contents).unwrap();
-
+        let contents = input::read!("input.txt").unwrap();
         let collection: Vec<String> = contents.split('\n').map(|x| x.to_string()).collect();
         collection
     }
@@ -142,4 +184,14 @@ mod tests {
         println!("My ID: {}", my_id);
         assert_eq!(my_id, 587);
     }
+
+    #[test]
+    fn bad_pass() {
+        let error = parse_passes("BFFFBBFRRR\nFFFBBBFRRL\nBBFFBBRFLL").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 3, column 1: expected boarding pass, found \"BBFFBBRFLL\""
+        );
+        assert!(parse_passes("FBFBBFFRLRL").is_err());
+    }
 }
@@ -1,14 +1,26 @@
+use input::InputError;
+use parser::nom::{
+    bytes::complete::take_while1,
+    combinator::{map, recognize},
+    error::context,
+};
+use parser::{blocks, lines, parse_all, ParseError, Parsed};
+use solution::{ParseResult, PartResult, Solution};
 use std::collections::HashSet;
-use std::fs::File;
-use std::io::Read;
+use std::fmt::Display;
 
-pub fn read_groups(filename: &str) -> Vec<String> {
-    let mut contents = String::new();
-    let mut file = File::open(filename).unwrap();
-    file.read_to_string(&mut contents).unwrap();
+pub fn read_groups(filename: &str) -> Result<Vec<String>, InputError> {
+    input::parse!(filename, parse_groups)
+}
 
-    let collection: Vec<String> = contents.split("\n\n").map(|x| x.to_string()).collect();
-    collection
+// The questions each person answered yes to, one person per line
+fn group(input: &str) -> Parsed<'_, &str> {
+    let answers = take_while1(|c: char| c.is_ascii_lowercase());
+    recognize(lines(context("answers", answers)))(input)
+}
+
+pub fn parse_groups(input: &str) -> Result<Vec<String>, ParseError> {
+    parse_all(input, blocks(map(group, String::from)))
 }
 
 pub fn get_people(group: String) -> Vec<String> {
@@ -20,7 +32,7 @@ pub fn score_group(group: String) -> usize {
     let people = get_people(group);
 
     let answers = people.iter().fold(String::new(), |mut collection, person| {
-        collection += &person;
+        collection += person;
         collection
     });
 
@@ -40,19 +52,43 @@ pub fn score_group2(group: String) -> usize {
     let group_answer = answers
         .iter()
         .fold(answers[0].clone(), |mut collection, answer| {
-            collection = collection.intersection(&answer).copied().collect();
+            collection = collection.intersection(answer).copied().collect();
             collection
         });
 
     group_answer.len()
 }
 
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> ParseResult<Vec<String>> {
+        Ok(parse_groups(input)?)
+    }
+
+    fn part1(groups: &Vec<String>) -> PartResult<impl Display> {
+        Ok(groups
+            .iter()
+            .map(|group| score_group(group.to_string()))
+            .sum::<usize>())
+    }
+
+    fn part2(groups: &Vec<String>) -> PartResult<impl Display> {
+        Ok(groups
+            .iter()
+            .map(|group| score_group2(group.to_string()))
+            .sum::<usize>())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     #[test]
     fn part_1_example() {
-        let groups = read_groups("example1.txt");
+        let groups = read_groups("example1.txt").unwrap();
         assert_eq!(groups.len(), 5);
 
         let score = groups.iter().fold(0, |mut score, group| {
@@ -65,7 +101,7 @@ mod tests {
 
     #[test]
     fn part_1_solution() {
-        let groups = read_groups("input.txt");
+        let groups = read_groups("input.txt").unwrap();
 
         let score = groups.iter().fold(0, |mut score, group| {
             score += score_group(group.to_string());
@@ -77,7 +113,7 @@ mod tests {
 
     #[test]
     fn part_2_example() {
-        let groups = read_groups("example1.txt");
+        let groups = read_groups("example1.txt").unwrap();
         assert_eq!(groups.len(), 5);
 
         let score = groups.iter().fold(0, |mut score, group| {
@@ -90,7 +126,7 @@ mod tests {
 
     #[test]
     fn part_2_solution() {
-        let groups = read_groups("input.txt");
+        let groups = read_groups("input.txt").unwrap();
 
         let score = groups.iter().fold(0, |mut score, group| {
             score += score_group2(group.to_string());
@@ -99,4 +135,11 @@ mod tests {
 
         assert_eq!(score, 3430);
     }
+
+    #[test]
+    fn bad_group() {
+        let error = parse_groups("abc\n\na\nb\nc\n\nab\nA c").unwrap_err();
+        assert_eq!((error.line, error.column), (8, 1));
+        assert_eq!(error.expected, "answers");
+    }
 }
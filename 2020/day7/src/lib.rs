@@ -1,42 +1,62 @@
 // #![recursion_limit = "1023"]
 
+use input::InputError;
+use parser::nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{alpha1, char},
+    combinator::{recognize, value},
+    multi::separated_list1,
+    sequence::{separated_pair, terminated, tuple},
+};
+use parser::{integer, lines, parse_all, ParseError, Parsed};
+use solution::{ParseResult, PartResult, Solution};
 use std::collections::HashMap;
 use std::collections::HashSet;
-use std::fs::File;
-use std::io::Read;
+use std::fmt::Display;
 
-pub fn read_rules(filename: &str) -> Vec<String> {
-    let mut contents = String::new();
-    let mut file = File::open(filename).unwrap();
-    file.read_to_string(&mut contents).unwrap();
+// A bag colour and the bags of each colour it has to hold
+pub type Rule = (String, Vec<(i32, String)>);
 
-    let collection: Vec<String> = contents.split("\n").map(|x| x.to_string()).collect();
-    collection
+pub fn read_rules(filename: &str) -> Result<Vec<Rule>, InputError> {
+    input::parse!(filename, parse_rules)
 }
 
-pub fn extract_rule(rule: &str) -> (String, Vec<(i32, String)>) {
-    let rule = &rule.replace(" bags", "");
-    let rule = &rule.replace(" contain", "");
-    let rule = &rule.replace(" bag", "");
-    let rule = &rule.replace(",", "");
-    let rule = &rule.replace(".", "");
+// "vibrant plum"
+fn colour(input: &str) -> Parsed<'_, String> {
+    let (input, colour) = recognize(tuple((alpha1, char(' '), alpha1)))(input)?;
+    Ok((input, colour.to_string()))
+}
 
-    let words: Vec<String> = rule.split(" ").map(|x| x.to_string()).collect();
+// "5 faded blue bags" or "1 faded blue bag"
+fn contents(input: &str) -> Parsed<'_, (i32, String)> {
+    terminated(
+        separated_pair(integer, char(' '), colour),
+        alt((tag(" bags"), tag(" bag"))),
+    )(input)
+}
 
-    let parent_node = String::from(&words[0]) + " " + &words[1];
+// "vibrant plum bags contain 5 faded blue bags, 6 dotted black bags."
+fn rule(input: &str) -> Parsed<'_, Rule> {
+    separated_pair(
+        colour,
+        tag(" bags contain "),
+        terminated(
+            alt((
+                value(Vec::new(), tag("no other bags")),
+                separated_list1(tag(", "), contents),
+            )),
+            char('.'),
+        ),
+    )(input)
+}
 
-    let mut nodes = Vec::new();
-    for i in (2..words.len()).step_by(3) {
-        if words[i] == "no" {
-            continue;
-        }
-        nodes.push((
-            words[i].parse::<i32>().unwrap(),
-            String::from(&words[i + 1]) + " " + &words[i + 2],
-        ));
-    }
+pub fn extract_rule(rule_str: &str) -> Result<Rule, ParseError> {
+    parse_all(rule_str, rule)
+}
 
-    (parent_node, nodes)
+pub fn parse_rules(input: &str) -> Result<Vec<Rule>, ParseError> {
+    parse_all(input, lines(rule))
 }
 
 fn get_parents(target: &str, rules_engine: &HashMap<String, Vec<String>>) -> Vec<String> {
@@ -80,23 +100,18 @@ fn count_parents(finish: &str, rules_engine: &HashMap<String, Vec<String>>) -> u
 
 fn explore(current_node: &str, rules_engine: &HashMap<String, Vec<(i32, String)>>) -> i32 {
     let mut score = 1;
-    match rules_engine.get(current_node) {
-        Some(children) => {
-            for (cost, child) in children {
-                score += cost * explore(&child, rules_engine);
-            }
+    if let Some(children) = rules_engine.get(current_node) {
+        for (cost, child) in children {
+            score += cost * explore(child, rules_engine);
         }
-        None => {}
     }
     score
 }
 
-pub fn count_the_ways_rev(rules: Vec<String>, target: &str) -> usize {
+pub fn count_the_ways_rev(rules: Vec<Rule>, target: &str) -> usize {
     let mut rules_engine: HashMap<String, Vec<String>> = HashMap::new();
 
-    for rule_str in &rules {
-        let (parent, children) = extract_rule(&rule_str);
-
+    for (parent, children) in rules {
         for (_cost, child) in children {
             let e = rules_engine
                 .entry(parent.clone())
@@ -108,14 +123,12 @@ pub fn count_the_ways_rev(rules: Vec<String>, target: &str) -> usize {
     count_parents(target, &rules_engine)
 }
 
-pub fn count_capacity(rules: Vec<String>, target: &str) -> i32 {
+pub fn count_capacity(rules: Vec<Rule>, target: &str) -> i32 {
     let mut rules_engine: HashMap<String, Vec<(i32, String)>> = HashMap::new();
 
-    for rule_str in &rules {
-        let (parent, children) = extract_rule(&rule_str);
-
+    for (parent, children) in rules {
         for (cost, child) in children {
-            let e = rules_engine.entry(parent.clone()).or_insert(Vec::new());
+            let e = rules_engine.entry(parent.clone()).or_default();
             e.push((cost, child.clone()));
         }
     }
@@ -127,12 +140,32 @@ fn count_weighted_children(start: &str, rules_engine: &HashMap<String, Vec<(i32,
     explore(start, rules_engine)
 }
 
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Vec<Rule>;
+
+    fn parse(input: &str) -> ParseResult<Vec<Rule>> {
+        Ok(parse_rules(input)?)
+    }
+
+    // Bags that can end up holding a shiny gold one
+    fn part1(rules: &Vec<Rule>) -> PartResult<impl Display> {
+        Ok(count_the_ways_rev(rules.clone(), "shiny gold"))
+    }
+
+    // Bags a shiny gold one has to hold
+    fn part2(rules: &Vec<Rule>) -> PartResult<impl Display> {
+        Ok(count_capacity(rules.clone(), "shiny gold"))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     #[test]
     fn part_1_example() {
-        let rules = read_rules("example1.txt");
+        let rules = read_rules("example1.txt").unwrap();
         let score = count_the_ways_rev(rules, "shiny gold");
         assert_eq!(score, 4);
     }
@@ -140,24 +173,24 @@ mod tests {
     #[test]
     fn rule_extractor_test() {
         let rule_str = "vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.";
-        let rule = extract_rule(rule_str);
+        let rule = extract_rule(rule_str).unwrap();
         assert_eq!(rule.0, "vibrant plum");
         assert_eq!(rule.1.len(), 2);
 
         let rule_str = "vibrant plum bags contain 5 faded blue bags.";
-        let rule = extract_rule(rule_str);
+        let rule = extract_rule(rule_str).unwrap();
         assert_eq!(rule.0, "vibrant plum");
         assert_eq!(rule.1.len(), 1);
 
         let rule_str = "faded yellow bags contain 3 posh lime bags, 4 wavy blue bags, 3 faded crimson bags, 2 shiny lavender bags.";
-        let rule = extract_rule(rule_str);
+        let rule = extract_rule(rule_str).unwrap();
         assert_eq!(rule.0, "faded yellow");
         assert_eq!(rule.1.len(), 4);
     }
 
     #[test]
     fn part_1_solution() {
-        let rules = read_rules("input.txt");
+        let rules = read_rules("input.txt").unwrap();
         let score = count_the_ways_rev(rules, "shiny gold");
 
         assert_eq!(score, 172)
@@ -165,7 +198,7 @@ mod tests {
 
     #[test]
     fn part_2_example_1() {
-        let rules = read_rules("example1.txt");
+        let rules = read_rules("example1.txt").unwrap();
         let score = count_capacity(rules, "shiny gold");
 
         assert_eq!(score, 32);
@@ -173,15 +206,28 @@ mod tests {
 
     #[test]
     fn part_2_example_2() {
-        let rules = read_rules("example2.txt");
+        let rules = read_rules("example2.txt").unwrap();
         let score = count_capacity(rules, "shiny gold");
         assert_eq!(score, 126);
     }
 
     #[test]
     fn part_2_solution() {
-        let rules = read_rules("input.txt");
+        let rules = read_rules("input.txt").unwrap();
         let score = count_capacity(rules, "shiny gold");
         assert_eq!(score, 39645);
     }
+
+    #[test]
+    fn bad_rule() {
+        let rule = extract_rule("faded blue bags contain no other bags.").unwrap();
+        assert_eq!(rule, ("faded blue".to_string(), vec![]));
+
+        let error = parse_rules(
+            "bright white bags contain 1 shiny gold bag.\nmuted yellow bags contain 2 shiny gold bags 9 faded blue bags.",
+        )
+        .unwrap_err();
+        assert_eq!((error.line, error.column), (2, 44));
+        assert_eq!(error.expected, "'.'");
+    }
 }
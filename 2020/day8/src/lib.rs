@@ -1,11 +1,17 @@
-use std::fs::File;
-use std::{collections::HashSet, io::Read};
+use input::InputError;
+use parser::nom::{
+    branch::alt, bytes::complete::tag, combinator::map, error::context, sequence::preceded,
+};
+use parser::{integer, lines, parse_all, ParseError, Parsed};
+use solution::{ParseResult, PartResult, Solution};
+use std::collections::HashSet;
+use std::fmt::Display;
 
 #[derive(Debug, Copy, Clone)]
 pub enum Instruction {
-    nop(i32), // No Op
-    acc(i32), // Accumulate
-    jmp(i32), // Relative Jump
+    Nop(i32), // No Op
+    Acc(i32), // Accumulate
+    Jmp(i32), // Relative Jump
 }
 
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -14,24 +20,23 @@ pub enum ReturnStatus<T> {
     Duplicate(T),
 }
 
-pub fn read_instructions(filename: &str) -> Vec<Instruction> {
-    let mut contents = String::new();
-    let mut file = File::open(filename).unwrap();
-    file.read_to_string(&mut contents).unwrap();
-
-    let collection: Vec<String> = contents.split("\n").map(|x| x.to_string()).collect();
-    let mut instructions = Vec::new();
-    for line in collection {
-        let words: Vec<&str> = line.split(" ").collect();
-        instructions.push(match words[0] {
-            "nop" => Instruction::nop(words[1].parse().unwrap()),
-            "acc" => Instruction::acc(words[1].parse().unwrap()),
-            "jmp" => Instruction::jmp(words[1].parse().unwrap()),
-            _ => panic!("Unexpected input instruction"),
-        })
-    }
+pub fn read_instructions(filename: &str) -> Result<Vec<Instruction>, InputError> {
+    input::parse!(filename, parse_instructions)
+}
 
-    instructions
+fn instruction(input: &str) -> Parsed<'_, Instruction> {
+    context(
+        "instruction",
+        alt((
+            map(preceded(tag("nop "), integer), Instruction::Nop),
+            map(preceded(tag("acc "), integer), Instruction::Acc),
+            map(preceded(tag("jmp "), integer), Instruction::Jmp),
+        )),
+    )(input)
+}
+
+pub fn parse_instructions(contents: &str) -> Result<Vec<Instruction>, ParseError> {
+    parse_all(contents, lines(instruction))
 }
 
 pub fn execute_instructions(instructions: Vec<Instruction>) -> ReturnStatus<i32> {
@@ -44,16 +49,16 @@ pub fn execute_instructions(instructions: Vec<Instruction>) -> ReturnStatus<i32>
         if instruction_history.contains(&line_number) {
             return ReturnStatus::Duplicate(accumulator);
         }
-        instruction_history.insert(line_number.clone());
+        instruction_history.insert(line_number);
 
         match instructions.get(line_number as usize) {
             Some(inst) => match inst {
-                Instruction::nop(_) => line_number += 1,
-                Instruction::acc(v) => {
+                Instruction::Nop(_) => line_number += 1,
+                Instruction::Acc(v) => {
                     accumulator += v;
                     line_number += 1;
                 }
-                Instruction::jmp(v) => line_number += v,
+                Instruction::Jmp(v) => line_number += v,
                 // _ => panic!("Unexpected instruction"),
             },
             None => break,
@@ -63,7 +68,7 @@ pub fn execute_instructions(instructions: Vec<Instruction>) -> ReturnStatus<i32>
 }
 
 // Return the line numbers of all instances of an instruction type
-pub fn find_instances(instructions: &Vec<Instruction>, target: Instruction) -> Vec<usize> {
+pub fn find_instances(instructions: &[Instruction], target: Instruction) -> Vec<usize> {
     let mut line_numbers = Vec::new();
 
     for (line_number, line) in instructions.iter().enumerate() {
@@ -77,32 +82,66 @@ pub fn find_instances(instructions: &Vec<Instruction>, target: Instruction) -> V
 
 // return a copy of the instructions with the target line swapped
 pub fn instruction_swapper(
-    instructions: &Vec<Instruction>,
+    instructions: &[Instruction],
     target_line: usize,
 ) -> Vec<Instruction> {
     let mut new_inst = Vec::new();
     for (line_number, inst) in instructions.iter().enumerate() {
         if line_number == target_line {
             new_inst.push(match inst {
-                Instruction::jmp(v) => Instruction::nop(*v),
-                Instruction::nop(v) => Instruction::jmp(*v),
+                Instruction::Jmp(v) => Instruction::Nop(*v),
+                Instruction::Nop(v) => Instruction::Jmp(*v),
                 _ => panic!("Unswappable type!"),
             })
         } else {
-            new_inst.push(inst.clone())
+            new_inst.push(*inst)
         }
     }
 
     new_inst
 }
 
+// Flip one jmp/nop at a time until the program runs off the end
+pub fn repair(instructions: &[Instruction]) -> Option<i32> {
+    let mut candidates = find_instances(instructions, Instruction::Jmp(0));
+    candidates.append(&mut find_instances(instructions, Instruction::Nop(0)));
+
+    candidates.into_iter().find_map(|attempt| {
+        match execute_instructions(instruction_swapper(instructions, attempt)) {
+            ReturnStatus::Success(v) => Some(v),
+            ReturnStatus::Duplicate(_) => None,
+        }
+    })
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = Vec<Instruction>;
+
+    fn parse(input: &str) -> ParseResult<Vec<Instruction>> {
+        Ok(parse_instructions(input)?)
+    }
+
+    fn part1(instructions: &Vec<Instruction>) -> PartResult<impl Display> {
+        Ok(match execute_instructions(instructions.clone()) {
+            ReturnStatus::Duplicate(v) => v,
+            ReturnStatus::Success(_) => panic!("Program terminated without looping"),
+        })
+    }
+
+    fn part2(instructions: &Vec<Instruction>) -> PartResult<impl Display> {
+        Ok(repair(instructions).expect("No single swap fixes the program"))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn part_1_example() {
-        let instructions = read_instructions("example1.txt");
+        let instructions = read_instructions("example1.txt").unwrap();
         assert_eq!(
             execute_instructions(instructions),
             ReturnStatus::Duplicate(5)
@@ -111,18 +150,28 @@ mod tests {
 
     #[test]
     fn part_1_solution() {
-        let instructions = read_instructions("input.txt");
+        let instructions = read_instructions("input.txt").unwrap();
         assert_eq!(
             execute_instructions(instructions),
             ReturnStatus::Duplicate(2058)
         );
     }
 
+    #[test]
+    fn unexpected_instruction() {
+        let error = parse_instructions("nop +0\nacc +1\njmp -4\nhcf +1").unwrap_err();
+        assert_eq!((error.line, error.column), (4, 1));
+        assert_eq!(
+            error.to_string(),
+            "line 4, column 1: expected instruction, found \"hcf +1\""
+        );
+    }
+
     #[test]
     fn part_2_example() {
-        let instructions = read_instructions("example1.txt");
-        let mut jumps = find_instances(&instructions, Instruction::jmp(0));
-        let mut noops = find_instances(&instructions, Instruction::nop(0));
+        let instructions = read_instructions("example1.txt").unwrap();
+        let mut jumps = find_instances(&instructions, Instruction::Jmp(0));
+        let mut noops = find_instances(&instructions, Instruction::Nop(0));
 
         jumps.append(&mut noops);
 
@@ -141,9 +190,9 @@ mod tests {
 
     #[test]
     fn part_2_solution() {
-        let instructions = read_instructions("input.txt");
-        let mut jumps = find_instances(&instructions, Instruction::jmp(0));
-        let mut noops = find_instances(&instructions, Instruction::nop(0));
+        let instructions = read_instructions("input.txt").unwrap();
+        let mut jumps = find_instances(&instructions, Instruction::Jmp(0));
+        let mut noops = find_instances(&instructions, Instruction::Nop(0));
 
         jumps.append(&mut noops);
 
@@ -1,14 +1,15 @@
+use input::InputError;
+use parser::{integer, lines, parse_all, ParseError};
+use solution::{ParseResult, PartResult, Solution};
 use std::collections::vec_deque::VecDeque;
-use std::fs::File;
-use std::io::Read;
+use std::fmt::Display;
 
-pub fn read_input(filename: &str) -> Vec<i64> {
-    let mut contents = String::new();
-    let mut file = File::open(filename).unwrap();
-    file.read_to_string(&mut contents).unwrap();
+pub fn read_input(filename: &str) -> Result<Vec<i64>, InputError> {
+    input::parse!(filename, parse_input)
+}
 
-    let collection: Vec<i64> = contents.split("\n").map(|x| x.parse().unwrap()).collect();
-    collection
+pub fn parse_input(contents: &str) -> Result<Vec<i64>, ParseError> {
+    parse_all(contents, lines(integer))
 }
 
 fn is_valid(cypher: &VecDeque<i64>, entry: i64) -> bool {
@@ -26,29 +27,29 @@ fn is_valid(cypher: &VecDeque<i64>, entry: i64) -> bool {
     false
 }
 
-pub fn find_error(input: &Vec<i64>, preamble_size: usize) -> i64 {
+pub fn find_error(input: &[i64], preamble_size: usize) -> i64 {
     let mut cypher: VecDeque<i64> = VecDeque::with_capacity(preamble_size);
 
     // preload the cypher
-    for i in 0..preamble_size {
-        cypher.push_front(input[i]);
+    for &value in &input[..preamble_size] {
+        cypher.push_front(value);
     }
 
     // process data
-    for i in preamble_size as usize..input.len() {
-        if !is_valid(&cypher, input[i]) {
-            return input[i];
+    for &value in &input[preamble_size..] {
+        if !is_valid(&cypher, value) {
+            return value;
         }
 
         cypher.pop_back();
-        cypher.push_front(input[i]);
+        cypher.push_front(value);
     }
 
     // failure
     -1
 }
 
-pub fn find_weakness_set(input: &Vec<i64>, error: i64) -> Vec<i64> {
+pub fn find_weakness_set(input: &[i64], error: i64) -> Vec<i64> {
     for i in 0..input.len() - 1 {
         let mut k = 0;
         let mut value = 0;
@@ -56,7 +57,7 @@ pub fn find_weakness_set(input: &Vec<i64>, error: i64) -> Vec<i64> {
             value += input[i + k];
 
             if value == error {
-                let ans: Vec<i64> = input[i..=i + k].iter().map(|x| x.clone()).collect();
+                let ans: Vec<i64> = input[i..=i + k].to_vec();
                 return ans;
             }
 
@@ -68,12 +69,12 @@ pub fn find_weakness_set(input: &Vec<i64>, error: i64) -> Vec<i64> {
     }
 
     //failure
-    return Vec::new();
+    Vec::new()
 }
 
-pub fn find_weakness_set_improved(input: &Vec<i64>, error: i64) -> VecDeque<i64> {
+pub fn find_weakness_set_improved(input: &[i64], error: i64) -> VecDeque<i64> {
     let mut weakness_set = VecDeque::new();
-    let mut input: VecDeque<i64> = input.iter().map(|x|{x.clone()}).collect();
+    let mut input: VecDeque<i64> = input.iter().copied().collect();
     loop {
         let value: i64 = weakness_set.iter().sum();
         if value > error {
@@ -88,19 +89,38 @@ pub fn find_weakness_set_improved(input: &Vec<i64>, error: i64) -> VecDeque<i64>
     weakness_set
 }
 
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = Vec<i64>;
+
+    fn parse(input: &str) -> ParseResult<Vec<i64>> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(input: &Vec<i64>) -> PartResult<impl Display> {
+        Ok(find_error(input, 25))
+    }
+
+    fn part2(input: &Vec<i64>) -> PartResult<impl Display> {
+        let set = find_weakness_set_improved(input, find_error(input, 25));
+        Ok(set.iter().max().unwrap() + set.iter().min().unwrap())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     #[test]
     fn part_1_example() {
-        let input = read_input("example1.txt");
+        let input = read_input("example1.txt").unwrap();
         let error = find_error(&input, 5);
 
         assert_eq!(error, 127);
     }
     #[test]
     fn part_1_solution() {
-        let input = read_input("input.txt");
+        let input = read_input("input.txt").unwrap();
         let error = find_error(&input, 25);
 
         assert_eq!(error, 1309761972);
@@ -108,7 +128,7 @@ mod tests {
 
     #[test]
     fn part_2_example() {
-        let input = read_input("example1.txt");
+        let input = read_input("example1.txt").unwrap();
         let error = find_error(&input, 5);
 
         let pair = find_weakness_set(&input, error);
@@ -119,7 +139,7 @@ mod tests {
 
     #[test]
     fn part_2_solution() {
-        let input = read_input("input.txt");
+        let input = read_input("input.txt").unwrap();
         let error = find_error(&input, 25);
 
         let pair = find_weakness_set(&input, error);
@@ -130,7 +150,7 @@ mod tests {
 
     #[test]
     fn part_2_solution_improved() {
-        let input = read_input("input.txt");
+        let input = read_input("input.txt").unwrap();
         let error = find_error(&input, 25);
 
         let pair = find_weakness_set_improved(&input, error);
@@ -138,4 +158,13 @@ mod tests {
 
         assert_eq!(ans, 177989832);
     }
+
+    #[test]
+    fn bad_number() {
+        let error = parse_input("35\n20\n-\n25").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 3, column 1: expected integer, found \"-\""
+        );
+    }
 }
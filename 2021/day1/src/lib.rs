@@ -1,32 +1,55 @@
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-};
-
-fn readInput(filename: &str) -> Vec<i32> {
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
-
-    reader
-        .lines()
-        .map(|x| x.unwrap().parse::<i32>().unwrap())
-        .collect()
+use input::InputError;
+use parser::{integer, lines, parse_all, ParseError};
+use solution::{ParseResult, PartResult, Solution};
+use std::fmt::Display;
+
+pub fn read_input(filename: &str) -> Result<Vec<i32>, InputError> {
+    input::parse!(filename, parse_input)
+}
+
+pub fn parse_input(contents: &str) -> Result<Vec<i32>, ParseError> {
+    parse_all(contents, lines(integer))
 }
 
-fn is_incrementing(a: i32, b: i32) -> bool {
+pub fn is_incrementing(a: i32, b: i32) -> bool {
     a < b
 }
 
-fn sliding_sum(a: i32, b: i32, c: i32) -> i32 {
+pub fn sliding_sum(a: i32, b: i32, c: i32) -> i32 {
     a + b + c
 }
 
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<i32>;
+
+    fn parse(input: &str) -> ParseResult<Vec<i32>> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(depths: &Vec<i32>) -> PartResult<impl Display> {
+        Ok(depths
+            .windows(2)
+            .filter(|w| is_incrementing(w[0], w[1]))
+            .count())
+    }
+
+    // Neighbouring windows share two depths, so only the ends need comparing
+    fn part2(depths: &Vec<i32>) -> PartResult<impl Display> {
+        Ok(depths
+            .windows(4)
+            .filter(|w| is_incrementing(w[0], w[3]))
+            .count())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     #[test]
     fn example1() {
-        let data = readInput("example1.txt");
+        let data = read_input("example1.txt").unwrap();
 
         let mut last = i32::MAX;
         let mut count = 0;
@@ -42,7 +65,7 @@ mod tests {
 
     #[test]
     fn part1() {
-        let data = readInput("input.txt");
+        let data = read_input("input.txt").unwrap();
 
         let mut last = i32::MAX;
         let mut count = 0;
@@ -58,7 +81,7 @@ mod tests {
 
     #[test]
     fn example2() {
-        let data = readInput("example1.txt");
+        let data = read_input("example1.txt").unwrap();
 
         let mut a = 0;
         let mut b = 0;
@@ -75,12 +98,12 @@ mod tests {
             b = d;
             last = sum;
         }
-        assert_eq!(count, 5)
+        assert_eq!(count - 2, 5)
     }
 
     #[test]
     fn part2() {
-        let data = readInput("input.txt");
+        let data = read_input("input.txt").unwrap();
 
         let mut a = 0;
         let mut b = 0;
@@ -99,4 +122,10 @@ mod tests {
         }
         assert_eq!(count - 2, 1359)
     }
+
+    #[test]
+    fn bad_depth() {
+        let error = parse_input("199\n200\n2o8\n210").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 2));
+    }
 }
@@ -1,13 +1,17 @@
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-};
-
-pub fn read_input(filename: &str) -> Vec<String> {
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
+use input::InputError;
+use parser::nom::{bytes::complete::take_while1, combinator::map, error::context};
+use parser::{lines, parse_all, ParseError};
+use solution::{ParseResult, PartResult, Solution};
+use std::fmt::Display;
+
+pub fn read_input(filename: &str) -> Result<Vec<String>, InputError> {
+    input::parse!(filename, parse_input)
+}
 
-    reader.lines().map(|x| x.unwrap()).collect()
+// Lines of nothing but brackets
+pub fn parse_input(contents: &str) -> Result<Vec<String>, ParseError> {
+    let chunks = context("brackets", take_while1(|c| "()[]{}<>".contains(c)));
+    parse_all(contents, lines(map(chunks, String::from)))
 }
 
 fn score_bad_char(line: &str) -> u64 {
@@ -105,6 +109,30 @@ pub fn complete_line(line: &str) -> u64 {
     }
 }
 
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> ParseResult<Self::Input> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(lines: &Self::Input) -> PartResult<impl Display> {
+        Ok(lines.iter().map(|line| evaluate_line(line)).sum::<u64>())
+    }
+
+    fn part2(lines: &Self::Input) -> PartResult<impl Display> {
+        let mut scores: Vec<u64> = lines
+            .iter()
+            .map(|line| complete_line(line))
+            .filter(|&score| score > 0)
+            .collect();
+        scores.sort();
+        Ok(scores[scores.len() / 2])
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -134,13 +162,13 @@ mod tests {
         let bad4 = "[<(<(<(<{}))><([]([]()";
         let bad5 = "<{([([[(<>()){}]>(<<{{";
 
-        assert_eq!(evaluate_line(&bad1), 1197);
-        assert_eq!(evaluate_line(&bad2), 3);
-        assert_eq!(evaluate_line(&bad3), 57);
-        assert_eq!(evaluate_line(&bad4), 3);
-        assert_eq!(evaluate_line(&bad5), 25137);
+        assert_eq!(evaluate_line(bad1), 1197);
+        assert_eq!(evaluate_line(bad2), 3);
+        assert_eq!(evaluate_line(bad3), 57);
+        assert_eq!(evaluate_line(bad4), 3);
+        assert_eq!(evaluate_line(bad5), 25137);
 
-        let input = read_input("example.txt");
+        let input = read_input("example.txt").unwrap();
         assert_eq!(
             input.iter().fold(0, |sum, line| sum + evaluate_line(line)),
             26397
@@ -149,7 +177,7 @@ mod tests {
 
     #[test]
     fn part1() {
-        let input = read_input("input.txt");
+        let input = read_input("input.txt").unwrap();
         assert_eq!(
             input.iter().fold(0, |sum, line| sum + evaluate_line(line)),
             390993
@@ -164,19 +192,17 @@ mod tests {
         assert_eq!(complete_line("{<[[]]>}<{[{[{[]{()[[[]"), 995444);
         assert_eq!(complete_line("<{([{{}}[<[[[<>{}]]]>[]]"), 294);
 
-        let mut scores1 = vec![
-            complete_line("[({(<(())[]>[[{[]{<()<>>"),
+        let mut scores1 = [complete_line("[({(<(())[]>[[{[]{<()<>>"),
             complete_line("[(()[<>])]({[<{<<[]>>("),
             complete_line("(((({<>}<{<{<>}{[]{[]{}"),
             complete_line("{<[[]]>}<{[{[{[]{()[[[]"),
-            complete_line("<{([{{}}[<[[[<>{}]]]>[]]"),
-        ];
+            complete_line("<{([{{}}[<[[[<>{}]]]>[]]")];
 
         scores1.sort();
 
         assert_eq!(scores1[scores1.len() / 2], 288957);
 
-        let input = read_input("example.txt");
+        let input = read_input("example.txt").unwrap();
         let mut scores = Vec::new();
         for line in input {
             let score = complete_line(&line);
@@ -192,7 +218,7 @@ mod tests {
 
     #[test]
     fn part2() {
-        let input = read_input("input.txt");
+        let input = read_input("input.txt").unwrap();
         let mut scores = Vec::new();
         for line in input {
             let score = complete_line(&line);
@@ -206,4 +232,10 @@ mod tests {
         assert_eq!(scores[scores.len() / 2], 2391385187);
 
     }
+
+    #[test]
+    fn bad_line() {
+        let error = parse_input("[({(<(())[]>[[{[]{<()<>>\n[(()[<>])]({[<{<<[]>>(x").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 23));
+    }
 }
@@ -1,23 +1,23 @@
+use grid::{Grid, GridError};
+use input::InputError;
+use solution::{ParseResult, PartResult, Solution};
 use std::{
     collections::{HashMap, HashSet},
-    fs::File,
-    io::{BufRead, BufReader},
+    fmt::Display,
 };
 
 type EnergyGrid = HashMap<(usize, usize), u32>;
 
-pub fn read_input(filename: &str) -> EnergyGrid {
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
-
-    let mut grid = EnergyGrid::new();
+pub fn read_input(filename: &str) -> Result<EnergyGrid, InputError> {
+    input::parse!(filename, parse_input)
+}
 
-    for (y, line) in reader.lines().enumerate() {
-        for (x, c) in line.unwrap().chars().enumerate() {
-            grid.insert((x, y), c.to_digit(10).unwrap());
-        }
-    }
-    grid
+pub fn parse_input(contents: &str) -> Result<EnergyGrid, GridError> {
+    let grid = Grid::try_parse(contents, |c| c.to_digit(10))?;
+    Ok(grid
+        .iter()
+        .map(|(point, &energy)| (point, energy))
+        .collect())
 }
 
 pub fn print_grid(grid: &EnergyGrid) {
@@ -160,20 +160,38 @@ pub fn find_sync(grid: &EnergyGrid) -> usize {
     steps
 }
 
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = EnergyGrid;
+
+    fn parse(input: &str) -> ParseResult<Self::Input> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(grid: &Self::Input) -> PartResult<impl Display> {
+        Ok(step(grid, 100))
+    }
+
+    fn part2(grid: &Self::Input) -> PartResult<impl Display> {
+        Ok(find_sync(grid))
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
     use super::*;
     #[test]
     fn example1a() {
-        let energies = read_input("example1.txt");
+        let energies = read_input("example1.txt").unwrap();
         let thing = step(&energies, 2);
         assert_eq!(thing, 9);
     }
 
     #[test]
     fn example1b() {
-        let energies = read_input("example2.txt");
+        let energies = read_input("example2.txt").unwrap();
         let after_10 = step(&energies, 10);
         assert_eq!(after_10, 204);
 
@@ -183,22 +201,34 @@ mod tests {
 
     #[test]
     fn part1() {
-        let energies = read_input("input.txt");
+        let energies = read_input("input.txt").unwrap();
         let after_100 = step(&energies, 100);
         assert_eq!(after_100, 1688);
     }
 
     #[test]
     fn example2() {
-        let energies = read_input("example2.txt");
+        let energies = read_input("example2.txt").unwrap();
         let sync = find_sync(&energies);
         assert_eq!(sync, 195);
     }
 
     #[test]
     fn part2() {
-        let energies = read_input("input.txt");
+        let energies = read_input("input.txt").unwrap();
         let sync = find_sync(&energies);
         assert_eq!(sync, 403);
     }
+
+    #[test]
+    fn bad_energies() {
+        assert_eq!(
+            parse_input("11111\n19991\n191").unwrap_err(),
+            GridError::Ragged {
+                line: 3,
+                width: 3,
+                expected: 5
+            }
+        );
+    }
 }
@@ -1,131 +1,99 @@
-use std::{
-    collections::HashSet,
-    fs::File,
-    io::{BufRead, BufReader},
+use input::InputError;
+use parser::nom::{
+    character::complete::{alpha1, char},
+    combinator::map,
+    error::context,
+    sequence::separated_pair,
 };
+use parser::{lines, parse_all, ParseError, Parsed};
+use solution::{ParseResult, PartResult, Solution};
+use std::{collections::BTreeSet, fmt::Display};
 
 type Node = String;
 type Edge = (Node, Node);
 
-pub fn read_input(filename: &str) -> Vec<Edge> {
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
-
-    reader
-        .lines()
-        .map(|x| {
-            let node_string = x.unwrap();
-            let mut nodes = node_string.split('-');
-            (
-                nodes.next().unwrap().to_string(),
-                nodes.next().unwrap().to_string(),
-            )
-        })
-        .collect()
+pub fn read_input(filename: &str) -> Result<Vec<Edge>, InputError> {
+    input::parse!(filename, parse_input)
+}
+
+fn cave(input: &str) -> Parsed<'_, Node> {
+    context("cave", map(alpha1, String::from))(input)
 }
 
-fn find_expansions(edges: &Vec<Edge>, node: &Node) -> Vec<Node> {
-    // oops this is a unidirectional expansion, we dont want that...
+// "start-A"
+pub fn parse_input(contents: &str) -> Result<Vec<Edge>, ParseError> {
+    parse_all(contents, lines(separated_pair(cave, char('-'), cave)))
+}
+
+fn find_expansions<'a>(edges: &'a [Edge], node: &str) -> Vec<&'a Node> {
+    // caves are joined both ways, so look at either end of each edge
     edges
         .iter()
         .filter(|(x, y)| x == node || y == node)
-        .map(|(x, y)| {
-            if x == node {
-                y.to_string()
-            } else {
-                x.to_string()
-            }
-        })
+        .map(|(x, y)| if x == node { y } else { x })
         .collect()
 }
 
-pub fn explore(edges: &Vec<Edge>) -> i32 {
-    let mut queue = Vec::new();
-
-    queue.push(vec!["start".to_string()]);
-
-    let mut paths = 0;
-    while !queue.is_empty() {
-        let path = queue.pop().unwrap();
-        let expansion_node = path.last().unwrap();
-
-        if expansion_node == "end" {
-            paths += 1;
-            continue;
-        }
-
-        // get possible expansions
-        let expansions = find_expansions(edges, &expansion_node);
-
-        for expansion in &expansions {
-            // Dont explore small caves multiple times
-            let first_char = expansion.chars().into_iter().next().unwrap();
-            if first_char.is_lowercase() && path.contains(expansion) {
-                continue;
-            }
-
-            // println!("Expanding: {:?} -> {:?}", path, &expansion);
-            let mut new_path = path.clone();
-            new_path.push(expansion.to_string());
+fn is_small(cave: &str) -> bool {
+    cave.chars().next().unwrap().is_lowercase()
+}
 
-            queue.push(new_path);
-        }
-    }
+// Where a path has got to, the small caves it has been through and whether it
+// can still go back into one of them
+type State<'a> = (&'a str, BTreeSet<&'a str>, bool);
 
-    paths
+fn count_routes<'a>(edges: &'a [Edge], revisit: bool) -> usize {
+    let expand = |(cave, visited, revisit): &State<'a>| {
+        find_expansions(edges, cave)
+            .into_iter()
+            // Dont _ever_ explore start
+            .filter(|&expansion| expansion != "start")
+            .filter_map(|expansion| {
+                let mut visited = visited.clone();
+                if !is_small(expansion) || visited.insert(expansion) {
+                    Some((expansion.as_str(), visited, *revisit))
+                } else if *revisit {
+                    // the one small cave allowed a second visit
+                    Some((expansion.as_str(), visited, false))
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+
+    search::count_paths(
+        ("start", BTreeSet::new(), revisit),
+        expand,
+        |(cave, _, _)| *cave == "end",
+    )
 }
 
-fn double_exploration(path: &Vec<Node>) -> bool {
-    let small_caves: Vec<&Node> = path
-        .iter()
-        .filter(|x| x.chars().into_iter().next().unwrap().is_lowercase())
-        .collect();
-    let set: HashSet<&&Node> = small_caves.iter().collect();
-    set.len() != small_caves.len()
+pub fn explore(edges: &[Edge]) -> usize {
+    count_routes(edges, false)
 }
 
-pub fn explore_revised(edges: &Vec<Edge>) -> i32 {
-    let mut queue = Vec::new();
-
-    queue.push(vec!["start".to_string()]);
-
-    let mut paths = 0;
-    while !queue.is_empty() {
-        let path = queue.pop().unwrap();
-        let expansion_node = path.last().unwrap();
+// Allow a single double-exploration now
+pub fn explore_revised(edges: &[Edge]) -> usize {
+    count_routes(edges, true)
+}
 
-        if expansion_node == "end" {
-            paths += 1;
-            continue;
-        }
+pub struct Day12;
 
-        // get possible expansions
-        let expansions = find_expansions(edges, &expansion_node);
+impl Solution for Day12 {
+    type Input = Vec<Edge>;
 
-        for expansion in &expansions {
-            // Dont _ever_ explore start
-            if expansion == "start" {
-                continue;
-            }
-
-            // Allow a single double-exploration now
-            if double_exploration(&path) {
-                // Dont explore small caves multiple times
-                let first_char = expansion.chars().into_iter().next().unwrap();
-                if first_char.is_lowercase() && path.contains(expansion) {
-                    continue;
-                }
-            }
-
-            // println!("Expanding: {:?} -> {:?}", path, &expansion);
-            let mut new_path = path.clone();
-            new_path.push(expansion.to_string());
+    fn parse(input: &str) -> ParseResult<Self::Input> {
+        Ok(parse_input(input)?)
+    }
 
-            queue.push(new_path);
-        }
+    fn part1(edges: &Self::Input) -> PartResult<impl Display> {
+        Ok(explore(edges))
     }
 
-    paths
+    fn part2(edges: &Self::Input) -> PartResult<impl Display> {
+        Ok(explore_revised(edges))
+    }
 }
 
 #[cfg(test)]
@@ -134,45 +102,52 @@ mod tests {
 
     #[test]
     fn example1() {
-        let edges1 = read_input("example1.txt");
+        let edges1 = read_input("example1.txt").unwrap();
         let paths1 = explore(&edges1);
         assert_eq!(paths1, 10);
 
-        let edges2 = read_input("example2.txt");
+        let edges2 = read_input("example2.txt").unwrap();
         let paths2 = explore(&edges2);
         assert_eq!(paths2, 19);
 
-        let edges3 = read_input("example3.txt");
+        let edges3 = read_input("example3.txt").unwrap();
         let paths3 = explore(&edges3);
         assert_eq!(paths3, 226);
     }
 
     #[test]
     fn part1() {
-        let edges = read_input("input.txt");
+        let edges = read_input("input.txt").unwrap();
         let paths = explore(&edges);
         assert_eq!(paths, 4338);
     }
 
     #[test]
     fn example2() {
-        let edges1 = read_input("example1.txt");
+        let edges1 = read_input("example1.txt").unwrap();
         let paths1 = explore_revised(&edges1);
         assert_eq!(paths1, 36);
 
-        let edges2 = read_input("example2.txt");
+        let edges2 = read_input("example2.txt").unwrap();
         let paths2 = explore_revised(&edges2);
         assert_eq!(paths2, 103);
 
-        let edges3 = read_input("example3.txt");
+        let edges3 = read_input("example3.txt").unwrap();
         let paths3 = explore_revised(&edges3);
         assert_eq!(paths3, 3509);
     }
 
     #[test]
     fn part2() {
-        let edges = read_input("input.txt");
+        let edges = read_input("input.txt").unwrap();
         let paths = explore_revised(&edges);
         assert_eq!(paths, 114189);
     }
+
+    #[test]
+    fn bad_edge() {
+        let error = parse_input("start-A\nstart-b\nA--c").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 3));
+        assert_eq!(error.expected, "cave");
+    }
 }
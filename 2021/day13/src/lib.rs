@@ -1,24 +1,32 @@
-use std::{
-    collections::HashSet,
-    fs::File,
-    io::{BufRead, BufReader},
+use input::InputError;
+use parser::nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::char,
+    combinator::{cut, map},
+    error::context,
+    sequence::{preceded, separated_pair},
 };
+use parser::{blank_lines, integer, lines, parse_all, ParseError, Parsed};
+use solution::{ParseResult, PartResult, Solution};
+use std::{collections::HashSet, fmt::Display};
 
-pub fn read_input(filename: &str) -> HashSet<(i32, i32)> {
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
-
-    reader
-        .lines()
-        .map(|x| {
-            let x = x.unwrap();
-            let mut coords = x.split(',');
-            (
-                coords.next().unwrap().clone().parse().unwrap(),
-                coords.next().unwrap().clone().parse().unwrap(),
-            )
-        })
-        .collect()
+// The dots and the folds to make
+pub type Manual = (HashSet<(i32, i32)>, Vec<Fold>);
+
+pub fn read_input(filename: &str) -> Result<Manual, InputError> {
+    input::parse!(filename, parse_input)
+}
+
+// The dots, a blank line and then the folds
+pub fn parse_input(contents: &str) -> Result<Manual, ParseError> {
+    let dots = map(lines(separated_pair(integer, char(','), integer)), |dots| {
+        dots.into_iter().collect()
+    });
+    parse_all(
+        contents,
+        separated_pair(dots, blank_lines, lines(fold_along)),
+    )
 }
 
 pub fn fold_along_x(x_mirror: i32, dots: &HashSet<(i32, i32)>) -> HashSet<(i32, i32)> {
@@ -63,6 +71,47 @@ pub fn print_dots(dots: &HashSet<(i32, i32)>) {
         println!();
     }
 }
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Fold {
+    X(i32),
+    Y(i32),
+}
+
+// "fold along y=7"
+fn fold_along(input: &str) -> Parsed<'_, Fold> {
+    let x = map(preceded(tag("x="), integer), Fold::X);
+    let y = map(preceded(tag("y="), integer), Fold::Y);
+    preceded(tag("fold along "), cut(context("fold", alt((x, y)))))(input)
+}
+
+pub fn fold(dots: &HashSet<(i32, i32)>, fold: Fold) -> HashSet<(i32, i32)> {
+    match fold {
+        Fold::X(x) => fold_along_x(x, dots),
+        Fold::Y(y) => fold_along_y(y, dots),
+    }
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Manual;
+
+    fn parse(input: &str) -> ParseResult<Self::Input> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1((dots, folds): &Self::Input) -> PartResult<impl Display> {
+        Ok(fold(dots, folds[0]).len())
+    }
+
+    fn part2((dots, folds): &Self::Input) -> PartResult<impl Display> {
+        let dots = folds
+            .iter()
+            .fold(dots.clone(), |dots, &along| fold(&dots, along));
+        Ok(ocr::read_points(dots))
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -70,7 +119,7 @@ mod tests {
 
     #[test]
     fn example1() {
-        let dots = read_input("example.txt");
+        let (dots, _) = read_input("example.txt").unwrap();
 
         let dots = fold_along_y(7, &dots);
         print_dots(&dots);
@@ -81,7 +130,7 @@ mod tests {
     }
     #[test]
     fn part1() {
-        let dots = read_input("input.txt");
+        let (dots, _) = read_input("input.txt").unwrap();
 
         let dots = fold_along_x(655, &dots);
         assert_eq!(dots.len(), 706);
@@ -89,7 +138,7 @@ mod tests {
 
     #[test]
     fn part2() {
-        let dots = read_input("input.txt");
+        let (dots, _) = read_input("input.txt").unwrap();
 
         let dots = fold_along_x(655, &dots);
         let dots = fold_along_y(447, &dots);
@@ -104,7 +153,20 @@ mod tests {
         let dots = fold_along_y(13, &dots);
         let dots = fold_along_y(6, &dots);
 
-        print_dots(&dots);
-        // prints out LRFJBJEH
+        assert_eq!(ocr::read_points(dots), "LRFJBJEH");
+    }
+
+    #[test]
+    fn bad_fold() {
+        assert_eq!(
+            parse_input("6,10\n0,14\n\nfold along y=7\nfold along x=5")
+                .unwrap()
+                .1,
+            [Fold::Y(7), Fold::X(5)]
+        );
+
+        let error = parse_input("6,10\n0,14\n\nfold along z=7").unwrap_err();
+        assert_eq!((error.line, error.column), (4, 12));
+        assert_eq!(error.expected, "fold");
     }
 }
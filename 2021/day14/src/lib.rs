@@ -1,25 +1,37 @@
-use std::collections::{HashMap, HashSet};
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
+use input::InputError;
+use parser::nom::{
+    bytes::complete::{tag, take_while1},
+    character::complete::satisfy,
+    combinator::map,
+    error::context,
+    sequence::{pair, separated_pair},
 };
+use parser::{blank_lines, lines, parse_all, ParseError, Parsed};
+use solution::{ParseResult, PartResult, Solution};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
 
-pub fn read_input(filename: &str) -> HashMap<(char, char), char> {
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
+type Rules = HashMap<(char, char), char>;
 
-    let mut rules = HashMap::new();
+pub fn read_input(filename: &str) -> Result<(String, Rules), InputError> {
+    input::parse!(filename, parse_input)
+}
 
-    reader.lines().for_each(|line| {
-        let line = line.unwrap();
-        let mut data = line.split(" -> ");
-        let mut from = data.next().unwrap().chars();
-        let to = data.next().unwrap().chars().next().unwrap();
+fn element(input: &str) -> Parsed<'_, char> {
+    context("element", satisfy(|c| c.is_ascii_uppercase()))(input)
+}
 
-        rules.insert((from.next().unwrap(), from.next().unwrap()), to);
-    });
+// "CH -> B"
+fn rule(input: &str) -> Parsed<'_, ((char, char), char)> {
+    separated_pair(pair(element, element), tag(" -> "), element)(input)
+}
 
-    rules
+// The polymer template, a blank line and then the pair insertion rules
+pub fn parse_input(contents: &str) -> Result<(String, Rules), ParseError> {
+    let template = take_while1(|c: char| c.is_ascii_uppercase());
+    let template = context("polymer template", map(template, String::from));
+    let rules = map(lines(rule), |rules| rules.into_iter().collect());
+    parse_all(contents, separated_pair(template, blank_lines, rules))
 }
 
 pub fn grow(polymer: String, pair_insertion_rules: &HashMap<(char, char), char>) -> String {
@@ -36,7 +48,7 @@ pub fn grow(polymer: String, pair_insertion_rules: &HashMap<(char, char), char>)
         // new_string.push(*second);
     }
 
-    new_string.push(polymer.chars().into_iter().rev().next().unwrap());
+    new_string.push(polymer.chars().next_back().unwrap());
 
     new_string
 }
@@ -101,7 +113,7 @@ pub fn get_frequencies(polymer: &HashMap<(char, char), usize>) -> HashMap<char,
     let mut firsts = HashMap::new();
     let mut seconds = HashMap::new();
 
-    for (_i, (k, v)) in polymer.iter().enumerate() {
+    for (k, v) in polymer.iter() {
         let e = firsts.entry(k.0).or_insert(0);
         *e += v;
 
@@ -120,6 +132,31 @@ pub fn get_frequencies(polymer: &HashMap<(char, char), usize>) -> HashMap<char,
     freqs
 }
 
+// Most common element count less the least common
+pub fn score(frequencies: &HashMap<char, usize>) -> usize {
+    frequencies.values().max().unwrap() - frequencies.values().min().unwrap()
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = (String, HashMap<(char, char), char>);
+
+    fn parse(input: &str) -> ParseResult<Self::Input> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1((template, rules): &Self::Input) -> PartResult<impl Display> {
+        let polymer = (0..10).fold(polymer2hashy(template), |p, _| grow2(&p, rules));
+        Ok(score(&get_frequencies(&polymer)))
+    }
+
+    fn part2((template, rules): &Self::Input) -> PartResult<impl Display> {
+        let polymer = (0..40).fold(polymer2hashy(template), |p, _| grow2(&p, rules));
+        Ok(score(&get_frequencies(&polymer)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -127,7 +164,7 @@ mod tests {
 
     #[test]
     fn example1() {
-        let pair_insertion_rules = read_input("example.txt");
+        let (_, pair_insertion_rules) = read_input("example.txt").unwrap();
         let polymer_template = "NNCB".to_string();
 
         // Step 1
@@ -165,7 +202,7 @@ mod tests {
 
     #[test]
     fn part1() {
-        let pair_insertion_rules = read_input("input.txt");
+        let (_, pair_insertion_rules) = read_input("input.txt").unwrap();
         let polymer_template = "KHSSCSKKCPFKPPBBOKVF".to_string();
 
         let mut poly_10 = grow(polymer_template, &pair_insertion_rules);
@@ -179,10 +216,10 @@ mod tests {
 
     #[test]
     fn example2() {
-        let pair_insertion_rules = read_input("example.txt");
+        let (_, pair_insertion_rules) = read_input("example.txt").unwrap();
         let polymer_template = "NNCB";
 
-        let mut polymer = polymer2hashy(&polymer_template);
+        let mut polymer = polymer2hashy(polymer_template);
 
         // step 1
         polymer = grow2(&polymer, &pair_insertion_rules);
@@ -196,7 +233,7 @@ mod tests {
         polymer = grow2(&polymer, &pair_insertion_rules);
         assert_eq!(&polymer, &polymer2hashy("NBBBCNCCNBBNBNBBCHBHHBCHB"));
 
-        let mut poly_10 = polymer2hashy(&polymer_template);
+        let mut poly_10 = polymer2hashy(polymer_template);
         for _ in 0..10 {
             poly_10 = grow2(&poly_10, &pair_insertion_rules);
         }
@@ -211,7 +248,7 @@ mod tests {
 
         assert_eq!(score, 1588);
 
-        let mut poly_40 = polymer2hashy(&polymer_template);
+        let mut poly_40 = polymer2hashy(polymer_template);
         for _ in 0..40 {
             poly_40 = grow2(&poly_40, &pair_insertion_rules);
         }
@@ -230,10 +267,10 @@ mod tests {
 
     #[test]
     fn part2() {
-        let pair_insertion_rules = read_input("input.txt");
+        let (_, pair_insertion_rules) = read_input("input.txt").unwrap();
         let polymer_template = "KHSSCSKKCPFKPPBBOKVF";
 
-        let mut polymer = polymer2hashy(&polymer_template);
+        let mut polymer = polymer2hashy(polymer_template);
         for _ in 0..40 {
             polymer = grow2(&polymer, &pair_insertion_rules);
         }
@@ -249,4 +286,14 @@ mod tests {
 
         assert_eq!(score, 12271437788530)
     }
+
+    #[test]
+    fn bad_rule() {
+        let error = parse_input("NNCB\n\nCH -> B\nHh -> N").unwrap_err();
+        assert_eq!((error.line, error.column), (4, 2));
+        assert_eq!(error.expected, "element");
+
+        let error = parse_input("NNCB\nCH -> B").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+    }
 }
@@ -1,6 +1,14 @@
+use parser::nom::{
+    bytes::complete::tag,
+    combinator::map,
+    sequence::{preceded, separated_pair},
+};
+use parser::{integer, parse_all, ParseError, Parsed};
+use solution::{ParseResult, PartResult, Solution};
 use std::cmp::Ordering;
+use std::fmt::Display;
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct Target {
     xmin: i32,
     xmax: i32,
@@ -84,6 +92,45 @@ pub fn find_shots(target: &Target) -> i32 {
     hits
 }
 
+// "94..151"
+fn range(input: &str) -> Parsed<'_, (i32, i32)> {
+    separated_pair(integer, tag(".."), integer)(input)
+}
+
+// "target area: x=94..151, y=-156..-103"
+pub fn parse_target(input: &str) -> Result<Target, ParseError> {
+    let x = preceded(tag("target area: x="), range);
+    let y = preceded(tag("y="), range);
+    let target = separated_pair(x, tag(", "), y);
+    parse_all(
+        input,
+        map(target, |((xmin, xmax), (ymin, ymax))| Target {
+            xmin,
+            xmax,
+            ymin,
+            ymax,
+        }),
+    )
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input = Target;
+
+    fn parse(input: &str) -> ParseResult<Target> {
+        Ok(parse_target(input)?)
+    }
+
+    fn part1(target: &Target) -> PartResult<impl Display> {
+        Ok(find_best_shot(target).2)
+    }
+
+    fn part2(target: &Target) -> PartResult<impl Display> {
+        Ok(find_shots(target))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -153,4 +200,20 @@ mod tests {
         //4664 low
         //13008 high
     }
+
+    #[test]
+    fn bad_target() {
+        assert_eq!(
+            parse_target("target area: x=20..30, y=-10..-5\n").unwrap(),
+            Target {
+                xmin: 20,
+                xmax: 30,
+                ymin: -10,
+                ymax: -5,
+            }
+        );
+
+        let error = parse_target("target area: x=20..30, y=-10..").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 31));
+    }
 }
@@ -1,39 +1,34 @@
+use input::InputError;
+use parser::nom::{
+    bytes::complete::tag,
+    character::complete::{char, digit1, line_ending},
+    sequence::{delimited, pair, preceded, tuple},
+};
+use parser::{blocks, integer, lines, parse_all, ParseError, Parsed};
+use solution::{ParseResult, PartResult, Solution};
 use std::collections::{HashMap, VecDeque};
 use std::f32::consts::PI;
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-};
+use std::fmt::Display;
 
 type Scanner = Vec<(i32, i32, i32)>;
 
-pub fn read_input(filename: &str) -> Vec<Scanner> {
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
-
-    let mut nodes = Vec::new();
-    let mut scanner = Scanner::new();
-    for line in reader.lines() {
-        let line = line.unwrap();
-        if line.contains("--- scanner") {
-            scanner.clear();
-            continue;
-        } else if line.is_empty() {
-            nodes.push(scanner.clone());
-            continue;
-        }
-
-        let mut entries = line.split(',');
-        scanner.push((
-            entries.next().unwrap().parse().unwrap(),
-            entries.next().unwrap().parse().unwrap(),
-            entries.next().unwrap().parse().unwrap(),
-        ));
-    }
+pub fn read_input(filename: &str) -> Result<Vec<Scanner>, InputError> {
+    input::parse!(filename, parse_input)
+}
 
-    nodes.push(scanner.clone());
+// "--- scanner 0 ---" and then one "x,y,z" beacon per line
+fn scanner(input: &str) -> Parsed<'_, Scanner> {
+    let header = delimited(tag("--- scanner "), digit1, tag(" ---"));
+    let beacon = tuple((
+        integer,
+        preceded(char(','), integer),
+        preceded(char(','), integer),
+    ));
+    preceded(pair(header, line_ending), lines(beacon))(input)
+}
 
-    nodes
+pub fn parse_input(contents: &str) -> Result<Vec<Scanner>, ParseError> {
+    parse_all(contents, blocks(scanner))
 }
 
 pub fn rot_x(input: &Scanner, rot: f32) -> Scanner {
@@ -118,12 +113,10 @@ pub fn find_translations(
     (max, offset)
 }
 
-pub fn construct_map(
-    scanners: &[Scanner],
-) -> (
-    HashMap<(i32, i32, i32), i32 /* observed*/>,
-    Vec<(i32, i32, i32)>,
-) {
+// Each beacon with how many scanners observed it, and each scanner's position
+type BeaconMap = HashMap<(i32, i32, i32), i32>;
+
+pub fn construct_map(scanners: &[Scanner]) -> (BeaconMap, Vec<(i32, i32, i32)>) {
     let mut scanners: VecDeque<&Scanner> = scanners.iter().collect();
     let mut beacon_map = HashMap::new();
 
@@ -168,7 +161,7 @@ pub fn construct_map(
 }
 
 pub fn max_offset(offsets: &[(i32, i32, i32)]) -> i32 {
-    let a = offsets.clone();
+    let a = offsets;
     let b = offsets;
 
     let mut max_distance = 0;
@@ -182,6 +175,24 @@ pub fn max_offset(offsets: &[(i32, i32, i32)]) -> i32 {
     max_distance
 }
 
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input = (BeaconMap, Vec<(i32, i32, i32)>);
+
+    fn parse(input: &str) -> ParseResult<Self::Input> {
+        Ok(construct_map(&parse_input(input)?))
+    }
+
+    fn part1((map, _): &Self::Input) -> PartResult<impl Display> {
+        Ok(map.len())
+    }
+
+    fn part2((_, offsets): &Self::Input) -> PartResult<impl Display> {
+        Ok(max_offset(offsets))
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -205,21 +216,21 @@ mod tests {
 
     #[test]
     fn example1() {
-        let scanners = read_input("example.txt");
+        let scanners = read_input("example.txt").unwrap();
         let (map, _) = construct_map(&scanners);
         assert_eq!(map.len(), 79);
     }
 
     #[test]
     fn part1() {
-        let scanners = read_input("input.txt");
+        let scanners = read_input("input.txt").unwrap();
         let (map, _) = construct_map(&scanners);
         assert_eq!(map.len(), 449);
     }
 
     #[test]
     fn example2() {
-        let scanners = read_input("example.txt");
+        let scanners = read_input("example.txt").unwrap();
         let (_, offsets) = construct_map(&scanners);
 
         assert_eq!(max_offset(&offsets), 3621);
@@ -227,9 +238,19 @@ mod tests {
 
     #[test]
     fn part2() {
-        let scanners = read_input("input.txt");
+        let scanners = read_input("input.txt").unwrap();
         let (_, offsets) = construct_map(&scanners);
 
         assert_eq!(max_offset(&offsets), 13128);
     }
+
+    #[test]
+    fn bad_scanner() {
+        let error = parse_input("--- scanner 0 ---\n404,-588,-901\n528,-643\n").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 9));
+
+        let error =
+            parse_input("--- scanner 0 ---\n404,-588,-901\n\n--- scaner 1 ---").unwrap_err();
+        assert_eq!((error.line, error.column), (4, 1));
+    }
 }
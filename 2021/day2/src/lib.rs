@@ -1,72 +1,95 @@
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
+use input::InputError;
+use parser::nom::{
+    branch::alt, bytes::complete::tag, character::complete::char, error::context,
+    sequence::separated_pair,
 };
+use parser::{integer, lines, parse_all, ParseError, Parsed};
+use solution::{ParseResult, PartResult, Solution};
+use std::fmt::Display;
+
+// "forward 5"
+fn command(input: &str) -> Parsed<'_, (&str, i32)> {
+    let keyword = context("command", alt((tag("forward"), tag("down"), tag("up"))));
+    separated_pair(keyword, char(' '), integer)(input)
+}
 
 // Parses input and returns relative motion
-pub fn read_input(filename: &str) -> Vec<(i32 /*Horizontal*/, i32 /*Depth*/)> {
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
-
-    reader
-        .lines()
-        .map(|line| {
-            let words = line.unwrap();
-            let mut i = words.split(' ').into_iter();
-            let keyword = i.next().unwrap();
-            let command = i.next().unwrap().parse::<i32>().unwrap();
-
-            match keyword {
-                "forward" => (command, 0),
-                "down" => (0, command),
-                "up" => (0, -command),
-                _ => panic!("invalid input!"),
-            }
+pub fn read_input(filename: &str) -> Result<Vec<(i32 /*Horizontal*/, i32 /*Depth*/)>, InputError> {
+    input::parse!(filename, parse_input)
+}
+
+pub fn parse_input(contents: &str) -> Result<Vec<(i32 /*Horizontal*/, i32 /*Depth*/)>, ParseError> {
+    let commands = parse_all(contents, lines(command))?;
+    Ok(commands
+        .into_iter()
+        .map(|(keyword, command)| match keyword {
+            "forward" => (command, 0),
+            "down" => (0, command),
+            _ => (0, -command),
         })
-        .collect()
+        .collect())
 }
 
 // Parses input and returns relative motion using aim definition
-pub fn read_input_with_aim(filename: &str) -> Vec<(i32 /*Horizontal*/, i32 /*Depth*/)> {
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
+pub fn read_input_with_aim(
+    filename: &str,
+) -> Result<Vec<(i32 /*Horizontal*/, i32 /*Depth*/)>, InputError> {
+    input::parse!(filename, parse_input_with_aim)
+}
 
+pub fn parse_input_with_aim(
+    contents: &str,
+) -> Result<Vec<(i32 /*Horizontal*/, i32 /*Depth*/)>, ParseError> {
+    let commands = parse_all(contents, lines(command))?;
     let mut aim = 0;
-    reader
-        .lines()
-        .map(|line| {
-            let words = line.unwrap();
-            let mut i = words.split(' ').into_iter();
-            let keyword = i.next().unwrap();
-            let command = i.next().unwrap().parse::<i32>().unwrap();
-            match keyword {
-                "forward" => (command, command * aim),
-                "down" => {
-                    aim += command;
-                    (0, 0)
-                }
-                "up" => {
-                    aim -= command;
-                    (0, 0)
-                }
-                _ => panic!("invalid input!"),
+    Ok(commands
+        .into_iter()
+        .map(|(keyword, command)| match keyword {
+            "forward" => (command, command * aim),
+            "down" => {
+                aim += command;
+                (0, 0)
+            }
+            _ => {
+                aim -= command;
+                (0, 0)
             }
         })
-        .collect()
+        .collect())
 }
 
-pub fn find_displacement(input: &Vec<(i32, i32)>) -> (i32, i32) {
+pub fn find_displacement(input: &[(i32, i32)]) -> (i32, i32) {
     input
         .iter()
         .fold((0, 0), |dist, coord| (dist.0 + coord.0, dist.1 + coord.1))
 }
 
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = (Vec<(i32, i32)>, Vec<(i32, i32)>);
+
+    fn parse(input: &str) -> ParseResult<Self::Input> {
+        Ok((parse_input(input)?, parse_input_with_aim(input)?))
+    }
+
+    fn part1((motion, _): &Self::Input) -> PartResult<impl Display> {
+        let (horizontal, depth) = find_displacement(motion);
+        Ok(horizontal * depth)
+    }
+
+    fn part2((_, motion): &Self::Input) -> PartResult<impl Display> {
+        let (horizontal, depth) = find_displacement(motion);
+        Ok(horizontal * depth)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     #[test]
     fn example1() {
-        let data = read_input("example1.txt");
+        let data = read_input("example1.txt").unwrap();
         let displacement = find_displacement(&data);
 
         assert_eq!(displacement.0 * displacement.1, 150);
@@ -74,23 +97,33 @@ mod tests {
 
     #[test]
     fn part1() {
-        let data = read_input("input.txt");
+        let data = read_input("input.txt").unwrap();
         let displacement = find_displacement(&data);
         assert_eq!(displacement.0 * displacement.1, 1692075);
     }
 
     #[test]
     fn example2() {
-        let data = read_input_with_aim("example1.txt");
+        let data = read_input_with_aim("example1.txt").unwrap();
         let displacement = find_displacement(&data);
         assert_eq!(displacement.0 * displacement.1, 900);
     }
 
     #[test]
     fn part2() {
-        let data = read_input_with_aim("input.txt");
+        let data = read_input_with_aim("input.txt").unwrap();
         let displacement = find_displacement(&data);
 
         assert_eq!(displacement.0 * displacement.1, 1749524700);
     }
+
+    #[test]
+    fn bad_command() {
+        let error = parse_input("forward 5\nbackward 5").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.expected, "command");
+
+        let error = parse_input_with_aim("forward 5\ndown five").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 6));
+    }
 }
@@ -1,108 +1,116 @@
-use std::collections::HashMap;
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
+use grid::Grid;
+use input::InputError;
+use parser::nom::{
+    branch::alt,
+    character::complete::char,
+    combinator::{map_res, value, verify},
+    error::context,
+    multi::many1,
+    sequence::separated_pair,
 };
+use parser::{blank_lines, lines, parse_all, ParseError, Parsed};
+use solution::{ParseResult, PartResult, Solution};
+use std::fmt::{self, Display};
+
+// The image is infinite, so past the edge of the grid every pixel is the
+// background.  That can flash on and off when the decoder lights a pixel with
+// nothing lit around it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Image {
+    pub pixels: Grid<bool>,
+    pub background: bool,
+}
 
-pub fn read_input(filename: &str) -> (String, HashMap<(i32, i32), char>) {
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
-    let mut decoder = String::new();
-    let mut pixels = HashMap::new();
-
-    for (y, line) in reader.lines().enumerate() {
-        let line = line.unwrap();
-        if y == 0 {
-            decoder = line.clone();
-        } else if y == 1 {
-            continue;
-        } else {
-            for (x, c) in line.chars().enumerate() {
-                pixels.insert((x as i32, y as i32 - 2), c);
-            }
-        }
+impl Image {
+    fn get(&self, x: isize, y: isize) -> bool {
+        *self.pixels.get_signed(x, y).unwrap_or(&self.background)
     }
+}
 
-    (decoder, pixels)
+impl Display for Image {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let pixels = self.pixels.map(|&lit| if lit { '#' } else { '.' });
+        write!(f, "{}", pixels)
+    }
 }
 
-pub fn get_limits(pixels: &HashMap<(i32, i32), char>) -> (i32, i32) {
-    let mut max_x = 0;
-    let mut max_y = 0;
+pub fn read_input(filename: &str) -> Result<(Vec<bool>, Image), InputError> {
+    input::parse!(filename, parse_input)
+}
 
-    for (x, y) in pixels.keys() {
-        max_x = max_x.max(*x);
-        max_y = max_y.max(*y);
-    }
+// true where it's lit
+fn pixel(input: &str) -> Parsed<'_, bool> {
+    alt((value(true, char('#')), value(false, char('.'))))(input)
+}
 
-    (max_x, max_y)
+// The 512 pixel decoder, a blank line and then the image
+pub fn parse_input(contents: &str) -> Result<(Vec<bool>, Image), ParseError> {
+    let decoder = verify(many1(pixel), |decoder: &Vec<bool>| decoder.len() == 512);
+    let decoder = context("512 pixel decoder", decoder);
+    let image = context(
+        "rectangular image",
+        map_res(lines(many1(pixel)), Grid::from_rows),
+    );
+    let (decoder, pixels) = parse_all(contents, separated_pair(decoder, blank_lines, image))?;
+
+    let image = Image {
+        pixels,
+        background: false,
+    };
+    Ok((decoder, image))
 }
 
-fn get_pixel(pixels: &HashMap<(i32, i32), char>, x: i32, y: i32) -> char {
-    if pixels.contains_key(&(x, y)) {
-        *pixels.get(&(x, y)).unwrap()
+// The 3x3 square around a pixel, read as a binary number from the top left
+fn decoder_index(image: &Image, x: isize, y: isize) -> usize {
+    (-1..=1)
+        .flat_map(|dy| (-1..=1).map(move |dx| (dx, dy)))
+        .fold(0, |index, (dx, dy)| {
+            index << 1 | image.get(x + dx, y + dy) as usize
+        })
+}
+
+// Every pixel that can change is within one of the old image, so it grows by
+// one all round
+pub fn enhance(image: &Image, decoder: &[bool]) -> Image {
+    let width = image.pixels.width() + 2;
+    let height = image.pixels.height() + 2;
+    let pixels = Grid::from_fn(width, height, |(x, y)| {
+        decoder[decoder_index(image, x as isize - 1, y as isize - 1)]
+    });
+
+    let background = if image.background {
+        decoder[511]
     } else {
-        '.'
-    }
+        decoder[0]
+    };
+    Image { pixels, background }
 }
 
-fn get_decoder_word(pixels: &HashMap<(i32, i32), char>, x: i32, y: i32) -> String {
-    let mut word = String::new();
-    word.push(get_pixel(pixels, x - 1, y - 1));
-    word.push(get_pixel(pixels, x, y - 1));
-    word.push(get_pixel(pixels, x + 1, y - 1));
-    word.push(get_pixel(pixels, x - 1, y));
-    word.push(get_pixel(pixels, x, y));
-    word.push(get_pixel(pixels, x + 1, y));
-    word.push(get_pixel(pixels, x - 1, y + 1));
-    word.push(get_pixel(pixels, x, y + 1));
-    word.push(get_pixel(pixels, x + 1, y + 1));
-    word
+pub fn enhance_times(image: &Image, decoder: &[bool], times: usize) -> Image {
+    (0..times).fold(image.clone(), |image, _| enhance(&image, decoder))
 }
 
-pub fn decode(decoder_word: &str, decoder: &str) -> char {
-    let index = decoder_word
-        .chars()
-        .rev()
-        .enumerate()
-        .fold(0, |index, (i, c)| {
-            if c == '#' {
-                index + 2u32.pow(i as u32)
-            } else {
-                index
-            }
-        });
-
-    decoder.chars().nth(index as usize).unwrap()
+pub fn count_lit(image: &Image) -> usize {
+    assert!(!image.background, "Infinitely many pixels are lit");
+    image.pixels.values().filter(|&&lit| lit).count()
 }
 
-pub fn enhance(
-    pixels: &HashMap<(i32, i32), char>,
-    decoder: &str,
-    min_x: i32,
-    max_x: i32,
-    min_y: i32,
-    max_y: i32,
-) -> HashMap<(i32, i32), char> {
-    let mut new_pixels = HashMap::new();
-    for x in min_x..max_x {
-        for y in min_y..max_y {
-            let decoder_word = get_decoder_word(pixels, x, y);
-            let decoded_pixel = decode(&decoder_word, decoder);
-            new_pixels.insert((x, y), decoded_pixel);
-        }
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Input = (Vec<bool>, Image);
+
+    fn parse(input: &str) -> ParseResult<Self::Input> {
+        Ok(parse_input(input)?)
     }
-    new_pixels
-}
 
-pub fn print(pixels: &HashMap<(i32, i32), char>) {
-    for y in -55..155 {
-        for x in -55..155 {
-            print!("{}", get_pixel(pixels, x, y));
-        }
-        println!();
+    fn part1((decoder, image): &Self::Input) -> PartResult<impl Display> {
+        Ok(count_lit(&enhance_times(image, decoder, 2)))
+    }
+
+    fn part2((decoder, image): &Self::Input) -> PartResult<impl Display> {
+        Ok(count_lit(&enhance_times(image, decoder, 50)))
     }
-    println!();
 }
 
 #[cfg(test)]
@@ -112,84 +120,61 @@ mod tests {
 
     #[test]
     fn example1() {
-        let (decoder, pixels) = read_input("example.txt");
-
-        let (x, y) = get_limits(&pixels);
-
-        let mut iter = 2;
-        let new_pixels = enhance(&pixels, &decoder, -iter, x + iter, -iter, y + iter);
-        assert_eq!(24, new_pixels.iter().filter(|(_, &x)| { x == '#' }).count());
+        let (decoder, image) = read_input("example.txt").unwrap();
 
-        iter = 4;
-        let new_pixels = enhance(&new_pixels, &decoder, -iter, x + iter, -iter, y + iter);
-        assert_eq!(35, new_pixels.iter().filter(|(_, &x)| { x == '#' }).count());
+        let image = enhance(&image, &decoder);
+        assert_eq!(24, count_lit(&image));
+        assert_eq!(
+            image.to_string(),
+            ".##.##.
+#..#.#.
+##.#..#
+####..#
+.#..##.
+..##..#
+...#.#."
+        );
+
+        let image = enhance(&image, &decoder);
+        assert_eq!(35, count_lit(&image));
     }
 
     #[test]
     fn part1() {
-        let (decoder, pixels) = read_input("input.txt");
-
-        let (x, y) = get_limits(&pixels);
-
-        let mut iter = 10; // need to handle the stupid "flashing" problem, solve far out, trim back on the next flash
-        let new_pixels = enhance(&pixels, &decoder, -iter, x + iter, -iter, y + iter);
+        let (decoder, image) = read_input("input.txt").unwrap();
 
-        iter = 3;
-        let new_pixels = enhance(&new_pixels, &decoder, -iter, x + iter, -iter, y + iter);
+        // the background flashes, so it's only dark after an even number of steps
+        let image = enhance(&image, &decoder);
+        assert!(image.background);
+        let image = enhance(&image, &decoder);
+        assert!(!image.background);
 
-        assert_eq!(
-            5479,
-            new_pixels.iter().filter(|(_, &x)| { x == '#' }).count()
-        )
+        assert_eq!(5479, count_lit(&image));
     }
 
     #[test]
     fn example2() {
-        let (decoder, mut pixels) = read_input("example.txt");
-
-        let (x, y) = get_limits(&pixels);
-
-        for i in 0..49 {
-            let buffer = 5;
-            pixels = enhance(
-                &pixels,
-                &decoder,
-                -i - buffer,
-                x + i + buffer,
-                -i - buffer,
-                y + i + buffer,
-            );
-        }
-        let i = 51; // trim to proper space
-
-        pixels = enhance(&pixels, &decoder, -i, x + i, -i, y + i);
-
-        assert_eq!(3351, pixels.iter().filter(|(_, &x)| { x == '#' }).count());
+        let (decoder, image) = read_input("example.txt").unwrap();
+
+        assert_eq!(3351, count_lit(&enhance_times(&image, &decoder, 50)));
     }
 
     #[test]
     fn part2() {
-        let (decoder, mut pixels) = read_input("input.txt");
-
-        let (x, y) = get_limits(&pixels);
-
-        for _ in 0..49 {
-            let i = 0;
-            // run 49 times with "buffer"
-            let buffer = 110;
-            pixels = enhance(
-                &pixels,
-                &decoder,
-                -i - buffer,
-                x + i + buffer,
-                -i - buffer,
-                y + i + buffer,
-            );
-        }
-        let i = 51; // trim to proper space
-
-        pixels = enhance(&pixels, &decoder, -i, x + i, -i, y + i);
-
-        assert_eq!(19012, pixels.iter().filter(|(_, &x)| { x == '#' }).count());
+        let (decoder, image) = read_input("input.txt").unwrap();
+
+        assert_eq!(19012, count_lit(&enhance_times(&image, &decoder, 50)));
+    }
+
+    #[test]
+    fn bad_image() {
+        let error = parse_input("..#.#..#####\n\n#..#.\n#....").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 1));
+        assert_eq!(error.expected, "512 pixel decoder");
+
+        let decoder = ".".repeat(512);
+        let error = parse_input(&format!("{}\n\n#..#.\n#...", decoder)).unwrap_err();
+        assert_eq!((error.line, error.column), (3, 1));
+        assert_eq!(error.expected, "rectangular image");
     }
 }
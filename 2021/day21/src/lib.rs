@@ -1,4 +1,14 @@
 use memoize::memoize;
+use parser::nom::{
+    bytes::complete::tag,
+    character::complete::{digit1, line_ending},
+    combinator::verify,
+    error::context,
+    sequence::{delimited, preceded, separated_pair},
+};
+use parser::{integer, parse_all, ParseError, Parsed};
+use solution::{ParseResult, PartResult, Solution};
+use std::fmt::Display;
 
 #[derive(Debug)]
 pub struct Player {
@@ -61,6 +71,67 @@ pub fn calculate_2(p1: u32, p2: u32, p1_score: u32, p2_score: u32, win_score: u3
     }
     num_wins
 }
+// "Player 1 starting position: 2"
+// "Player 1 starting position: 4"
+fn starting_position(input: &str) -> Parsed<'_, u32> {
+    let player = delimited(tag("Player "), digit1, tag(" starting position: "));
+    let position = verify(integer, |position| (1..=10).contains(position));
+    preceded(player, context("position on the track", position))(input)
+}
+
+pub fn parse_positions(input: &str) -> Result<(u32, u32), ParseError> {
+    let positions = separated_pair(starting_position, line_ending, starting_position);
+    parse_all(input, positions)
+}
+
+// The loser's score times the number of rolls
+pub fn play_deterministic(p1: u32, p2: u32) -> u32 {
+    let mut players = [
+        Player {
+            score: 0,
+            position: p1,
+        },
+        Player {
+            score: 0,
+            position: p2,
+        },
+    ];
+    let mut die = 0;
+    let mut rolls = 0;
+
+    for turn in 0.. {
+        let player = &mut players[turn % 2];
+        let distance = (0..3)
+            .map(|_| roll_deterministic(&mut die, &mut rolls))
+            .sum();
+        player.position = move_distance(player.position, distance);
+        player.score += player.position;
+        if player.win() {
+            return players[(turn + 1) % 2].score * rolls;
+        }
+    }
+    unreachable!()
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Input = (u32, u32);
+
+    fn parse(input: &str) -> ParseResult<(u32, u32)> {
+        Ok(parse_positions(input)?)
+    }
+
+    fn part1(&(p1, p2): &(u32, u32)) -> PartResult<impl Display> {
+        Ok(play_deterministic(p1, p2))
+    }
+
+    fn part2(&(p1, p2): &(u32, u32)) -> PartResult<impl Display> {
+        let (p1_wins, p2_wins) = calculate_2(p1, p2, 0, 0, 21);
+        Ok(p1_wins.max(p2_wins))
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -187,4 +258,16 @@ mod tests {
         assert_eq!(133029050096658, p1_wins);
         assert_eq!(74014892331523, p2_wins);
     }
+
+    #[test]
+    fn bad_positions() {
+        let positions = "Player 1 starting position: 4\nPlayer 2 starting position: 8\n";
+        assert_eq!(parse_positions(positions).unwrap(), (4, 8));
+
+        let error =
+            parse_positions("Player 1 starting position: 4\nPlayer 2 starting position: 11")
+                .unwrap_err();
+        assert_eq!((error.line, error.column), (2, 29));
+        assert_eq!(error.expected, "position on the track");
+    }
 }
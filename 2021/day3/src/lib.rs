@@ -1,13 +1,16 @@
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-};
+use grid::{Grid, GridError};
+use input::InputError;
+use solution::{ParseResult, PartResult, Solution};
+use std::fmt::Display;
 
-pub fn read_input(filename: &str) -> Vec<String> {
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
+pub fn read_input(filename: &str) -> Result<Vec<String>, InputError> {
+    input::parse!(filename, parse_input)
+}
 
-    reader.lines().map(|x| x.unwrap()).collect()
+// Every number has to be the same width for the columns to line up
+pub fn parse_input(contents: &str) -> Result<Vec<String>, GridError> {
+    let bits = Grid::try_parse(contents, |c| matches!(c, '0' | '1').then(|| c))?;
+    Ok(bits.rows().map(|row| row.iter().collect()).collect())
 }
 
 pub fn most_common(input: &[String]) -> Vec<bool> {
@@ -44,8 +47,8 @@ pub fn invert_mask(mask: &[bool]) -> Vec<bool> {
     mask.iter().map(|x| !x).collect()
 }
 
-pub fn filter_mask_common(input: &Vec<String>) -> Vec<bool> {
-    let mut input = input.clone();
+pub fn filter_mask_common(input: &[String]) -> Vec<bool> {
+    let mut input = input.to_vec();
 
     // for each column
     for idx in 0..input.len() {
@@ -53,7 +56,7 @@ pub fn filter_mask_common(input: &Vec<String>) -> Vec<bool> {
             break;
         }
         let common = most_common(&input);
-        let key = if common[idx] == true { '1' } else { '0' };
+        let key = if common[idx] { '1' } else { '0' };
 
         let mut new_input = Vec::new();
         for i in input {
@@ -75,8 +78,8 @@ pub fn filter_mask_common(input: &Vec<String>) -> Vec<bool> {
         .collect()
 }
 
-pub fn filter_mask_uncommon(input: &Vec<String>) -> Vec<bool> {
-    let mut input = input.clone();
+pub fn filter_mask_uncommon(input: &[String]) -> Vec<bool> {
+    let mut input = input.to_vec();
 
     // for each column
     for idx in 0..input.len() {
@@ -84,7 +87,7 @@ pub fn filter_mask_uncommon(input: &Vec<String>) -> Vec<bool> {
             break;
         }
         let common = most_common(&input);
-        let key = if common[idx] == true { '0' } else { '1' }; // simple hack to flip common->uncommon
+        let key = if common[idx] { '0' } else { '1' }; // simple hack to flip common->uncommon
 
         let mut new_input = Vec::new();
         for i in input {
@@ -106,12 +109,31 @@ pub fn filter_mask_uncommon(input: &Vec<String>) -> Vec<bool> {
         .collect()
 }
 
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> ParseResult<Vec<String>> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(input: &Vec<String>) -> PartResult<impl Display> {
+        let gamma_mask = most_common(input);
+        Ok(mask_to_int(&gamma_mask) * mask_to_int(&invert_mask(&gamma_mask)))
+    }
+
+    fn part2(input: &Vec<String>) -> PartResult<impl Display> {
+        Ok(mask_to_int(&filter_mask_common(input)) * mask_to_int(&filter_mask_uncommon(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     #[test]
     fn example1() {
-        let input = read_input("example.txt");
+        let input = read_input("example.txt").unwrap();
 
         let gamma_mask = most_common(&input);
         let epsilon_mask = invert_mask(&gamma_mask);
@@ -126,7 +148,7 @@ mod tests {
 
     #[test]
     fn part1() {
-        let input = read_input("input.txt");
+        let input = read_input("input.txt").unwrap();
 
         let gamma_mask = most_common(&input);
         let epsilon_mask = invert_mask(&gamma_mask);
@@ -141,7 +163,7 @@ mod tests {
 
     #[test]
     fn example2() {
-        let input = read_input("example.txt");
+        let input = read_input("example.txt").unwrap();
 
         let oxygen_mask = filter_mask_common(&input);
         let oxygen = mask_to_int(&oxygen_mask);
@@ -156,7 +178,7 @@ mod tests {
 
     #[test]
     fn part2() {
-        let input = read_input("input.txt");
+        let input = read_input("input.txt").unwrap();
 
         let oxygen_mask = filter_mask_common(&input);
         let oxygen = mask_to_int(&oxygen_mask);
@@ -168,4 +190,24 @@ mod tests {
 
         println!("{}", oxygen * co2);
     }
+
+    #[test]
+    fn bad_report() {
+        assert_eq!(
+            parse_input("00100\n11110\n1011\n").unwrap_err(),
+            GridError::Ragged {
+                line: 3,
+                width: 4,
+                expected: 5
+            }
+        );
+        assert_eq!(
+            parse_input("00100\n11120").unwrap_err(),
+            GridError::BadCell {
+                line: 2,
+                column: 4,
+                found: '2'
+            }
+        );
+    }
 }
@@ -1,7 +1,14 @@
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
+use input::InputError;
+use parser::nom::{
+    character::complete::{space0, space1},
+    combinator::{map, verify},
+    error::context,
+    multi::separated_list1,
+    sequence::{preceded, separated_pair},
 };
+use parser::{blank_lines, blocks, integer, integers, lines, parse_all, ParseError, Parsed};
+use solution::{ParseResult, PartResult, Solution};
+use std::fmt::Display;
 
 // type Board = [[i32; 5]; 5];
 
@@ -17,7 +24,7 @@ type Moves = Vec<i32>;
 pub fn check_rows(board: &Board) -> bool {
     'row: for row in board {
         for entry in row {
-            if entry.0 == false {
+            if !entry.0 {
                 continue 'row;
             }
         }
@@ -32,7 +39,7 @@ pub fn check_rows(board: &Board) -> bool {
 pub fn check_cols(board: &Board) -> bool {
     'col: for idx in 0..5 {
         for row in board {
-            if row[idx].0 == false {
+            if !row[idx].0 {
                 continue 'col;
             }
         }
@@ -44,41 +51,33 @@ pub fn check_cols(board: &Board) -> bool {
     false
 }
 
-pub fn read_input(filename: &str) -> (Moves, Vec<Board>) {
-    let file = File::open(filename).unwrap();
-    let mut reader = BufReader::new(file);
-
-    let mut line = String::new();
-    reader.read_line(&mut line).unwrap();
-
-    // strip off the trailing newline
-    let line = line.replace('\n', "");
-
-    let moves: Moves = line.split(',').map(|s| s.parse().unwrap()).collect();
-
-    let mut boards = Vec::new();
-    // let mut idx = 0;
-    let mut tmp_board: Board = Vec::new();
+pub fn read_input(filename: &str) -> Result<(Moves, Vec<Board>), InputError> {
+    input::parse!(filename, parse_input)
+}
 
-    // consume that emptyline so that we dont create an empty board
-    reader.consume(1);
+// " 8  2 23  4 24", as nothing is marked yet
+fn row(input: &str) -> Parsed<'_, Row> {
+    let numbers = preceded(space0, separated_list1(space1, integer));
+    let numbers = context(
+        "row of five numbers",
+        verify(numbers, |row: &Vec<i32>| row.len() == 5),
+    );
+    map(numbers, |row| row.into_iter().map(|n| (false, n)).collect())(input)
+}
 
-    for line in reader.lines() {
-        let line = line.unwrap();
-        if line.len() > 1 {
-            let row: Row = line
-                .split_ascii_whitespace()
-                .map(|s| (false, s.parse().unwrap()))
-                .collect();
-            tmp_board.push(row);
-        } else {
-            boards.push(tmp_board.clone());
-            tmp_board.clear();
-        }
-    }
-    boards.push(tmp_board.clone());
+fn board(input: &str) -> Parsed<'_, Board> {
+    context(
+        "five rows",
+        verify(lines(row), |board: &Board| board.len() == 5),
+    )(input)
+}
 
-    (moves, boards)
+// The moves, a blank line and then the boards with blank lines between them
+pub fn parse_input(contents: &str) -> Result<(Moves, Vec<Board>), ParseError> {
+    parse_all(
+        contents,
+        separated_pair(integers(","), blank_lines, blocks(board)),
+    )
 }
 
 pub fn mark_boards(number: i32, boards: &Vec<Board>) -> Vec<Board> {
@@ -87,7 +86,7 @@ pub fn mark_boards(number: i32, boards: &Vec<Board>) -> Vec<Board> {
     for board in boards {
         let mut new_board = board.clone();
         for row in &mut new_board {
-            for mut entry in row {
+            for entry in row {
                 if entry.1 == number {
                     entry.0 = true;
                 }
@@ -121,6 +120,38 @@ pub fn print_board(board: &Board) {
     }
 }
 
+// Scores of the boards in the order they win
+pub fn play(moves: &Moves, boards: &[Board]) -> Vec<i32> {
+    let mut boards = boards.to_vec();
+    let mut scores = Vec::new();
+    for &number in moves {
+        let (won, playing) = mark_boards(number, &boards)
+            .into_iter()
+            .partition::<Vec<_>, _>(|board| check_cols(board) || check_rows(board));
+        scores.extend(won.iter().map(|board| calculate_score(board, number)));
+        boards = playing;
+    }
+    scores
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = (Moves, Vec<Board>);
+
+    fn parse(input: &str) -> ParseResult<Self::Input> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1((moves, boards): &Self::Input) -> PartResult<impl Display> {
+        Ok(*play(moves, boards).first().ok_or("No board ever wins")?)
+    }
+
+    fn part2((moves, boards): &Self::Input) -> PartResult<impl Display> {
+        Ok(*play(moves, boards).last().ok_or("No board ever wins")?)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::process::exit;
@@ -128,16 +159,16 @@ mod tests {
     use super::*;
     #[test]
     fn example1() {
-        let (moves, mut boards) = read_input("example.txt");
+        let (moves, mut boards) = read_input("example.txt").unwrap();
 
         for number in moves {
             println!("Playing {}...", number);
             boards = mark_boards(number, &boards);
 
             for board in &boards {
-                if check_cols(&board) || check_rows(&board) {
+                if check_cols(board) || check_rows(board) {
                     println!("Number {} did it!", number);
-                    assert_eq!(4512, calculate_score(&board, number));
+                    assert_eq!(4512, calculate_score(board, number));
                     exit(0);
                 }
             }
@@ -146,16 +177,16 @@ mod tests {
 
     #[test]
     fn part1() {
-        let (moves, mut boards) = read_input("input.txt");
+        let (moves, mut boards) = read_input("input.txt").unwrap();
 
         for number in moves {
             println!("Playing {}...", number);
             boards = mark_boards(number, &boards);
 
             for board in &boards {
-                if check_cols(&board) || check_rows(&board) {
+                if check_cols(board) || check_rows(board) {
                     println!("Number {} did it!", number);
-                    assert_eq!(67716, calculate_score(&board, number));
+                    assert_eq!(67716, calculate_score(board, number));
                     exit(0);
                 }
             }
@@ -164,7 +195,7 @@ mod tests {
 
     #[test]
     fn example2() {
-        let (moves, mut boards) = read_input("example.txt");
+        let (moves, mut boards) = read_input("example.txt").unwrap();
 
         for number in moves {
             println!("Playing {}...", number);
@@ -172,8 +203,8 @@ mod tests {
 
             let mut new_boards = Vec::new();
             for board in &updated_boards {
-                if check_cols(&board) || check_rows(&board) {
-                    print_board(&board);
+                if check_cols(board) || check_rows(board) {
+                    print_board(board);
                     continue;
                 }
                 new_boards.push(board.clone());
@@ -181,7 +212,7 @@ mod tests {
             if updated_boards.len() == 1 {
                 assert_eq!(
                     1924,
-                    calculate_score(&updated_boards.first().unwrap(), number)
+                    calculate_score(updated_boards.first().unwrap(), number)
                 );
             }
             boards = new_boards;
@@ -190,7 +221,7 @@ mod tests {
 
     #[test]
     fn part2() {
-        let (moves, mut boards) = read_input("input.txt");
+        let (moves, mut boards) = read_input("input.txt").unwrap();
 
         for number in moves {
             println!("Playing {}...", number);
@@ -198,8 +229,8 @@ mod tests {
 
             let mut new_boards = Vec::new();
             for board in &updated_boards {
-                if check_cols(&board) || check_rows(&board) {
-                    print_board(&board);
+                if check_cols(board) || check_rows(board) {
+                    print_board(board);
                     continue;
                 }
                 new_boards.push(board.clone());
@@ -207,10 +238,22 @@ mod tests {
             if updated_boards.len() == 1 {
                 assert_eq!(
                     1830,
-                    calculate_score(&updated_boards.first().unwrap(), number)
+                    calculate_score(updated_boards.first().unwrap(), number)
                 );
             }
             boards = new_boards;
         }
     }
+
+    #[test]
+    fn bad_board() {
+        let board = "22 13 17 11  0\n 8  2 23  4 24\n21  9 14 16  7\n 6 10  3 18  5\n";
+        let error = parse_input(&format!("7,4,9\n\n{} 1 12 20 15", board)).unwrap_err();
+        assert_eq!((error.line, error.column), (7, 1));
+        assert_eq!(error.expected, "row of five numbers");
+
+        let error = parse_input(&format!("7,4,9\n\n{}", board)).unwrap_err();
+        assert_eq!((error.line, error.column), (3, 1));
+        assert_eq!(error.expected, "five rows");
+    }
 }
@@ -1,10 +1,11 @@
 use core::panic;
-use regex::Regex;
-use std::{
-    collections::HashMap,
-    fs::File,
-    io::{BufRead, BufReader},
+use input::InputError;
+use parser::nom::{
+    bytes::complete::tag, character::complete::char, combinator::map, sequence::separated_pair,
 };
+use parser::{integer, lines, parse_all, ParseError, Parsed};
+use solution::{ParseResult, PartResult, Solution};
+use std::{collections::HashMap, fmt::Display};
 
 pub type Point = (i32, i32);
 
@@ -14,31 +15,23 @@ pub struct Line {
     end: Point,
 }
 
-pub fn read_input(filename: &str) -> Vec<Line> {
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
-
-    let mut lines = Vec::new();
-    for line in reader.lines() {
-        let line = line.unwrap();
-
-        let re = Regex::new(r"^(\d+),(\d+) -> (\d+),(\d+)$").unwrap();
-        let c = re.captures(&line).unwrap();
-        let point1: Point = (
-            c.get(1).unwrap().as_str().parse().unwrap(),
-            c.get(2).unwrap().as_str().parse().unwrap(),
-        );
-        let point2: Point = (
-            c.get(3).unwrap().as_str().parse().unwrap(),
-            c.get(4).unwrap().as_str().parse().unwrap(),
-        );
-
-        lines.push(Line {
-            start: point1,
-            end: point2,
-        });
-    }
-    lines
+pub fn read_input(filename: &str) -> Result<Vec<Line>, InputError> {
+    input::parse!(filename, parse_input)
+}
+
+fn point(input: &str) -> Parsed<'_, Point> {
+    separated_pair(integer, char(','), integer)(input)
+}
+
+// "0,9 -> 5,9"
+fn line(input: &str) -> Parsed<'_, Line> {
+    map(separated_pair(point, tag(" -> "), point), |(start, end)| {
+        Line { start, end }
+    })(input)
+}
+
+pub fn parse_input(contents: &str) -> Result<Vec<Line>, ParseError> {
+    parse_all(contents, lines(line))
 }
 
 pub fn is_horizontal(line: &Line) -> bool {
@@ -114,13 +107,39 @@ pub fn count_overlaps(map: &HashMap<Point, i32 /* instances */>) -> i32 {
         .fold(0, |sum, (_k, v)| if v > &1 { sum + 1 } else { sum })
 }
 
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = Vec<Line>;
+
+    fn parse(input: &str) -> ParseResult<Vec<Line>> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(lines: &Vec<Line>) -> PartResult<impl Display> {
+        let lines: Vec<&Line> = lines
+            .iter()
+            .filter(|x| is_horizontal(x) || is_vertical(x))
+            .collect();
+        Ok(count_overlaps(&draw(&lines)))
+    }
+
+    fn part2(lines: &Vec<Line>) -> PartResult<impl Display> {
+        let lines: Vec<&Line> = lines
+            .iter()
+            .filter(|x| is_horizontal(x) || is_vertical(x) || is_diagonal(x))
+            .collect();
+        Ok(count_overlaps(&draw2(&lines)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn example1() {
-        let lines = read_input("example.txt");
+        let lines = read_input("example.txt").unwrap();
 
         let filtered_lines: Vec<&Line> = lines
             .iter()
@@ -136,7 +155,7 @@ mod tests {
 
     #[test]
     fn part1() {
-        let lines = read_input("input.txt");
+        let lines = read_input("input.txt").unwrap();
 
         let filtered_lines: Vec<&Line> = lines
             .iter()
@@ -152,7 +171,7 @@ mod tests {
 
     #[test]
     fn example2() {
-        let lines = read_input("example.txt");
+        let lines = read_input("example.txt").unwrap();
 
         let filtered_lines: Vec<&Line> = lines
             .iter()
@@ -168,7 +187,7 @@ mod tests {
 
     #[test]
     fn part2() {
-        let lines = read_input("input.txt");
+        let lines = read_input("input.txt").unwrap();
 
         let filtered_lines: Vec<&Line> = lines
             .iter()
@@ -181,4 +200,10 @@ mod tests {
 
         assert_eq!(overlaps, 22037)
     }
+
+    #[test]
+    fn bad_line() {
+        let error = parse_input("0,9 -> 5,9\n8,0 => 0,8").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 4));
+    }
 }
@@ -1,18 +1,14 @@
-use std::{
-    collections::HashMap,
-    fs::File,
-    io::{BufReader, Read},
-};
-
-pub fn read_input(filename: &str) -> Vec<usize> {
-    let file = File::open(filename).unwrap();
-    let mut reader = BufReader::new(file);
-    let mut data = String::new();
-
-    reader.read_to_string(&mut data).unwrap();
-    data.split(',')
-        .map(|x| x.parse::<usize>().unwrap())
-        .collect()
+use input::InputError;
+use parser::{integers, parse_all, ParseError};
+use solution::{ParseResult, PartResult, Solution};
+use std::{collections::HashMap, fmt::Display};
+
+pub fn read_input(filename: &str) -> Result<Vec<usize>, InputError> {
+    input::parse!(filename, parse_input)
+}
+
+pub fn parse_input(contents: &str) -> Result<Vec<usize>, ParseError> {
+    parse_all(contents, integers(","))
 }
 
 pub fn next_day(state: Vec<usize>) -> Vec<usize> {
@@ -81,13 +77,33 @@ pub fn count_size(state: &HashMap<usize /*timer*/, usize /*count*/>) -> usize {
     state.iter().fold(0, |sum, (_, count)| sum + count)
 }
 
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = Vec<usize>;
+
+    fn parse(input: &str) -> ParseResult<Vec<usize>> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(state: &Vec<usize>) -> PartResult<impl Display> {
+        let state = (0..80).fold(vec2hashy(state.clone()), |state, _| next_day3(state));
+        Ok(count_size(&state))
+    }
+
+    fn part2(state: &Vec<usize>) -> PartResult<impl Display> {
+        let state = (0..256).fold(vec2hashy(state.clone()), |state, _| next_day3(state));
+        Ok(count_size(&state))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn example1() {
-        let mut state = read_input("example.txt");
+        let mut state = read_input("example.txt").unwrap();
 
         for _ in 0..18 {
             let new_state = next_day(state);
@@ -105,7 +121,7 @@ mod tests {
 
     #[test]
     fn example1a() {
-        let vec_state = read_input("example.txt");
+        let vec_state = read_input("example.txt").unwrap();
 
         let mut state = vec2hashy(vec_state);
 
@@ -123,7 +139,7 @@ mod tests {
 
     #[test]
     fn part1() {
-        let mut state = read_input("input.txt");
+        let mut state = read_input("input.txt").unwrap();
 
         for _ in 0..80 {
             let new_state = next_day2(state);
@@ -134,7 +150,7 @@ mod tests {
 
     #[test]
     fn example2() {
-        let vec_state = read_input("example.txt");
+        let vec_state = read_input("example.txt").unwrap();
 
         let mut state = vec2hashy(vec_state);
         for _ in 0..256 {
@@ -146,7 +162,7 @@ mod tests {
 
     #[test]
     fn part2() {
-        let vec_state = read_input("input.txt");
+        let vec_state = read_input("input.txt").unwrap();
 
         let mut state = vec2hashy(vec_state);
         for _ in 0..256 {
@@ -155,4 +171,10 @@ mod tests {
         }
         assert_eq!(count_size(&state), 1682576647495);
     }
+
+    #[test]
+    fn bad_timers() {
+        let error = parse_input("3,4,3,1,,2").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 8));
+    }
 }
@@ -1,15 +1,14 @@
-use std::{
-    fs::File,
-    io::{BufReader, Read},
-};
-
-pub fn read_input(filename: &str) -> Vec<i32> {
-    let file = File::open(filename).unwrap();
-    let mut reader = BufReader::new(file);
-    let mut data = String::new();
-
-    reader.read_to_string(&mut data).unwrap();
-    data.split(',').map(|x| x.parse::<i32>().unwrap()).collect()
+use input::InputError;
+use parser::{integers, parse_all, ParseError};
+use solution::{ParseResult, PartResult, Solution};
+use std::fmt::Display;
+
+pub fn read_input(filename: &str) -> Result<Vec<i32>, InputError> {
+    input::parse!(filename, parse_input)
+}
+
+pub fn parse_input(contents: &str) -> Result<Vec<i32>, ParseError> {
+    parse_all(contents, integers(","))
 }
 
 pub fn align_to(positions: &[i32], desired_position: i32) -> i32 {
@@ -29,6 +28,35 @@ pub fn crab_align_to(positions: &[i32], desired_position: i32) -> i32 {
     })
 }
 
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Vec<i32>;
+
+    fn parse(input: &str) -> ParseResult<Vec<i32>> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(positions: &Vec<i32>) -> PartResult<impl Display> {
+        let (min, max) = (
+            positions.iter().min().unwrap(),
+            positions.iter().max().unwrap(),
+        );
+        Ok((*min..=*max).map(|x| align_to(positions, x)).min().unwrap())
+    }
+
+    fn part2(positions: &Vec<i32>) -> PartResult<impl Display> {
+        let (min, max) = (
+            positions.iter().min().unwrap(),
+            positions.iter().max().unwrap(),
+        );
+        Ok((*min..=*max)
+            .map(|x| crab_align_to(positions, x))
+            .min()
+            .unwrap())
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -36,7 +64,7 @@ mod tests {
 
     #[test]
     fn example1() {
-        let positions = read_input("example.txt");
+        let positions = read_input("example.txt").unwrap();
 
         assert_eq!(align_to(&positions, 2), 37); // given solutions
         assert_eq!(align_to(&positions, 1), 41); // given solutions
@@ -59,7 +87,7 @@ mod tests {
 
     #[test]
     fn part1() {
-        let positions = read_input("input.txt");
+        let positions = read_input("input.txt").unwrap();
 
         let mut min_target = i32::MIN;
         let mut min_cost = i32::MAX;
@@ -77,7 +105,7 @@ mod tests {
 
     #[test]
     fn example2() {
-        let positions = read_input("example.txt");
+        let positions = read_input("example.txt").unwrap();
 
         assert_eq!(crab_align_to(&positions, 5), 168); // given solutions
         assert_eq!(crab_align_to(&positions, 2), 206); // given solutions
@@ -98,7 +126,7 @@ mod tests {
 
     #[test]
     fn part2() {
-        let positions = read_input("input.txt");
+        let positions = read_input("input.txt").unwrap();
 
         let mut min_target = i32::MIN;
         let mut min_cost = i32::MAX;
@@ -113,4 +141,10 @@ mod tests {
         assert_eq!(min_target, 470);
         assert_eq!(min_cost, 93006301);
     }
+
+    #[test]
+    fn bad_positions() {
+        let error = parse_input("16,1,2,0;4,2").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 9));
+    }
 }
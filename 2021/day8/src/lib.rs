@@ -1,34 +1,42 @@
-use std::collections::HashSet;
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
+use input::InputError;
+use parser::nom::{
+    bytes::complete::{tag, take_while1},
+    character::complete::char,
+    combinator::{map, verify},
+    error::context,
+    multi::{count, separated_list1},
+    sequence::{separated_pair, terminated},
 };
+use parser::{lines, parse_all, ParseError, Parsed};
+use solution::{ParseResult, PartResult, Solution};
+use std::collections::HashSet;
+use std::fmt::Display;
 
-pub fn read_input(filename: &str) -> Vec<(Vec<String>, Vec<String>)> {
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
-
-    let mut lines = Vec::new();
-    reader.lines().for_each(|line| {
-        let line = line.unwrap();
-        let segments = line.replace("| ", "");
-        let segments = segments.split(' ');
+type Entry = (Vec<String>, Vec<String>);
 
-        let words: Vec<&str> = segments.collect();
+pub fn read_input(filename: &str) -> Result<Vec<Entry>, InputError> {
+    input::parse!(filename, parse_input)
+}
 
-        let signal_patterns: Vec<String> = words.iter().take(10).map(|x| x.to_string()).collect();
-        let digits: Vec<String> = words
-            .iter()
-            .rev()
-            .take(4)
-            .rev()
-            .map(|x| x.to_string())
-            .collect();
+// The wires lit up for one digit, like "cfbegad"
+fn segments(input: &str) -> Parsed<'_, String> {
+    let wires = take_while1(|c| ('a'..='g').contains(&c));
+    context("segments", map(wires, String::from))(input)
+}
 
-        lines.push((signal_patterns, digits));
-    });
+// Ten signal patterns, then " | " and the four output digits
+fn entry(input: &str) -> Parsed<'_, Entry> {
+    let patterns = count(terminated(segments, char(' ')), 10);
+    let digits = separated_list1(char(' '), segments);
+    let digits = context(
+        "four digits",
+        verify(digits, |digits: &Vec<String>| digits.len() == 4),
+    );
+    separated_pair(patterns, tag("| "), digits)(input)
+}
 
-    lines
+pub fn parse_input(contents: &str) -> Result<Vec<Entry>, ParseError> {
+    parse_all(contents, lines(entry))
 }
 
 pub fn maybe1478(input: &str) -> bool {
@@ -174,6 +182,31 @@ pub fn decode_score(decode: Vec<String>, coded_score: Vec<String>) -> i32 {
         + get_digit(&decode, &coded_score[3])
 }
 
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = Vec<(Vec<String>, Vec<String>)>;
+
+    fn parse(input: &str) -> ParseResult<Self::Input> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(entries: &Self::Input) -> PartResult<impl Display> {
+        Ok(entries
+            .iter()
+            .flat_map(|(_, values)| values)
+            .filter(|value| maybe1478(value))
+            .count())
+    }
+
+    fn part2(entries: &Self::Input) -> PartResult<impl Display> {
+        Ok(entries
+            .iter()
+            .map(|(data, score)| decode_score(decode(data.clone()), score.clone()))
+            .sum::<i32>())
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -181,7 +214,7 @@ mod tests {
 
     #[test]
     fn example1() {
-        let data = read_input("example.txt");
+        let data = read_input("example.txt").unwrap();
 
         let mut count = 0;
         for (_, values) in data {
@@ -196,7 +229,7 @@ mod tests {
 
     #[test]
     fn part1() {
-        let data = read_input("input.txt");
+        let data = read_input("input.txt").unwrap();
 
         let mut count = 0;
         for (_, values) in data {
@@ -239,7 +272,7 @@ mod tests {
 
     #[test]
     fn example2b() {
-        let input = read_input("example.txt");
+        let input = read_input("example.txt").unwrap();
         let mut sum = 0;
 
         for (data, score) in input {
@@ -251,7 +284,7 @@ mod tests {
 
     #[test]
     fn part2() {
-        let input = read_input("input.txt");
+        let input = read_input("input.txt").unwrap();
         let mut sum = 0;
 
         for (data, score) in input {
@@ -260,4 +293,15 @@ mod tests {
         }
         assert_eq!(sum, 1027422)
     }
+
+    #[test]
+    fn bad_entry() {
+        let patterns = "be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb";
+        let error = parse_input(&format!("{} | fdgacbe cefdb cefbgd", patterns)).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 62));
+        assert_eq!(error.expected, "four digits");
+
+        let error = parse_input("be cfbegad | fdgacbe cefdb cefbgd gcbe").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 12));
+    }
 }
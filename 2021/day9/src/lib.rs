@@ -1,124 +1,72 @@
-use std::{
-    collections::{HashSet, VecDeque},
-    fs::File,
-    io::{BufRead, BufReader},
-};
-
-pub fn read_input(filename: &str) -> Vec<Vec<u32>> {
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
-
-    reader
-        .lines()
-        .map(|x| {
-            x.unwrap()
-                .chars()
-                .map(|c| c.to_digit(10).unwrap())
-                .collect()
-        })
-        .collect()
-}
-
-pub fn is_low(depth_map: &[Vec<u32>], x: usize, y: usize) -> bool {
-    let max_x = depth_map[0].len() - 1;
-    let max_y = depth_map.len() - 1;
-
-    let point = depth_map[y][x];
+use grid::{Grid, GridError};
+use input::InputError;
+use solution::{ParseResult, PartResult, Solution};
+use std::fmt::Display;
 
-    if x > 0 && depth_map[y][x - 1] <= point {
-        return false;
-    }
+pub fn read_input(filename: &str) -> Result<Grid<u32>, InputError> {
+    input::parse!(filename, parse_input)
+}
 
-    if x < max_x && depth_map[y][x + 1] <= point {
-        return false;
-    }
+pub fn parse_input(contents: &str) -> Result<Grid<u32>, GridError> {
+    Grid::try_parse(contents, |c| c.to_digit(10))
+}
 
-    if y > 0 && depth_map[y - 1][x] <= point {
-        return false;
-    }
+pub fn is_low(depth_map: &Grid<u32>, x: usize, y: usize) -> bool {
+    let point = depth_map[(x, y)];
+    depth_map
+        .neighbours4((x, y))
+        .all(|neighbour| depth_map[neighbour] > point)
+}
 
-    if y < max_y && depth_map[y + 1][x] <= point {
-        return false;
-    }
-    true
+pub fn calculate_risk(depth_map: &Grid<u32>) -> u32 {
+    depth_map
+        .iter()
+        .filter(|&((x, y), _)| is_low(depth_map, x, y))
+        .map(|(_, depth)| 1 + depth)
+        .sum()
 }
 
-pub fn calculate_risk(depth_map: &[Vec<u32>]) -> u32 {
-    let mut total_risk = 0;
-    let width = depth_map[0].len();
-    let height = depth_map.len();
+pub fn basin_area(depth_map: &Grid<u32>, x: usize, y: usize) -> u32 {
+    // expand uphill from the low point, stopping at the 9s between basins
+    let uphill = |&point: &(usize, usize)| {
+        depth_map
+            .neighbours4(point)
+            .filter(|&neighbour| {
+                let depth = depth_map[neighbour];
+                depth > depth_map[point] && depth != 9
+            })
+            .collect::<Vec<_>>()
+    };
+    search::flood_fill([(x, y)], uphill).visited as u32
+}
 
-    for x in 0..width {
-        for y in 0..height {
-            if is_low(depth_map, x, y) {
-                total_risk += 1 + depth_map[y][x];
-            }
-        }
-    }
+pub fn find_basins(depth_map: &Grid<u32>) -> u32 {
+    let mut basins: Vec<u32> = depth_map
+        .points()
+        .filter(|&(x, y)| is_low(depth_map, x, y))
+        .map(|(x, y)| basin_area(depth_map, x, y))
+        .collect();
+    basins.sort_unstable();
 
-    total_risk
+    basins.iter().rev().take(3).product()
 }
 
-pub fn basin_area(depth_map: &[Vec<u32>], x: usize, y: usize) -> u32 {
-    let max_x = depth_map[0].len() - 1;
-    let max_y = depth_map.len() - 1;
-
-    let mut area = 0;
-    let mut explored = HashSet::new();
-    let mut queue = VecDeque::new();
-
-    // seed the search
-    queue.push_back((x, y));
-
-    while !queue.is_empty() {
-        // pop new point
-        let (x, y) = queue.pop_front().unwrap();
-
-        // mark it explored
-        if explored.contains(&(x, y)) {
-            continue;
-        } else {
-            explored.insert((x, y));
-        }
-
-        // accumulate
-        area += 1;
-
-        // expand
-        if x < max_x && depth_map[y][x + 1] > depth_map[y][x] && depth_map[y][x + 1] != 9 {
-            queue.push_back((x + 1, y));
-        }
-        if x > 0 && depth_map[y][x - 1] > depth_map[y][x] && depth_map[y][x - 1] != 9 {
-            queue.push_back((x - 1, y));
-        }
-        if y < max_y && depth_map[y + 1][x] > depth_map[y][x] && depth_map[y + 1][x] != 9 {
-            queue.push_back((x, y + 1));
-        }
-        if y > 0 && depth_map[y - 1][x] > depth_map[y][x] && depth_map[y - 1][x] != 9 {
-            queue.push_back((x, y - 1));
-        }
-    }
+pub struct Day9;
 
-    area
-}
+impl Solution for Day9 {
+    type Input = Grid<u32>;
 
-pub fn find_basins(depth_map: &[Vec<u32>]) -> u32 {
-    let mut basins = Vec::new();
-    let width = depth_map[0].len();
-    let height = depth_map.len();
+    fn parse(input: &str) -> ParseResult<Self::Input> {
+        Ok(parse_input(input)?)
+    }
 
-    for x in 0..width {
-        for y in 0..height {
-            if is_low(depth_map, x, y) {
-                basins.push(basin_area(depth_map, x, y));
-            }
-        }
+    fn part1(depth_map: &Self::Input) -> PartResult<impl Display> {
+        Ok(calculate_risk(depth_map))
     }
-    basins.sort_unstable();
 
-    basins.iter().rev().next().unwrap()
-        * basins.iter().rev().nth(1).unwrap()
-        * basins.iter().rev().nth(2).unwrap()
+    fn part2(depth_map: &Self::Input) -> PartResult<impl Display> {
+        Ok(find_basins(depth_map))
+    }
 }
 
 #[cfg(test)]
@@ -128,7 +76,7 @@ mod tests {
 
     #[test]
     fn example1() {
-        let depth_map = read_input("example.txt");
+        let depth_map = read_input("example.txt").unwrap();
 
         let this = is_low(&depth_map, 1, 0);
         assert!(this); // must be true
@@ -147,7 +95,7 @@ mod tests {
     }
     #[test]
     fn part1() {
-        let depth_map = read_input("input.txt");
+        let depth_map = read_input("input.txt").unwrap();
 
         let risk = calculate_risk(&depth_map);
         assert_eq!(risk, 560);
@@ -155,7 +103,7 @@ mod tests {
 
     #[test]
     fn example2() {
-        let depth_map = read_input("example.txt");
+        let depth_map = read_input("example.txt").unwrap();
 
         let this = basin_area(&depth_map, 1, 0);
         assert_eq!(this, 3);
@@ -175,9 +123,21 @@ mod tests {
 
     #[test]
     fn part2() {
-        let depth_map = read_input("input.txt");
+        let depth_map = read_input("input.txt").unwrap();
 
         let basins = find_basins(&depth_map);
         assert_eq!(basins, 959136);
     }
+
+    #[test]
+    fn bad_heights() {
+        assert_eq!(
+            parse_input("2199943210\n3987894921\n98567x9892").unwrap_err(),
+            GridError::BadCell {
+                line: 3,
+                column: 6,
+                found: 'x'
+            }
+        );
+    }
 }
@@ -1,27 +1,15 @@
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-};
+use input::InputError;
+use parser::{blocks, integer, lines, parse_all, ParseError};
+use solution::{ParseResult, PartResult, Solution};
+use std::fmt::Display;
 
-pub fn read_input(filename: &str) -> Vec<Vec<i32>> {
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
-
-    let mut elves = Vec::new();
-
-    let mut elf = Vec::new();
-    for line in reader.lines() {
-        let line = line.unwrap();
-        if line.is_empty() {
-            elves.push(elf);
-            elf = Vec::new();
-        } else {
-            elf.push(line.parse::<i32>().unwrap());
-        }
-    }
-    elves.push(elf);
+pub fn read_input(filename: &str) -> Result<Vec<Vec<i32>>, InputError> {
+    input::parse!(filename, parse_input)
+}
 
-    elves
+// Each elf's calories, with a blank line between elves
+pub fn parse_input(contents: &str) -> Result<Vec<Vec<i32>>, ParseError> {
+    parse_all(contents, blocks(lines(integer)))
 }
 
 pub fn find_top(bags: Vec<Vec<i32>>) -> i32 {
@@ -37,30 +25,57 @@ pub fn find_top3(bags: Vec<Vec<i32>>) -> i32 {
     values[0] + values[1] + values[2]
 }
 
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<Vec<i32>>;
+
+    fn parse(input: &str) -> ParseResult<Self::Input> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(bags: &Self::Input) -> PartResult<impl Display> {
+        Ok(find_top(bags.clone()))
+    }
+
+    fn part2(bags: &Self::Input) -> PartResult<impl Display> {
+        if bags.len() < 3 {
+            return Err("There aren't three elves to add up".into());
+        }
+        Ok(find_top3(bags.clone()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     #[test]
     fn example1() {
-        let data = read_input("example1.txt");
+        let data = read_input("example1.txt").unwrap();
         assert_eq!(find_top(data), 24000);
     }
 
     #[test]
     fn part1() {
-        let data = read_input("input.txt");
+        let data = read_input("input.txt").unwrap();
         assert_eq!(find_top(data), 72718);
     }
 
     #[test]
     fn example2() {
-        let data = read_input("example1.txt");
+        let data = read_input("example1.txt").unwrap();
         assert_eq!(find_top3(data), 45000);
     }
 
     #[test]
     fn part2() {
-        let data = read_input("input.txt");
+        let data = read_input("input.txt").unwrap();
         assert_eq!(find_top3(data), 213089);
     }
+
+    #[test]
+    fn bad_calories() {
+        let error = parse_input("1000\n2000\n\n4000\n5O00").unwrap_err();
+        assert_eq!((error.line, error.column), (5, 2));
+    }
 }
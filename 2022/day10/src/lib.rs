@@ -1,32 +1,36 @@
-use std::{
-    collections::VecDeque,
-    fs::File,
-    io::{BufRead, BufReader},
+use input::InputError;
+use parser::nom::{
+    branch::alt,
+    bytes::complete::tag,
+    combinator::{map, value},
+    error::context,
+    sequence::preceded,
 };
+use parser::{integer, lines, parse_all, ParseError, Parsed};
+use solution::{ParseResult, PartResult, Solution};
+use std::{collections::VecDeque, fmt::Display};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Op {
     AddX(i32),
     NoOp,
 }
-pub fn read_input(filename: &str) -> Vec<Op> {
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
-
-    reader
-        .lines()
-        .map(|line| {
-            let line = line.unwrap();
-            if line.contains("noop") {
-                Op::NoOp
-            } else if line.contains("addx") {
-                let v: Vec<&str> = line.split(' ').collect();
-                Op::AddX(v[1].parse().unwrap())
-            } else {
-                panic!("arst");
-            }
-        })
-        .collect()
+pub fn read_input(filename: &str) -> Result<Vec<Op>, InputError> {
+    input::parse!(filename, parse_input)
+}
+
+fn op(input: &str) -> Parsed<'_, Op> {
+    context(
+        "noop or addx",
+        alt((
+            value(Op::NoOp, tag("noop")),
+            map(preceded(tag("addx "), integer), Op::AddX),
+        )),
+    )(input)
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<Op>, ParseError> {
+    parse_all(input, lines(op))
 }
 
 pub fn execute(ops: &[Op], cycles: i32) -> i32 {
@@ -64,13 +68,51 @@ pub fn execute(ops: &[Op], cycles: i32) -> i32 {
     x
 }
 
+// Which of the 40x6 CRT pixels the sprite lights up as it's drawn
+pub fn render_crt(ops: &[Op]) -> Vec<Vec<bool>> {
+    let mut screen = Vec::new();
+    let mut row_offset = 0;
+
+    for _ in 0..6 {
+        let mut row = Vec::new();
+        for c in 0..40 {
+            let sprite_position = execute(ops, c + row_offset + 1);
+            row.push(c == sprite_position - 1 || c == sprite_position || c == sprite_position + 1);
+        }
+        screen.push(row);
+        row_offset += 40;
+    }
+    screen
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Vec<Op>;
+
+    fn parse(input: &str) -> ParseResult<Vec<Op>> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(ops: &Vec<Op>) -> PartResult<impl Display> {
+        Ok([20, 60, 100, 140, 180, 220]
+            .iter()
+            .map(|&cycle| execute(ops, cycle) * cycle)
+            .sum::<i32>())
+    }
+
+    fn part2(ops: &Vec<Op>) -> PartResult<impl Display> {
+        Ok(ocr::read_grid(&render_crt(ops)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn example1_b() {
-        let ops = read_input("example2.txt");
+        let ops = read_input("example2.txt").unwrap();
         assert_eq!(execute(&ops, 20), 21);
         assert_eq!(execute(&ops, 60), 19);
         assert_eq!(execute(&ops, 100), 18);
@@ -81,7 +123,7 @@ mod tests {
 
     #[test]
     fn part1() {
-        let ops = read_input("input.txt");
+        let ops = read_input("input.txt").unwrap();
 
         let v = execute(&ops, 20) * 20
             + execute(&ops, 60) * 60
@@ -95,39 +137,39 @@ mod tests {
 
     #[test]
     fn example2() {
-        let ops = read_input("example2.txt");
-        let mut row_offset = 0;
-
-        for _ in 0..6 {
-            for c in 0..40 {
-                let sprite_position = execute(&ops, c + row_offset + 1);
-                if c == sprite_position - 1 || c == sprite_position || c == sprite_position + 1 {
-                    print!("#");
-                } else {
-                    print!(".");
-                }
-            }
-            row_offset += 40;
-            println!();
-        }
+        let ops = read_input("example2.txt").unwrap();
+        let screen = render_crt(&ops);
+
+        let rows: Vec<String> = screen
+            .iter()
+            .map(|row| row.iter().map(|&lit| if lit { '#' } else { '.' }).collect())
+            .collect();
+        assert_eq!(rows[0], "##..##..##..##..##..##..##..##..##..##..");
+        assert_eq!(rows[5], "#######.......#######.......#######.....");
+    }
+
+    #[test]
+    fn bad_instruction() {
+        let error = parse_input("noop\naddx 3\naddx x\nmulx 2").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 6));
+        assert_eq!(error.expected, "integer");
+
+        let error = parse_input("noop\nmulx 2").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 1: expected noop or addx, found \"mulx 2\""
+        );
+        assert_eq!(
+            Day10::parse("noop\nmulx 2").unwrap_err().to_string(),
+            error.to_string()
+        );
     }
 
     #[test]
     fn part2() {
-        let ops = read_input("input.txt");
-        let mut row_offset = 0;
-
-        for _ in 0..6 {
-            for c in 0..40 {
-                let sprite_position = execute(&ops, c + row_offset + 1);
-                if c == sprite_position - 1 || c == sprite_position || c == sprite_position + 1 {
-                    print!("#");
-                } else {
-                    print!(".");
-                }
-            }
-            row_offset += 40;
-            println!();
-        }
+        let ops = read_input("input.txt").unwrap();
+        let screen = render_crt(&ops);
+
+        assert_eq!(ocr::read_grid(&screen), "ZKGRKGRK");
     }
 }
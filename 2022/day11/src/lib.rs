@@ -1,3 +1,16 @@
+use parser::nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{char, digit1, line_ending, one_of, space1},
+    combinator::{map, value, verify},
+    error::context,
+    multi::separated_list1,
+    sequence::{pair, preceded, terminated, tuple},
+};
+use parser::{blocks, integer, parse_all, Parsed};
+use solution::{ParseResult, PartResult, Solution};
+use std::fmt::Display;
+
 type Type = dyn Fn(u64, Vec<Vec<u64>>) -> Vec<Vec<u64>>;
 
 pub struct Monkey {
@@ -5,7 +18,7 @@ pub struct Monkey {
     test: Box<Type>,
 }
 
-pub fn eval(items: Vec<Vec<u64>>, monkeys: Vec<Monkey>) -> u64 {
+pub fn eval(items: Vec<Vec<u64>>, monkeys: &[Monkey]) -> u64 {
     let mut items = items;
     let mut activity = vec![0; items.len()];
 
@@ -26,7 +39,7 @@ pub fn eval(items: Vec<Vec<u64>>, monkeys: Vec<Monkey>) -> u64 {
     activity[0] * activity[1]
 }
 
-pub fn ridiculous_eval(items: Vec<Vec<u64>>, monkeys: Vec<Monkey>, gcf: u64) -> u64 {
+pub fn ridiculous_eval(items: Vec<Vec<u64>>, monkeys: &[Monkey], gcf: u64) -> u64 {
     let mut items = items;
     let mut activity = vec![0; items.len()];
 
@@ -47,6 +60,106 @@ pub fn ridiculous_eval(items: Vec<Vec<u64>>, monkeys: Vec<Monkey>, gcf: u64) ->
     activity[0] * activity[1]
 }
 
+// What a monkey was written down as: its items, "*" or "+" with a number or
+// None for "old", the divisor and who it throws to either way
+type Notes = (Vec<u64>, (char, Option<u64>), u64, usize, usize);
+
+// One "  Label: value" line of a monkey's notes
+fn note<'a, T>(
+    label: &'static str,
+    value: impl FnMut(&'a str) -> Parsed<'a, T>,
+) -> impl FnMut(&'a str) -> Parsed<'a, T> {
+    preceded(pair(space1, tag(label)), value)
+}
+
+fn monkey(input: &str) -> Parsed<'_, Notes> {
+    let header = tuple((tag("Monkey "), digit1, char(':'), line_ending));
+    let items = separated_list1(tag(", "), integer);
+    let operand = alt((value(None, tag("old")), map(integer, Some)));
+    let operation = context(
+        "operation",
+        pair(one_of("*+"), preceded(char(' '), operand)),
+    );
+    let divisor = context("divisor", verify(integer, |&divisor: &u64| divisor > 0));
+    preceded(
+        header,
+        tuple((
+            terminated(note("Starting items: ", items), line_ending),
+            terminated(note("Operation: new = old ", operation), line_ending),
+            terminated(note("Test: divisible by ", divisor), line_ending),
+            terminated(note("If true: throw to monkey ", integer), line_ending),
+            note("If false: throw to monkey ", integer),
+        )),
+    )(input)
+}
+
+// The starting items, in the order they're inspected from the back, the monkeys
+// and the product of their divisors
+pub fn parse_monkeys(input: &str) -> ParseResult<(Vec<Vec<u64>>, Vec<Monkey>, u64)> {
+    let notes = parse_all(input, blocks(monkey))?;
+
+    let mut items = Vec::new();
+    let mut monkeys = Vec::new();
+    let mut gcf = 1;
+
+    for (idx, (mut held, operation, divisor, if_true, if_false)) in
+        notes.iter().cloned().enumerate()
+    {
+        if let Some(target) = [if_true, if_false]
+            .into_iter()
+            .find(|&t| t >= notes.len() || t == idx)
+        {
+            return Err(format!("Monkey {} can't throw to monkey {}", idx, target).into());
+        }
+
+        held.reverse();
+        items.push(held);
+
+        let op: Box<dyn Fn(&u64) -> u64> = match operation {
+            ('*', None) => Box::new(|old| old * old),
+            ('*', Some(v)) => Box::new(move |old| old * v),
+            (_, None) => Box::new(|old| old + old),
+            (_, Some(v)) => Box::new(move |old| old + v),
+        };
+        gcf *= divisor;
+
+        monkeys.push(Monkey {
+            op,
+            test: Box::new(move |v, mut items| {
+                if v % divisor == 0 {
+                    items[if_true].insert(0, v)
+                } else {
+                    items[if_false].insert(0, v)
+                }
+                items
+            }),
+        });
+    }
+
+    Ok((items, monkeys, gcf))
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = (Vec<Vec<u64>>, Vec<Monkey>, u64);
+
+    fn parse(input: &str) -> ParseResult<(Vec<Vec<u64>>, Vec<Monkey>, u64)> {
+        parse_monkeys(input)
+    }
+
+    // The monkeys are only looked at, so each part just needs its own items
+    fn part1((items, monkeys, _): &(Vec<Vec<u64>>, Vec<Monkey>, u64)) -> PartResult<impl Display> {
+        Ok(eval(items.clone(), monkeys))
+    }
+
+    fn part2(
+        (items, monkeys, gcf): &(Vec<Vec<u64>>, Vec<Monkey>, u64),
+    ) -> PartResult<impl Display> {
+        Ok(ridiculous_eval(items.clone(), monkeys, *gcf))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -202,7 +315,7 @@ mod tests {
         ];
 
         let monkeys = make_test_monkeys();
-        assert_eq!(eval(items, monkeys), 10605);
+        assert_eq!(eval(items, &monkeys), 10605);
     }
 
     #[test]
@@ -219,7 +332,7 @@ mod tests {
         ];
 
         let monkeys = make_real_monkeys();
-        assert_eq!(eval(items, monkeys), 50172);
+        assert_eq!(eval(items, &monkeys), 50172);
     }
 
     #[test]
@@ -233,7 +346,7 @@ mod tests {
 
         let monkeys = make_test_monkeys();
         assert_eq!(
-            ridiculous_eval(items, monkeys, 19 * 23 * 13 * 17),
+            ridiculous_eval(items, &monkeys, 19 * 23 * 13 * 17),
             2713310158
         );
     }
@@ -253,8 +366,43 @@ mod tests {
 
         let monkeys = make_real_monkeys();
         assert_eq!(
-            ridiculous_eval(items, monkeys, 17 * 2 * 5 * 3 * 7 * 13 * 19 * 11),
+            ridiculous_eval(items, &monkeys, 17 * 2 * 5 * 3 * 7 * 13 * 19 * 11),
             11614682178
         );
     }
+
+    #[test]
+    fn parsed_monkeys() {
+        let notes = "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 1
+    If false: throw to monkey 1
+
+Monkey 1:
+  Starting items: 54, 65, 75, 74
+  Operation: new = old * old
+  Test: divisible by 19
+    If true: throw to monkey 0
+    If false: throw to monkey 0
+";
+        let (items, monkeys, gcf) = parse_monkeys(notes).unwrap();
+        assert_eq!(items, [vec![98, 79], vec![74, 75, 65, 54]]);
+        assert_eq!(gcf, 23 * 19);
+        assert_eq!((monkeys[1].op)(&7), 49);
+
+        let error = parse_monkeys(&notes.replace("old * 19", "old / 19"))
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "line 3, column 24: expected operation, found \"/ 19\""
+        );
+
+        let error = parse_monkeys(&notes.replace("monkey 0", "monkey 2"))
+            .err()
+            .unwrap();
+        assert_eq!(error.to_string(), "Monkey 1 can't throw to monkey 2");
+    }
 }
@@ -1,157 +1,64 @@
-use std::{
-    collections::{HashMap, HashSet, VecDeque},
-    fs::File,
-    io::{BufRead, BufReader},
-};
+use grid::Grid;
+use input::InputError;
+use solution::{ParseResult, PartResult, Solution};
+use std::fmt::Display;
 
-type Map = HashMap<(usize, usize), u8>;
+type Map = Grid<u8>;
+type Position = (usize, usize);
 
 //
 // Read in map a-z => 97-122
 //
-pub fn read_input(filename: &str) -> (Map, (usize, usize), (usize, usize)) {
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
-
-    let mut map = HashMap::new();
-    let mut start = (0, 0);
-    let mut finish = (0, 0);
-    for (y, line) in reader.lines().enumerate() {
-        let line = line.unwrap();
-        for (x, c) in line.as_bytes().iter().enumerate() {
-            if c == &83 {
-                // "S"
-                map.insert((x, y), 97); // "a"
-                start = (x, y);
-            } else if c == &69 {
-                // "E"
-                map.insert((x, y), 122); //"z"
-                finish = (x, y);
-            } else {
-                map.insert((x, y), *c);
-            }
-        }
-    }
-    (map, start, finish)
+pub fn read_input(filename: &str) -> Result<(Map, Position, Position), InputError> {
+    input::parse!(filename, parse_input)
 }
 
-fn get_actions(map: &Map, pos: &(usize, usize)) -> Vec<(usize, usize)> {
-    let mut actions = Vec::new();
-    let h = map[pos];
-    let mut dirs = vec![(pos.0, pos.1 + 1), (pos.0 + 1, pos.1)];
-    if pos.0 > 0 {
-        dirs.push((pos.0 - 1, pos.1));
-    }
-    if pos.1 > 0 {
-        dirs.push((pos.0, pos.1 - 1));
-    }
-
-    for d in dirs {
-        if let Some(p) = map.get(&d) {
-            if p <= &(h + 1) {
-                actions.push(d);
-            }
-        }
-    }
+pub fn parse_input(text: &str) -> ParseResult<(Map, Position, Position)> {
+    let mut map = Grid::try_parse(text, |c| {
+        matches!(c, 'a'..='z' | 'S' | 'E').then(|| c as u8)
+    })?;
+
+    let start = map.position(|&c| c == b'S').ok_or("There's no start S")?;
+    let finish = map
+        .position(|&c| c == b'E')
+        .ok_or("There's no best signal E")?;
+    map[start] = b'a';
+    map[finish] = b'z';
+    Ok((map, start, finish))
+}
 
-    actions
+// Up at most one step
+fn get_actions(map: &Map, pos: &Position) -> Vec<Position> {
+    let h = map[*pos];
+    map.neighbours4(*pos).filter(|&p| map[p] <= h + 1).collect()
 }
-fn get_actions_descent(map: &Map, pos: &(usize, usize)) -> Vec<(usize, usize)> {
-    let mut actions = Vec::new();
-    let h = map[pos];
-    let mut dirs = vec![(pos.0, pos.1 + 1), (pos.0 + 1, pos.1)];
-    if pos.0 > 0 {
-        dirs.push((pos.0 - 1, pos.1));
-    }
-    if pos.1 > 0 {
-        dirs.push((pos.0, pos.1 - 1));
-    }
 
-    for d in dirs {
-        if let Some(p) = map.get(&d) {
-            if p >= &(h - 1) {
-                actions.push(d);
-            }
-        }
-    }
+pub fn explore(map: &Map, start: Position, finish: Position) -> Option<usize> {
+    search::bfs([start], |pos| get_actions(map, pos), |&pos| pos == finish).goal_cost()
+}
 
-    actions
+// Setting out from every square at the lowest elevation at once
+pub fn explore_from_lowest(map: &Map, finish: Position) -> Option<usize> {
+    let starts = map.iter().filter(|&(_, &h)| h == b'a').map(|(p, _)| p);
+    search::bfs(starts, |pos| get_actions(map, pos), |&pos| pos == finish).goal_cost()
 }
 
-pub fn explore(map: &Map, start: (usize, usize), finish: (usize, usize)) -> usize {
-    let mut queue = VecDeque::new();
-    queue.push_back((vec![start], 0));
-    let mut visited = HashSet::new();
-
-    loop {
-        if queue.is_empty() {
-            break;
-        }
-
-        let (path, _) = queue.pop_front().unwrap();
-        let pos = path.last().unwrap();
-
-        if visited.contains(pos) {
-            continue;
-        }
-        visited.insert(*pos);
-        let actions = get_actions(map, pos);
-
-        for action in actions {
-            if action == finish {
-                return path.len();
-            }
-            if path.contains(&action) {
-                continue;
-            }
-
-            let mut new_path = path.clone();
-            new_path.push(action);
-            let cost = path.len();
-
-            let idx = queue.partition_point(|&(_, x)| x < cost);
-            queue.insert(idx, (new_path, cost));
-        }
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = (Map, (usize, usize), (usize, usize));
+
+    fn parse(input: &str) -> ParseResult<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1((map, start, finish): &Self::Input) -> PartResult<impl Display> {
+        Ok(explore(map, *start, *finish).ok_or("No path to the best signal")?)
     }
-    0
-}
 
-pub fn descend(map: &Map, start: (usize, usize)) -> usize {
-    let mut queue = VecDeque::new();
-    queue.push_back((vec![start], 0));
-    let mut visited = HashSet::new();
-
-    loop {
-        if queue.is_empty() {
-            break;
-        }
-
-        let (path, _) = queue.pop_front().unwrap();
-        let pos = path.last().unwrap();
-
-        if visited.contains(pos) {
-            continue;
-        }
-        visited.insert(*pos);
-        let actions = get_actions_descent(map, pos);
-
-        for action in actions {
-            if map[&action] == b'a' {
-                return path.len();
-            }
-            if path.contains(&action) {
-                continue;
-            }
-
-            let mut new_path = path.clone();
-            new_path.push(action);
-            let cost = path.len();
-
-            let idx = queue.partition_point(|&(_, x)| x < cost);
-            queue.insert(idx, (new_path, cost));
-        }
+    fn part2((map, _, finish): &Self::Input) -> PartResult<impl Display> {
+        Ok(explore_from_lowest(map, *finish).ok_or("No path to the best signal")?)
     }
-    0
 }
 
 #[cfg(test)]
@@ -160,33 +67,42 @@ mod tests {
 
     #[test]
     fn example1() {
-        let (map, start, finish) = read_input("example.txt");
+        let (map, start, finish) = read_input("example.txt").unwrap();
         assert_eq!(start, (0, 0));
         assert_eq!(finish, (5, 2));
-        assert_eq!(map[&start], 'a' as u8);
-        assert_eq!(map[&finish], 'z' as u8);
+        assert_eq!(map[start], b'a');
+        assert_eq!(map[finish], b'z');
 
-        assert_eq!(explore(&map, start, finish), 31);
+        assert_eq!(explore(&map, start, finish), Some(31));
     }
 
     #[test]
     fn part1() {
-        let (map, start, finish) = read_input("input.txt");
+        let (map, start, finish) = read_input("input.txt").unwrap();
 
-        assert_eq!(explore(&map, start, finish), 528);
+        assert_eq!(explore(&map, start, finish), Some(528));
     }
 
     #[test]
     fn example2() {
-        let (map, _, finish) = read_input("example.txt");
+        let (map, _, finish) = read_input("example.txt").unwrap();
 
-        assert_eq!(descend(&map, finish), 29);
+        assert_eq!(explore_from_lowest(&map, finish), Some(29));
     }
 
     #[test]
     fn part2() {
-        let (map, _, finish) = read_input("input.txt");
+        let (map, _, finish) = read_input("input.txt").unwrap();
+
+        assert_eq!(explore_from_lowest(&map, finish), Some(522));
+    }
+
+    #[test]
+    fn bad_map() {
+        let error = parse_input("Sabqponm\nabcryxxl\naccsz#xk").unwrap_err();
+        assert_eq!(error.to_string(), "line 3, column 6: '#' isn't a grid cell");
 
-        assert_eq!(descend(&map, finish), 522);
+        let error = parse_input("Sabqponm\nabcryxxl").unwrap_err();
+        assert_eq!(error.to_string(), "There's no best signal E");
     }
 }
@@ -1,9 +1,16 @@
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
+use input::InputError;
+use parser::nom::{
+    branch::alt,
+    character::complete::{char, one_of},
+    combinator::value,
+    error::context,
+    sequence::separated_pair,
 };
+use parser::{lines, parse_all, ParseError, Parsed};
+use solution::{ParseResult, PartResult, Solution};
+use std::fmt::Display;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 enum Choice {
     Rock,
     Paper,
@@ -26,68 +33,68 @@ pub struct Round {
 use crate::Choice::*;
 use crate::Goal::*;
 
-pub fn read_input(filename: &str) -> Vec<Round> {
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
-
-    let mut rounds = Vec::new();
-    for line in reader.lines() {
-        let line = line.unwrap();
-        let moves: Vec<&str> = line.split(' ').collect();
-        let opponent = match moves[0] {
-            "A" => Rock,
-            "B" => Paper,
-            "C" => Scissor,
-            _ => panic!("arst"),
-        };
-        let you = match moves[1] {
-            "X" => Rock,
-            "Y" => Paper,
-            "Z" => Scissor,
-            _ => panic!("arst"),
-        };
-        rounds.push(Round { opponent, you });
-    }
+pub fn read_input(filename: &str) -> Result<Vec<Round>, InputError> {
+    input::parse!(filename, parse_input)
+}
 
-    rounds
+// "A Y", keeping the second column to read either way
+fn round(input: &str) -> Parsed<'_, (Choice, char)> {
+    let opponent = alt((
+        value(Rock, char('A')),
+        value(Paper, char('B')),
+        value(Scissor, char('C')),
+    ));
+    let second = one_of("XYZ");
+    separated_pair(
+        context("A, B or C", opponent),
+        char(' '),
+        context("X, Y or Z", second),
+    )(input)
 }
 
-pub fn correct_input(filename: &str) -> Vec<Round> {
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
-
-    let mut rounds = Vec::new();
-    for line in reader.lines() {
-        let line = line.unwrap();
-        let moves: Vec<&str> = line.split(' ').collect();
-        let opponent = match moves[0] {
-            "A" => Rock,
-            "B" => Paper,
-            "C" => Scissor,
-            _ => panic!("arst"),
-        };
-        let goal = match moves[1] {
-            "X" => Lose,
-            "Y" => Draw,
-            "Z" => Win,
-            _ => panic!("arst"),
-        };
-        let you = match (goal, &opponent) {
-            (Lose, Rock) => Scissor,
-            (Lose, Paper) => Rock,
-            (Lose, Scissor) => Paper,
-            (Draw, Rock) => Rock,
-            (Draw, Paper) => Paper,
-            (Draw, Scissor) => Scissor,
-            (Win, Rock) => Paper,
-            (Win, Paper) => Scissor,
-            (Win, Scissor) => Rock,
-        };
+pub fn parse_input(contents: &str) -> Result<Vec<Round>, ParseError> {
+    let rounds = parse_all(contents, lines(round))?;
+    Ok(rounds
+        .into_iter()
+        .map(|(opponent, you)| {
+            let you = match you {
+                'X' => Rock,
+                'Y' => Paper,
+                _ => Scissor,
+            };
+            Round { opponent, you }
+        })
+        .collect())
+}
 
-        rounds.push(Round { opponent, you });
-    }
+pub fn correct_input(filename: &str) -> Result<Vec<Round>, InputError> {
+    input::parse!(filename, parse_corrected)
+}
 
-    rounds
+pub fn parse_corrected(contents: &str) -> Result<Vec<Round>, ParseError> {
+    let rounds = parse_all(contents, lines(round))?;
+    Ok(rounds
+        .into_iter()
+        .map(|(opponent, goal)| {
+            let goal = match goal {
+                'X' => Lose,
+                'Y' => Draw,
+                _ => Win,
+            };
+            let you = match (goal, &opponent) {
+                (Lose, Rock) => Scissor,
+                (Lose, Paper) => Rock,
+                (Lose, Scissor) => Paper,
+                (Draw, Rock) => Rock,
+                (Draw, Paper) => Paper,
+                (Draw, Scissor) => Scissor,
+                (Win, Rock) => Paper,
+                (Win, Paper) => Scissor,
+                (Win, Scissor) => Rock,
+            };
+            Round { opponent, you }
+        })
+        .collect())
 }
 
 pub fn score_game(rounds: &[Round]) -> i32 {
@@ -113,31 +120,60 @@ pub fn score_game(rounds: &[Round]) -> i32 {
     score
 }
 
+pub struct Day2;
+
+impl Solution for Day2 {
+    // The second column read as your move, then as how the round has to end
+    type Input = (Vec<Round>, Vec<Round>);
+
+    fn parse(input: &str) -> ParseResult<(Vec<Round>, Vec<Round>)> {
+        Ok((parse_input(input)?, parse_corrected(input)?))
+    }
+
+    fn part1((rounds, _): &(Vec<Round>, Vec<Round>)) -> PartResult<impl Display> {
+        Ok(score_game(rounds))
+    }
+
+    fn part2((_, corrected): &(Vec<Round>, Vec<Round>)) -> PartResult<impl Display> {
+        Ok(score_game(corrected))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn example1() {
-        let rounds = read_input("example1.txt");
+        let rounds = read_input("example1.txt").unwrap();
         assert_eq!(score_game(&rounds), 15)
     }
 
     #[test]
     fn part1() {
-        let rounds = read_input("input.txt");
+        let rounds = read_input("input.txt").unwrap();
         assert_eq!(score_game(&rounds), 14069)
     }
 
     #[test]
     fn example2() {
-        let rounds = correct_input("example1.txt");
+        let rounds = correct_input("example1.txt").unwrap();
         assert_eq!(score_game(&rounds), 12)
     }
 
     #[test]
     fn part2() {
-        let rounds = correct_input("input.txt");
+        let rounds = correct_input("input.txt").unwrap();
         assert_eq!(score_game(&rounds), 12411) //13081  too high
     }
+
+    #[test]
+    fn bad_round() {
+        let error = parse_input("A Y\nB X\nC W").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 3));
+        assert_eq!(error.expected, "X, Y or Z");
+
+        let error = parse_corrected("A Y\nD X").unwrap_err();
+        assert_eq!(error.expected, "A, B or C");
+    }
 }
@@ -1,23 +1,25 @@
-use std::{
-    collections::HashSet,
-    fs::File,
-    io::{BufRead, BufReader},
+use input::InputError;
+use parser::nom::{
+    character::complete::alpha1,
+    combinator::{map, verify},
+    error::context,
 };
+use parser::{lines, parse_all, ParseError};
+use solution::{ParseResult, PartResult, Solution};
+use std::{collections::HashSet, fmt::Display};
 
-pub fn read_input(filename: &str) -> Vec<(String, String)> {
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
-
-    let mut rucksacks = Vec::new();
-    for line in reader.lines() {
-        let line = line.unwrap();
-        let a = line.split_at(line.len() / 2);
-
-        rucksacks.push((a.0.to_owned(), a.1.to_owned()));
-        assert!(a.0.len() == a.1.len());
-    }
+pub fn read_input(filename: &str) -> Result<Vec<(String, String)>, InputError> {
+    input::parse!(filename, parse_input)
+}
 
-    rucksacks
+// Each rucksack's items, split evenly between its two compartments
+pub fn parse_input(contents: &str) -> Result<Vec<(String, String)>, ParseError> {
+    let items = verify(alpha1, |items: &str| items.len().is_multiple_of(2));
+    let rucksack = map(items, |items: &str| {
+        let (left, right) = items.split_at(items.len() / 2);
+        (left.to_owned(), right.to_owned())
+    });
+    parse_all(contents, lines(context("rucksack", rucksack)))
 }
 
 pub fn score_rucksacks(rucksacks: &Vec<(String, String)>) -> i32 {
@@ -72,6 +74,24 @@ pub fn score_rucksack_groups(rucksacks: &[(String, String)]) -> i32 {
     score
 }
 
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Vec<(String, String)>;
+
+    fn parse(input: &str) -> ParseResult<Self::Input> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(rucksacks: &Self::Input) -> PartResult<impl Display> {
+        Ok(score_rucksacks(rucksacks))
+    }
+
+    fn part2(rucksacks: &Self::Input) -> PartResult<impl Display> {
+        Ok(score_rucksack_groups(rucksacks))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -87,7 +107,7 @@ mod tests {
 
     #[test]
     fn example1() {
-        let rucksacks = read_input("example1.txt");
+        let rucksacks = read_input("example1.txt").unwrap();
 
         score_rucksacks(&rucksacks);
         assert_eq!(score_rucksacks(&rucksacks), 157);
@@ -95,19 +115,26 @@ mod tests {
 
     #[test]
     fn part1() {
-        let rucksacks = read_input("input.txt");
+        let rucksacks = read_input("input.txt").unwrap();
         assert_eq!(score_rucksacks(&rucksacks), 7766);
     }
 
     #[test]
     fn example2() {
-        let rucksacks = read_input("example1.txt");
+        let rucksacks = read_input("example1.txt").unwrap();
         assert_eq!(score_rucksack_groups(&rucksacks), 70);
     }
 
     #[test]
     fn part2() {
-        let rucksacks = read_input("input.txt");
+        let rucksacks = read_input("input.txt").unwrap();
         assert_eq!(score_rucksack_groups(&rucksacks), 2415);
     }
+
+    #[test]
+    fn bad_rucksack() {
+        let error = parse_input("vJrwpWtwJgWrhcsFMMfFFhFp\nPmmdzqPrVvPwwTWBw").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.expected, "rucksack");
+    }
 }
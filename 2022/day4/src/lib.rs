@@ -1,39 +1,57 @@
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-};
-extern crate regex;
-use regex::{Captures, Regex};
-
-pub fn full_overlap(caps: &Captures) -> bool {
-    ((caps[1].parse::<i32>().unwrap() >= caps[3].parse::<i32>().unwrap())
-        && (caps[4].parse::<i32>().unwrap() >= caps[2].parse::<i32>().unwrap()))
-        || ((caps[1].parse::<i32>().unwrap() <= caps[3].parse::<i32>().unwrap())
-            && (caps[4].parse::<i32>().unwrap() <= caps[2].parse::<i32>().unwrap()))
+use input::InputError;
+use parser::nom::{character::complete::char, sequence::separated_pair};
+use parser::{integer, lines, parse_all, ParseError, Parsed};
+use solution::{ParseResult, PartResult, Solution};
+use std::fmt::Display;
+
+type Range = (i32, i32);
+pub type Pair = (Range, Range);
+
+pub fn full_overlap(&((a, b), (c, d)): &Pair) -> bool {
+    (a >= c && d >= b) || (a <= c && d <= b)
+}
+
+pub fn partial_overlap(&((a, b), (c, d)): &Pair) -> bool {
+    (c >= a && c <= b) || (d >= a && d <= b)
+}
+
+// "2-4,6-8"
+fn pair(input: &str) -> Parsed<'_, Pair> {
+    let range = |input| separated_pair(integer, char('-'), integer)(input);
+    separated_pair(range, char(','), range)(input)
+}
+
+pub fn parse_pairs(input: &str) -> Result<Vec<Pair>, ParseError> {
+    parse_all(input, lines(pair))
+}
+
+pub fn check_overlaps(filename: &str, pred: fn(&Pair) -> bool) -> Result<usize, InputError> {
+    let pairs = input::parse!(filename, parse_pairs)?;
+    Ok(count_overlaps(&pairs, pred))
 }
 
-pub fn partial_overlap(caps: &Captures) -> bool {
-    (caps[3].parse::<i32>().unwrap() >= caps[1].parse::<i32>().unwrap())
-        && (caps[3].parse::<i32>().unwrap() <= caps[2].parse::<i32>().unwrap())
-        || (caps[4].parse::<i32>().unwrap() >= caps[1].parse::<i32>().unwrap())
-            && (caps[4].parse::<i32>().unwrap() <= caps[2].parse::<i32>().unwrap())
+pub fn count_overlaps(pairs: &[Pair], pred: fn(&Pair) -> bool) -> usize {
+    pairs.iter().filter(|pair| pred(pair)).count()
 }
 
-pub fn check_overlaps(filename: &str, pred: fn(&Captures) -> bool) -> i32 {
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = Vec<Pair>;
 
-    let re = Regex::new(r"(\d+)-(\d+),(\d+)-(\d+)").unwrap();
-    let mut score = 0;
-    for line in reader.lines() {
-        let line = line.unwrap();
-        let caps = re.captures(&line).unwrap();
+    fn parse(input: &str) -> ParseResult<Vec<Pair>> {
+        Ok(parse_pairs(input)?)
+    }
+
+    fn part1(pairs: &Vec<Pair>) -> PartResult<impl Display> {
+        Ok(count_overlaps(pairs, full_overlap))
+    }
 
-        if pred(&caps) {
-            score += 1;
-        }
+    fn part2(pairs: &Vec<Pair>) -> PartResult<impl Display> {
+        Ok(count_overlaps(pairs, |c| {
+            partial_overlap(c) || full_overlap(c)
+        }))
     }
-    score
 }
 
 #[cfg(test)]
@@ -42,20 +60,21 @@ mod tests {
 
     #[test]
     fn example1() {
-        assert_eq!(check_overlaps("example1.txt", full_overlap), 2);
+        assert_eq!(check_overlaps("example1.txt", full_overlap).unwrap(), 2);
     }
 
     #[test]
     fn part1() {
-        assert_eq!(check_overlaps("input.txt", full_overlap), 507);
+        assert_eq!(check_overlaps("input.txt", full_overlap).unwrap(), 507);
     }
 
     #[test]
     fn example2() {
         assert_eq!(
             check_overlaps("example1.txt", |c| {
-                partial_overlap(&c) || full_overlap(&c)
-            }),
+                partial_overlap(c) || full_overlap(c)
+            })
+            .unwrap(),
             4
         );
     }
@@ -63,7 +82,7 @@ mod tests {
     #[test]
     fn part2() {
         assert_eq!(
-            check_overlaps("input.txt", |c| { partial_overlap(&c) || full_overlap(&c) }),
+            check_overlaps("input.txt", |c| { partial_overlap(c) || full_overlap(c) }).unwrap(),
             897
         );
     }
@@ -1,10 +1,16 @@
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
+use input::InputError;
+use parser::nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{char, line_ending, satisfy, space0, space1},
+    combinator::{cut, map, value, verify},
+    error::context,
+    multi::{many1, separated_list1},
+    sequence::{delimited, pair, preceded, terminated, tuple},
 };
-
-extern crate regex;
-use regex::Regex;
+use parser::{blank_lines, integer, lines, parse_all, ParseError, Parsed};
+use solution::{ParseResult, PartResult, Solution};
+use std::fmt::Display;
 
 fn do_move(from: usize, to: usize, mut stacks: Vec<Vec<char>>) -> Vec<Vec<char>> {
     let v = stacks[from - 1].pop().unwrap();
@@ -13,53 +19,101 @@ fn do_move(from: usize, to: usize, mut stacks: Vec<Vec<char>>) -> Vec<Vec<char>>
     stacks
 }
 
-pub fn read_input_9000(filename: &str, start: usize, mut stacks: Vec<Vec<char>>) -> Vec<Vec<char>> {
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
+// How many crates, and the stacks they go from and to
+pub type Move = (usize, usize, usize);
+
+// One crate at a time
+pub fn move_9000(mut stacks: Vec<Vec<char>>, moves: &[Move]) -> Vec<Vec<char>> {
+    for &(times, from, to) in moves {
+        for _ in 0..times {
+            stacks = do_move(from, to, stacks);
+        }
+    }
+    stacks
+}
+
+// Several crates at once, keeping their order
+pub fn move_9001(mut stacks: Vec<Vec<char>>, moves: &[Move]) -> Vec<Vec<char>> {
+    for &(times, from, to) in moves {
+        let l = stacks[from - 1].len() - times;
+        for m in stacks[from - 1].split_off(l) {
+            stacks[to - 1].push(m);
+        }
+    }
+    stacks
+}
 
-    let re = Regex::new(r"move (\d+) from (\d+) to (\d+)").unwrap();
+pub fn read_input(filename: &str) -> Result<(Vec<Vec<char>>, Vec<Move>), InputError> {
+    input::parse!(filename, parse_input)
+}
 
-    for (idx, line) in reader.lines().enumerate() {
-        if idx >= start {
-            let line = line.unwrap();
-            let caps = re.captures(&line).unwrap();
-            assert!(caps.len() == 4);
+// "[D]", or three spaces where a stack is shorter
+fn slot(input: &str) -> Parsed<'_, Option<char>> {
+    let crate_ = delimited(
+        char('['),
+        cut(context("crate", satisfy(|c| c.is_ascii_uppercase()))),
+        cut(char(']')),
+    );
+    alt((map(crate_, Some), value(None, tag("   "))))(input)
+}
 
-            let from = caps[2].parse::<usize>().unwrap();
-            let to = caps[3].parse::<usize>().unwrap();
-            let times = caps[1].parse::<usize>().unwrap();
+// The rows of crates from the top down, then " 1   2   3 " naming the stacks
+fn drawing(input: &str) -> Parsed<'_, Vec<Vec<char>>> {
+    let rows = many1(terminated(separated_list1(char(' '), slot), line_ending));
+    let labels = delimited(space0, separated_list1(space1, integer::<usize>), space0);
+    let (input, (rows, labels)) = pair(rows, labels)(input)?;
 
-            for _ in 0..times {
-                stacks = do_move(from, to, stacks);
-            }
+    let mut stacks = vec![Vec::new(); labels.len()];
+    for row in rows.iter().rev() {
+        for (stack, slot) in stacks.iter_mut().zip(row) {
+            stack.extend(*slot);
         }
     }
-    stacks
+    Ok((input, stacks))
 }
 
-pub fn read_input_9001(filename: &str, start: usize, mut stacks: Vec<Vec<char>>) -> Vec<Vec<char>> {
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
+// The drawing, a blank line and then moves like "move 1 from 2 to 1" between
+// the stacks it drew
+fn procedure(input: &str) -> Parsed<'_, (Vec<Vec<char>>, Vec<Move>)> {
+    let (input, stacks) = drawing(input)?;
+    let count = stacks.len();
+    let stack = move |input| {
+        let stack = verify(integer, |n: &usize| (1..=count).contains(n));
+        context("stack number", stack)(input)
+    };
+    let step = tuple((
+        preceded(tag("move "), integer),
+        preceded(tag(" from "), stack),
+        preceded(tag(" to "), stack),
+    ));
+    let (input, moves) = preceded(blank_lines, lines(step))(input)?;
+    Ok((input, (stacks, moves)))
+}
 
-    let re = Regex::new(r"move (\d+) from (\d+) to (\d+)").unwrap();
+pub fn parse_input(input: &str) -> Result<(Vec<Vec<char>>, Vec<Move>), ParseError> {
+    parse_all(input, procedure)
+}
 
-    for (idx, line) in reader.lines().enumerate() {
-        if idx >= start {
-            let line = line.unwrap();
-            let caps = re.captures(&line).unwrap();
-            assert!(caps.len() == 4);
+pub fn tops(stacks: &[Vec<char>]) -> String {
+    stacks.iter().filter_map(|s| s.last()).collect()
+}
 
-            let from = caps[2].parse::<usize>().unwrap();
-            let to = caps[3].parse::<usize>().unwrap();
-            let times = caps[1].parse::<usize>().unwrap();
+pub struct Day5;
 
-            let l = stacks[from - 1].len() - times;
-            for m in stacks[from - 1].split_off(l) {
-                stacks[to - 1].push(m);
-            }
-        }
+impl Solution for Day5 {
+    type Input = (Vec<Vec<char>>, Vec<Move>);
+
+    fn parse(input: &str) -> ParseResult<(Vec<Vec<char>>, Vec<Move>)> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1((stacks, moves): &(Vec<Vec<char>>, Vec<Move>)) -> PartResult<impl Display> {
+        Ok(tops(&move_9000(stacks.clone(), moves)))
+    }
+
+    fn part2((stacks, moves): &(Vec<Vec<char>>, Vec<Move>)) -> PartResult<impl Display> {
+        Ok(tops(&move_9001(stacks.clone(), moves)))
     }
-    stacks
 }
 
 #[cfg(test)]
@@ -68,73 +122,45 @@ mod tests {
 
     #[test]
     fn example1() {
-        let stacks = vec![vec!['z', 'n'], vec!['m', 'c', 'd'], vec!['p']];
-        let stacks = read_input_9000("example1.txt", 5, stacks);
-
-        println!();
+        let (stacks, moves) = read_input("example1.txt").unwrap();
+        assert_eq!(stacks, [vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P']]);
 
-        let mut ans = String::new();
-        for s in stacks {
-            ans.push(*s.last().unwrap());
-        }
-        assert_eq!(ans.to_uppercase(), "CMZ");
+        let stacks = move_9000(stacks, &moves);
+        assert_eq!(tops(&stacks), "CMZ");
     }
 
     #[test]
     fn part1() {
-        let stacks = vec![
-            vec!['s', 'c', 'v', 'n'],
-            vec!['z', 'm', 'j', 'h', 'n', 's'],
-            vec!['M', 'C', 'T', 'G', 'J', 'N', 'D'],
-            vec!['T', 'D', 'F', 'J', 'W', 'R', 'M'],
-            vec!['P', 'F', 'H'],
-            vec!['C', 'T', 'Z', 'H', 'J'],
-            vec!['D', 'P', 'R', 'Q', 'F', 'S', 'L', 'Z'],
-            vec!['C', 'S', 'L', 'H', 'D', 'F', 'P', 'W'],
-            vec!['D', 'S', 'M', 'P', 'F', 'N', 'G', 'Z'],
-        ];
-        let stacks = read_input_9000("input.txt", 10, stacks);
-
-        let mut ans = String::new();
-        for s in stacks {
-            ans.push(*s.last().unwrap());
-        }
-        assert_eq!(ans.to_uppercase(), "CNSZFDVLJ");
+        let (stacks, moves) = read_input("input.txt").unwrap();
+        let stacks = move_9000(stacks, &moves);
+        assert_eq!(tops(&stacks), "CNSZFDVLJ");
     }
 
     #[test]
     fn example2() {
-        let stacks = vec![vec!['z', 'n'], vec!['m', 'c', 'd'], vec!['p']];
-        let stacks = read_input_9001("example1.txt", 5, stacks);
-
-        println!();
-
-        let mut ans = String::new();
-        for s in stacks {
-            ans.push(*s.last().unwrap());
-        }
-        assert_eq!(ans.to_uppercase(), "MCD");
+        let (stacks, moves) = read_input("example1.txt").unwrap();
+        let stacks = move_9001(stacks, &moves);
+        assert_eq!(tops(&stacks), "MCD");
     }
 
     #[test]
     fn part2() {
-        let stacks = vec![
-            vec!['s', 'c', 'v', 'n'],
-            vec!['z', 'm', 'j', 'h', 'n', 's'],
-            vec!['M', 'C', 'T', 'G', 'J', 'N', 'D'],
-            vec!['T', 'D', 'F', 'J', 'W', 'R', 'M'],
-            vec!['P', 'F', 'H'],
-            vec!['C', 'T', 'Z', 'H', 'J'],
-            vec!['D', 'P', 'R', 'Q', 'F', 'S', 'L', 'Z'],
-            vec!['C', 'S', 'L', 'H', 'D', 'F', 'P', 'W'],
-            vec!['D', 'S', 'M', 'P', 'F', 'N', 'G', 'Z'],
-        ];
-        let stacks = read_input_9001("input.txt", 10, stacks);
-
-        let mut ans = String::new();
-        for s in stacks {
-            ans.push(*s.last().unwrap());
-        }
-        assert_eq!(ans.to_uppercase(), "QNDWLMGNS");
+        let (stacks, moves) = read_input("input.txt").unwrap();
+        let stacks = move_9001(stacks, &moves);
+        assert_eq!(tops(&stacks), "QNDWLMGNS");
+    }
+
+    #[test]
+    fn bad_procedure() {
+        let drawing = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\n";
+
+        let error = parse_input(&format!("{}move 1 from 2 to 4", drawing)).unwrap_err();
+        assert_eq!((error.line, error.column), (6, 18));
+        assert_eq!(error.expected, "stack number");
+
+        let error =
+            parse_input(&format!("{}move 1 from 2 to 1", drawing.replace('C', "c"))).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 6));
+        assert_eq!(error.expected, "crate");
     }
 }
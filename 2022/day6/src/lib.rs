@@ -1,8 +1,12 @@
+use parser::nom::{bytes::complete::take_while1, error::context};
+use parser::{parse_all, ParseError};
+use solution::{ParseResult, PartResult, Solution};
 use std::collections::HashSet;
+use std::fmt::Display;
 
-pub fn get_start(input: &str, marker_len: usize) -> usize {
+pub fn get_start(signal: &[u8], marker_len: usize) -> usize {
     let mut pos = marker_len;
-    for win in input.as_bytes().windows(marker_len) {
+    for win in signal.windows(marker_len) {
         let win_set: HashSet<&u8> = win.iter().collect();
 
         if win_set.len() == marker_len {
@@ -14,52 +18,91 @@ pub fn get_start(input: &str, marker_len: usize) -> usize {
     pos
 }
 
+// None if the signal ends before any marker
+fn find_marker(signal: &[u8], marker_len: usize) -> Option<usize> {
+    let start = get_start(signal, marker_len);
+    (start <= signal.len()).then_some(start)
+}
+
+pub fn parse_signal(input: &str) -> Result<Vec<u8>, ParseError> {
+    let letters = take_while1(|c: char| c.is_ascii_lowercase());
+    let signal = parse_all(input, context("signal", letters))?;
+    Ok(signal.as_bytes().to_vec())
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = Vec<u8>;
+
+    fn parse(input: &str) -> ParseResult<Vec<u8>> {
+        Ok(parse_signal(input)?)
+    }
+
+    fn part1(signal: &Vec<u8>) -> PartResult<impl Display> {
+        Ok(find_marker(signal, 4).ok_or("No start-of-packet marker")?)
+    }
+
+    fn part2(signal: &Vec<u8>) -> PartResult<impl Display> {
+        Ok(find_marker(signal, 14).ok_or("No start-of-message marker")?)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::fs;
 
     #[test]
     fn example1() {
         let marker_len = 4;
-        assert_eq!(get_start("mjqjpqmgbljsphdztnvjfqwrcgsmlb", marker_len), 7);
-        assert_eq!(get_start("bvwbjplbgvbhsrlpgdmjqwftvncz", marker_len), 5);
-        assert_eq!(get_start("nppdvjthqldpwncqszvftbrmjlhg", marker_len), 6);
+        assert_eq!(get_start(b"mjqjpqmgbljsphdztnvjfqwrcgsmlb", marker_len), 7);
+        assert_eq!(get_start(b"bvwbjplbgvbhsrlpgdmjqwftvncz", marker_len), 5);
+        assert_eq!(get_start(b"nppdvjthqldpwncqszvftbrmjlhg", marker_len), 6);
         assert_eq!(
-            get_start("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", marker_len),
+            get_start(b"nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", marker_len),
             10
         );
         assert_eq!(
-            get_start("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", marker_len),
+            get_start(b"zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", marker_len),
             11
         );
     }
 
     #[test]
     fn part1() {
-        let input = fs::read_to_string("input.txt").unwrap();
-        assert_eq!(get_start(&input, 4), 1343);
+        let input = input::read!("input.txt").unwrap();
+        assert_eq!(get_start(input.as_bytes(), 4), 1343);
     }
 
     #[test]
     fn example2() {
         let marker_len = 14;
-        assert_eq!(get_start("mjqjpqmgbljsphdztnvjfqwrcgsmlb", marker_len), 19);
-        assert_eq!(get_start("bvwbjplbgvbhsrlpgdmjqwftvncz", marker_len), 23);
-        assert_eq!(get_start("nppdvjthqldpwncqszvftbrmjlhg", marker_len), 23);
+        assert_eq!(get_start(b"mjqjpqmgbljsphdztnvjfqwrcgsmlb", marker_len), 19);
+        assert_eq!(get_start(b"bvwbjplbgvbhsrlpgdmjqwftvncz", marker_len), 23);
+        assert_eq!(get_start(b"nppdvjthqldpwncqszvftbrmjlhg", marker_len), 23);
         assert_eq!(
-            get_start("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", marker_len),
+            get_start(b"nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", marker_len),
             29
         );
         assert_eq!(
-            get_start("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", marker_len),
+            get_start(b"zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", marker_len),
             26
         );
     }
 
     #[test]
     fn part2() {
-        let input = fs::read_to_string("input.txt").unwrap();
-        assert_eq!(get_start(&input, 14), 2193);
+        let input = input::read!("input.txt").unwrap();
+        assert_eq!(get_start(input.as_bytes(), 14), 2193);
+    }
+
+    #[test]
+    fn bad_signal() {
+        let error = parse_signal("mjqjpqmgb1jsphdztnvjfqwrcgsmlb").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 10));
+
+        assert_eq!(find_marker(b"mjqjpqmgb", 4), Some(7));
+        assert_eq!(find_marker(b"aabbaabb", 4), None);
+        assert_eq!(find_marker(b"abc", 4), None);
     }
 }
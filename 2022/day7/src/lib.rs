@@ -1,7 +1,15 @@
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
+use input::InputError;
+use parser::nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::char,
+    combinator::{cut, map, value},
+    error::context,
+    sequence::{preceded, separated_pair},
 };
+use parser::{integer, lines, parse_all, word, ParseError, Parsed};
+use solution::{ParseResult, PartResult, Solution};
+use std::fmt::Display;
 
 #[derive(Debug, Clone)]
 pub struct Node {
@@ -30,64 +38,83 @@ impl Node {
     }
 }
 
-pub fn read_input(filename: &str) -> Node {
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
+pub fn read_input(filename: &str) -> Result<Node, InputError> {
+    input::parse!(filename, parse_input)
+}
 
-    let mut filesystem = Node::new("/");
+// A line of the terminal session
+#[derive(Clone)]
+enum Output<'a> {
+    Cd(&'a str),
+    Ls,
+    Dir(&'a str),
+    File(i32, &'a str),
+}
+
+// "$ cd a", "$ ls", "dir a" or "14848514 b.txt"
+fn output(input: &str) -> Parsed<'_, Output<'_>> {
+    let command = alt((
+        map(preceded(tag("cd "), word), Output::Cd),
+        value(Output::Ls, tag("ls")),
+    ));
+    let listing = alt((
+        map(preceded(tag("dir "), word), Output::Dir),
+        map(separated_pair(integer, char(' '), word), |(size, name)| {
+            Output::File(size, name)
+        }),
+    ));
+    alt((
+        preceded(tag("$ "), cut(context("cd or ls", command))),
+        listing,
+    ))(input)
+}
+
+fn find_dir<'a>(mut dir: &'a mut Node, path: &[&str]) -> Option<&'a mut Node> {
+    for name in path {
+        dir = dir
+            .children
+            .iter_mut()
+            .find(|child| child.filename == *name)?;
+    }
+    Some(dir)
+}
+
+// Only a directory that's been listed can be changed into
+pub fn parse_input(input: &str) -> Result<Node, ParseError> {
+    let session = parse_all(input, lines(output))?;
 
-    let mut cwd = &mut filesystem;
-    let mut location: Vec<String> = Vec::new();
-    // let mut location = "/".to_string();
-
-    for line in reader.lines() {
-        let line = line.unwrap();
-        if line.contains("$ cd /") {
-            // just ignore
-        } else if line.contains("$ cd") {
-            // move the cwd pointer
-            let target = line.replace("$ cd ", "");
-            if target == ".." {
+    let mut filesystem = Node::new("/");
+    let mut location: Vec<&str> = Vec::new();
+    for (line, output) in session.into_iter().enumerate() {
+        let listing = match output {
+            Output::Cd("/") => {
+                location.clear();
+                continue;
+            }
+            Output::Cd("..") => {
                 location.pop();
-                cwd = &mut filesystem;
-
-                for l in &location {
-                    cwd = cwd
-                        .children
-                        .iter_mut()
-                        .find(|child| child.filename == *l)
-                        .unwrap();
+                continue;
+            }
+            Output::Cd(target) => {
+                location.push(target);
+                if find_dir(&mut filesystem, &location).is_none() {
+                    return Err(ParseError {
+                        line: line + 1,
+                        column: 6,
+                        expected: "a directory listed in this one".to_string(),
+                        found: target.to_string(),
+                    });
                 }
-            } else {
-                location.push(target.clone());
-
-                cwd = cwd
-                    .children
-                    .iter_mut()
-                    .find(|child| child.filename == target)
-                    .unwrap();
+                continue;
             }
-        } else if line.contains("$ ls") {
-            // just ignore
-        } else {
-            // get the contents of an ls command
-            let mut s = line.split(' ');
-            let size: i32 = s.next().unwrap().parse().unwrap_or(0);
-            let name = s.next().unwrap();
-
-            let new_file = Node::new_with_size(name, size);
-            cwd.children.push(new_file);
-
-            // // update ancestor dir filesizes
-            // unsafe {
-            // ...
-            //  mutable graphs with ownership is hard
-            // ...
-            //     }
-            // }
-        }
+            Output::Ls => continue,
+            Output::Dir(name) => Node::new(name),
+            Output::File(size, name) => Node::new_with_size(name, size),
+        };
+        let cwd = find_dir(&mut filesystem, &location).expect("checked when changed into");
+        cwd.children.push(listing);
     }
-    filesystem
+    Ok(filesystem)
 }
 
 pub fn get_size(fs: &Node) -> (i32, i32) {
@@ -143,19 +170,42 @@ pub fn find_best_dir(target: i32, list: &Vec<i32>) -> i32 {
     }
     s
 }
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Node;
+
+    fn parse(input: &str) -> ParseResult<Node> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(filesystem: &Node) -> PartResult<impl Display> {
+        Ok(get_size(filesystem).0)
+    }
+
+    fn part2(filesystem: &Node) -> PartResult<impl Display> {
+        let size = get_size(filesystem).1;
+        let target = 30000000 - (70000000 - size);
+
+        let mut list = Vec::new();
+        get_sizes(filesystem, &mut list);
+        Ok(find_best_dir(target, &list))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn example1() {
-        let filesystem = read_input("example.txt");
+        let filesystem = read_input("example.txt").unwrap();
         assert_eq!(get_size(&filesystem).0, 95437);
     }
 
     #[test]
     fn part1() {
-        let filesystem = read_input("input.txt");
+        let filesystem = read_input("input.txt").unwrap();
 
         assert_eq!(get_size(&filesystem).0, 1770595);
         //5772414 Too high
@@ -163,7 +213,7 @@ mod tests {
 
     #[test]
     fn example2() {
-        let filesystem = read_input("example.txt");
+        let filesystem = read_input("example.txt").unwrap();
         let size = get_size(&filesystem).1;
         let target = 30000000 - (70000000 - size);
         println!("Need to delete at least {}", target);
@@ -176,7 +226,7 @@ mod tests {
 
     #[test]
     fn part2() {
-        let filesystem = read_input("input.txt");
+        let filesystem = read_input("input.txt").unwrap();
         let size = get_size(&filesystem).1;
         let target = 30000000 - (70000000 - size);
         println!("Need to delete at least {}", target);
@@ -186,4 +236,19 @@ mod tests {
 
         assert_eq!(find_best_dir(target, &list), 2195372);
     }
+
+    #[test]
+    fn bad_session() {
+        let error = parse_input("$ cd /\n$ ls\ndir a\n$ cd b").unwrap_err();
+        assert_eq!((error.line, error.column), (4, 6));
+        assert_eq!(error.found, "b");
+
+        let error = parse_input("$ cd /\n$ dir\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.expected, "cd or ls");
+
+        let error = parse_input("$ cd /\n$ ls\nb.txt 14848514").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 1));
+        assert_eq!(error.expected, "integer");
+    }
 }
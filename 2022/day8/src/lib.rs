@@ -1,156 +1,70 @@
-use std::{
-    collections::HashMap,
-    fs::File,
-    io::{BufRead, BufReader},
-};
-
-type Map = HashMap<(usize, usize), u32>;
-pub fn read_input(filename: &str) -> (Map, usize, usize) {
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
-
-    let mut map = HashMap::new();
-    let mut len_y = 0;
-    let mut len_x = 0;
-    for (y, line) in reader.lines().enumerate() {
-        let line = line.unwrap();
-        for (x, z) in line.chars().enumerate() {
-            map.insert((x, y), z.to_digit(10).unwrap());
-            len_x = x;
-        }
-        len_y = y;
-    }
+use grid::{Grid, GridError, ORTHOGONAL};
+use input::InputError;
+use solution::{ParseResult, PartResult, Solution};
+use std::fmt::Display;
 
-    (map, len_x + 1, len_y + 1)
+pub fn read_input(filename: &str) -> Result<Grid<u32>, InputError> {
+    input::parse!(filename, parse_input)
 }
 
-pub fn count_visibility(map: &Map, len_x: usize, len_y: usize) -> usize {
-    let mut count = (len_x + len_y - 2) * 2;
-
-    for x in 1..len_x - 1 {
-        'out: for y in 1..len_y - 1 {
-            let tree = map[&(x, y)];
-
-            // check visibility
-            // check up
-            'up: {
-                for dy in 0..=y - 1 {
-                    if tree > map[&(x, dy)] {
-                        continue;
-                    }
-                    break 'up;
-                }
-                count += 1;
-                continue 'out;
-            }
+pub fn parse_input(contents: &str) -> Result<Grid<u32>, GridError> {
+    Grid::try_parse(contents, |c| c.to_digit(10))
+}
 
-            // check down
-            'down: {
-                for dy in y + 1..len_y {
-                    if tree > map[&(x, dy)] {
-                        continue;
-                    }
-                    break 'down;
-                }
-                count += 1;
-                continue 'out;
-            }
+// Trees on the edge see straight out, so they're always visible
+pub fn count_visibility(map: &Grid<u32>) -> usize {
+    map.iter()
+        .filter(|&(p, tree)| {
+            ORTHOGONAL
+                .iter()
+                .any(|&d| map.ray(p, d).all(|q| map[q] < *tree))
+        })
+        .count()
+}
 
-            // check left
-            'left: {
-                for dx in 0..=x - 1 {
-                    if tree > map[&(dx, y)] {
-                        continue;
-                    }
-                    break 'left;
+// How many trees can be seen looking each way, up to and including the first
+// one at least as tall, multiplied together
+pub fn score_visibility(map: &Grid<u32>, x: usize, y: usize) -> usize {
+    let tree = map[(x, y)];
+
+    ORTHOGONAL
+        .iter()
+        .map(|&d| {
+            let mut score = 0;
+            for q in map.ray((x, y), d) {
+                score += 1;
+                if map[q] >= tree {
+                    break;
                 }
-                count += 1;
-                continue 'out;
             }
+            score
+        })
+        .product()
+}
 
-            // check right
-            'right: {
-                for dx in x + 1..len_x {
-                    if tree > map[&(dx, y)] {
-                        continue;
-                    }
-                    break 'right;
-                }
-                count += 1;
-                continue 'out;
-            }
-        }
-    }
-    count
+pub fn find_best(map: &Grid<u32>) -> usize {
+    map.points()
+        .map(|(x, y)| score_visibility(map, x, y))
+        .max()
+        .unwrap_or(0)
 }
 
-pub fn score_visibility(map: &Map, len_x: usize, len_y: usize, x: usize, y: usize) -> usize {
-    let tree = map[&(x, y)];
+pub struct Day8;
 
-    // check visibility
-    let up = {
-        let mut score = 0;
-        for dy in (0..=(y - 1)).rev() {
-            score += 1;
-            if tree > map[&(x, dy)] {
-                continue;
-            }
-            break;
-        }
-        score
-    };
-
-    let down = {
-        let mut score = 0;
-        for dy in (y + 1)..=len_y - 1 {
-            score += 1;
-            if tree > map[&(x, dy)] {
-                continue;
-            }
-            break;
-        }
-        score
-    };
-
-    let left = {
-        let mut score = 0;
-        // for dx in (x - 1)..=0 {
-        for dx in (0..=(x - 1)).rev() {
-            score += 1;
-            if tree > map[&(dx, y)] {
-                continue;
-            }
-            break;
-        }
-        score
-    };
-
-    let right = {
-        let mut score = 0;
-        for dx in (x + 1)..=len_x - 1 {
-            score += 1;
-            if tree > map[&(dx, y)] {
-                continue;
-            }
-            break;
-        }
-        score
-    };
-    right * left * up * down
-}
+impl Solution for Day8 {
+    type Input = Grid<u32>;
 
-pub fn find_best(map: &Map, len_x: usize, len_y: usize) -> usize {
-    let mut best = 0;
-    for x in 1..len_x - 1 {
-        for y in 1..len_y - 1 {
-            let score = score_visibility(map, len_x, len_y, x, y);
+    fn parse(input: &str) -> ParseResult<Self::Input> {
+        Ok(parse_input(input)?)
+    }
 
-            if score > best {
-                best = score;
-            }
-        }
+    fn part1(map: &Self::Input) -> PartResult<impl Display> {
+        Ok(count_visibility(map))
+    }
+
+    fn part2(map: &Self::Input) -> PartResult<impl Display> {
+        Ok(find_best(map))
     }
-    best
 }
 
 #[cfg(test)]
@@ -159,27 +73,39 @@ mod tests {
 
     #[test]
     fn example1() {
-        let (map, lx, ly) = read_input("example1.txt");
-        assert_eq!(count_visibility(&map, lx, ly), 21);
+        let map = read_input("example1.txt").unwrap();
+        assert_eq!(count_visibility(&map), 21);
     }
 
     #[test]
     fn part1() {
-        let (map, lx, ly) = read_input("input.txt");
-        assert_eq!(count_visibility(&map, lx, ly), 1854);
+        let map = read_input("input.txt").unwrap();
+        assert_eq!(count_visibility(&map), 1854);
     }
 
     #[test]
     fn example2() {
-        let (map, lx, ly) = read_input("example1.txt");
-        assert_eq!(score_visibility(&map, lx, ly, 2, 1), 4);
-        assert_eq!(score_visibility(&map, lx, ly, 2, 3), 8);
-        assert_eq!(find_best(&map, lx, ly), 8);
+        let map = read_input("example1.txt").unwrap();
+        assert_eq!(score_visibility(&map, 2, 1), 4);
+        assert_eq!(score_visibility(&map, 2, 3), 8);
+        assert_eq!(find_best(&map), 8);
     }
 
     #[test]
     fn part2() {
-        let (map, lx, ly) = read_input("input.txt");
-        assert_eq!(find_best(&map, lx, ly), 527340);
+        let map = read_input("input.txt").unwrap();
+        assert_eq!(find_best(&map), 527340);
+    }
+
+    #[test]
+    fn bad_heights() {
+        assert_eq!(
+            parse_input("30373\n25512\n6533\n").unwrap_err(),
+            GridError::Ragged {
+                line: 3,
+                width: 4,
+                expected: 5
+            }
+        );
     }
 }
@@ -1,10 +1,13 @@
-use regex::Regex;
-use std::{
-    collections::HashSet,
-    fs::File,
-    io::{BufRead, BufReader},
+use input::InputError;
+use parser::nom::{
+    character::complete::{char, one_of},
+    combinator::map,
+    error::context,
+    sequence::separated_pair,
 };
-extern crate regex;
+use parser::{integer, lines, parse_all, ParseError, Parsed};
+use solution::{ParseResult, PartResult, Solution};
+use std::{collections::HashSet, fmt::Display};
 
 #[derive(Debug)]
 pub struct Move {
@@ -12,22 +15,20 @@ pub struct Move {
     times: i32,
 }
 
-pub fn read_input(filename: &str) -> Vec<Move> {
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
-    let re = Regex::new(r"([U|L|D|R]) (\d+)").unwrap();
-
-    reader
-        .lines()
-        .map(|line| {
-            let line = line.unwrap();
-            let caps = re.captures(&line).unwrap();
-            Move {
-                dir: caps[1].chars().next().unwrap(),
-                times: caps[2].parse().unwrap(),
-            }
-        })
-        .collect()
+pub fn read_input(filename: &str) -> Result<Vec<Move>, InputError> {
+    input::parse!(filename, parse_input)
+}
+
+// "R 4"
+fn step(input: &str) -> Parsed<'_, Move> {
+    let dir = context("U, L, D or R", one_of("ULDR"));
+    map(separated_pair(dir, char(' '), integer), |(dir, times)| {
+        Move { dir, times }
+    })(input)
+}
+
+pub fn parse_input(contents: &str) -> Result<Vec<Move>, ParseError> {
+    parse_all(contents, lines(step))
 }
 
 fn fix_tail(current_head: (i32, i32), current_tail: (i32, i32)) -> (i32, i32) {
@@ -106,31 +107,59 @@ pub fn track_moves(moves: &Vec<Move>, n: usize) -> usize {
     visited.len()
 }
 
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = Vec<Move>;
+
+    fn parse(input: &str) -> ParseResult<Vec<Move>> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(moves: &Vec<Move>) -> PartResult<impl Display> {
+        Ok(track_moves(moves, 2))
+    }
+
+    fn part2(moves: &Vec<Move>) -> PartResult<impl Display> {
+        Ok(track_moves(moves, 10))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn example1() {
-        let moves = read_input("example1.txt");
+        let moves = read_input("example1.txt").unwrap();
         assert_eq!(track_moves(&moves, 2), 13);
     }
 
     #[test]
     fn part1() {
-        let moves = read_input("input.txt");
+        let moves = read_input("input.txt").unwrap();
         assert_eq!(track_moves(&moves, 2), 6087);
     }
 
     #[test]
     fn example2() {
-        let moves = read_input("example2.txt");
+        let moves = read_input("example2.txt").unwrap();
         assert_eq!(track_moves(&moves, 10), 36);
     }
 
     #[test]
     fn part2() {
-        let moves = read_input("input.txt");
+        let moves = read_input("input.txt").unwrap();
         assert_eq!(track_moves(&moves, 10), 2493);
     }
+
+    #[test]
+    fn bad_move() {
+        let error = parse_input("R 4\nU 4\nX 3").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 1));
+        assert_eq!(error.expected, "U, L, D or R");
+
+        let error = parse_input("R 4\nU four").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+    }
 }
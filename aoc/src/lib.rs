@@ -0,0 +1,1171 @@
+use bench::Benches;
+use solution::{ParseResult, Part, Registry, Runner, Solution};
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::panic;
+use std::path::{Path, PathBuf};
+use toml::{Table, Value};
+
+// Every day the runner knows about
+pub fn registry() -> Registry {
+    let mut registry = Registry::new();
+    registry
+        .register::<aoc2018_day1::Day1>(2018, 1)
+        .register::<aoc2018_day2::Day2>(2018, 2)
+        .register::<aoc2018_day3::Day3>(2018, 3)
+        .register::<aoc2018_day4::Day4>(2018, 4)
+        .register::<aoc2018_day5::Day5>(2018, 5)
+        .register::<aoc2018_day6::Day6>(2018, 6)
+        .register::<aoc2018_day7::Day7>(2018, 7)
+        .register::<aoc2018_day8::Day8>(2018, 8)
+        .register::<aoc2018_day10::Day10>(2018, 10)
+        .register::<aoc2018_day11::Day11>(2018, 11)
+        .register::<aoc2018_day12::Day12>(2018, 12)
+        .register::<aoc2018_day13::Day13>(2018, 13)
+        .register::<aoc2018_day15::Day15>(2018, 15)
+        .register::<aoc2019_day1::Day1>(2019, 1)
+        .register::<aoc2019_day2::Day2>(2019, 2)
+        .register::<aoc2019_day3::Day3>(2019, 3)
+        .register::<aoc2019_day4::Day4>(2019, 4)
+        .register::<aoc2019_day5::Day5>(2019, 5)
+        .register::<aoc2019_day6::Day6>(2019, 6)
+        .register::<aoc2019_day7::Day7>(2019, 7)
+        .register::<aoc2019_day8::Day8>(2019, 8)
+        .register::<aoc2019_day9::Day9>(2019, 9)
+        .register::<aoc2019_day10::Day10>(2019, 10)
+        .register::<aoc2019_day25::Day25>(2019, 25)
+        .register::<aoc2020_day1::Day1>(2020, 1)
+        .register::<aoc2020_day2::Day2>(2020, 2)
+        .register::<aoc2020_day3::Day3>(2020, 3)
+        .register::<aoc2020_day4::Day4>(2020, 4)
+        .register::<aoc2020_day5::Day5>(2020, 5)
+        .register::<aoc2020_day6::Day6>(2020, 6)
+        .register::<aoc2020_day7::Day7>(2020, 7)
+        .register::<aoc2020_day8::Day8>(2020, 8)
+        .register::<aoc2020_day9::Day9>(2020, 9)
+        .register::<aoc2020_day10::Day10>(2020, 10)
+        .register::<aoc2020_day11::Day11>(2020, 11)
+        .register::<aoc2020_day12::Day12>(2020, 12)
+        .register::<aoc2020_day13::Day13>(2020, 13)
+        .register::<aoc2020_day14::Day14>(2020, 14)
+        .register::<aoc2020_day15::Day15>(2020, 15)
+        .register::<aoc2020_day16::Day16>(2020, 16)
+        .register::<aoc2020_day17::Day17>(2020, 17)
+        .register::<aoc2021_day1::Day1>(2021, 1)
+        .register::<aoc2021_day2::Day2>(2021, 2)
+        .register::<aoc2021_day3::Day3>(2021, 3)
+        .register::<aoc2021_day4::Day4>(2021, 4)
+        .register::<aoc2021_day5::Day5>(2021, 5)
+        .register::<aoc2021_day6::Day6>(2021, 6)
+        .register::<aoc2021_day7::Day7>(2021, 7)
+        .register::<aoc2021_day8::Day8>(2021, 8)
+        .register::<aoc2021_day9::Day9>(2021, 9)
+        .register::<aoc2021_day10::Day10>(2021, 10)
+        .register::<aoc2021_day11::Day11>(2021, 11)
+        .register::<aoc2021_day12::Day12>(2021, 12)
+        .register::<aoc2021_day13::Day13>(2021, 13)
+        .register::<aoc2021_day14::Day14>(2021, 14)
+        .register::<aoc2021_day15::Day15>(2021, 15)
+        .register::<aoc2021_day16::Day16>(2021, 16)
+        .register::<aoc2021_day17::Day17>(2021, 17)
+        .register::<aoc2021_day19::Day19>(2021, 19)
+        .register::<aoc2021_day20::Day20>(2021, 20)
+        .register::<aoc2021_day21::Day21>(2021, 21)
+        .register::<aoc2022_day1::Day1>(2022, 1)
+        .register::<aoc2022_day2::Day2>(2022, 2)
+        .register::<aoc2022_day3::Day3>(2022, 3)
+        .register::<aoc2022_day4::Day4>(2022, 4)
+        .register::<aoc2022_day5::Day5>(2022, 5)
+        .register::<aoc2022_day6::Day6>(2022, 6)
+        .register::<aoc2022_day7::Day7>(2022, 7)
+        .register::<aoc2022_day8::Day8>(2022, 8)
+        .register::<aoc2022_day9::Day9>(2022, 9)
+        .register::<aoc2022_day10::Day10>(2022, 10)
+        .register::<aoc2022_day11::Day11>(2022, 11)
+        .register::<aoc2022_day12::Day12>(2022, 12);
+    registry
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Days {
+    One(u32, u32),
+    Year(u32),
+    All,
+}
+
+impl Days {
+    pub fn contains(&self, year: u32, day: u32) -> bool {
+        match *self {
+            Days::One(y, d) => (y, d) == (year, day),
+            Days::Year(y) => y == year,
+            Days::All => true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Run {
+        days: Days,
+        parts: Vec<Part>,
+        input: Option<PathBuf>,
+    },
+    Verify {
+        days: Days,
+    },
+    Bench {
+        days: Days,
+        quick: bool,
+    },
+    Fetch {
+        year: u32,
+        day: u32,
+        from: Option<PathBuf>,
+    },
+    New {
+        year: u32,
+        day: u32,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArgError {
+    NoCommand,
+    UnknownCommand(String),
+    UnknownFlag(String),
+    MissingValue(&'static str),
+    BadNumber(String),
+    BadPart(String),
+    InputForManyDays,
+}
+
+impl fmt::Display for ArgError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ArgError::NoCommand => write!(f, "no command given"),
+            ArgError::UnknownCommand(c) => write!(f, "unknown command {}", c),
+            ArgError::UnknownFlag(flag) => write!(f, "unknown flag {}", flag),
+            ArgError::MissingValue(what) => write!(f, "missing {}", what),
+            ArgError::BadNumber(s) => write!(f, "{} isn't a number", s),
+            ArgError::BadPart(s) => write!(f, "there's no part {}, only 1 and 2", s),
+            ArgError::InputForManyDays => write!(f, "--input only works for a single day"),
+        }
+    }
+}
+
+impl Error for ArgError {}
+
+pub const USAGE: &str = "usage:
+    aoc run YEAR DAY [--part N] [--input PATH]
+    aoc run YEAR [--part N]
+    aoc run --all [--part N]
+    aoc verify [YEAR [DAY]]
+    aoc bench [YEAR [DAY]] [--quick]
+    aoc fetch YEAR DAY [--from DIR]
+    aoc new YEAR DAY
+
+Inputs are read from YYYY/dayN in the repo, or under $AOC_INPUTS if set.
+Fetching needs a session token in $AOC_SESSION or ~/.config/aoc/session,
+unless --from gives a directory of YYYY/dayN/input.txt to copy from instead.
+Fetched inputs are cached in $AOC_CACHE, or ~/.cache/aoc.
+Benchmarks want a release build, and their results are added to
+bench-history.json in the repo";
+
+fn number(arg: Option<String>, what: &'static str) -> Result<u32, ArgError> {
+    let arg = arg.ok_or(ArgError::MissingValue(what))?;
+    arg.parse().map_err(|_| ArgError::BadNumber(arg))
+}
+
+#[derive(PartialEq)]
+enum Mode {
+    Run,
+    Verify,
+    Bench,
+}
+
+pub fn parse_args<I: IntoIterator<Item = String>>(args: I) -> Result<Command, ArgError> {
+    let mut args = args.into_iter();
+    let mode = match args.next().as_deref() {
+        Some("run") => Mode::Run,
+        Some("verify") => Mode::Verify,
+        Some("bench") => Mode::Bench,
+        Some("fetch") => return parse_fetch(args),
+        Some("new") => return parse_new(args),
+        Some(command) => return Err(ArgError::UnknownCommand(command.to_string())),
+        None => return Err(ArgError::NoCommand),
+    };
+
+    let mut numbers = Vec::new();
+    let mut all = false;
+    let mut parts = vec![Part::One, Part::Two];
+    let mut input = None;
+    let mut quick = false;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--all" => all = true,
+            "--quick" if mode == Mode::Bench => quick = true,
+            "--part" | "--input" if mode != Mode::Run => return Err(ArgError::UnknownFlag(arg)),
+            "--part" => {
+                parts = match args.next().as_deref() {
+                    Some("1") => vec![Part::One],
+                    Some("2") => vec![Part::Two],
+                    Some(part) => return Err(ArgError::BadPart(part.to_string())),
+                    None => return Err(ArgError::MissingValue("part")),
+                }
+            }
+            "--input" => {
+                input = Some(PathBuf::from(
+                    args.next().ok_or(ArgError::MissingValue("input path"))?,
+                ))
+            }
+            flag if flag.starts_with("--") => return Err(ArgError::UnknownFlag(arg)),
+            _ => numbers.push(arg),
+        }
+    }
+
+    let mut numbers = numbers.into_iter();
+    let days = if all || (mode != Mode::Run && numbers.len() == 0) {
+        Days::All
+    } else {
+        let year = number(numbers.next(), "year")?;
+        match numbers.next() {
+            Some(day) => Days::One(year, number(Some(day), "day")?),
+            None => Days::Year(year),
+        }
+    };
+    if let Some(extra) = numbers.next() {
+        return Err(ArgError::UnknownCommand(extra));
+    }
+    if input.is_some() && !matches!(days, Days::One(..)) {
+        return Err(ArgError::InputForManyDays);
+    }
+
+    Ok(match mode {
+        Mode::Run => Command::Run { days, parts, input },
+        Mode::Verify => Command::Verify { days },
+        Mode::Bench => Command::Bench { days, quick },
+    })
+}
+
+fn parse_fetch(mut args: impl Iterator<Item = String>) -> Result<Command, ArgError> {
+    let mut numbers = Vec::new();
+    let mut from = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--from" => {
+                from = Some(PathBuf::from(
+                    args.next().ok_or(ArgError::MissingValue("directory"))?,
+                ))
+            }
+            flag if flag.starts_with("--") => return Err(ArgError::UnknownFlag(arg)),
+            _ => numbers.push(arg),
+        }
+    }
+
+    let (year, day) = year_and_day(numbers)?;
+    Ok(Command::Fetch { year, day, from })
+}
+
+fn parse_new(args: impl Iterator<Item = String>) -> Result<Command, ArgError> {
+    let mut numbers = Vec::new();
+    for arg in args {
+        if arg.starts_with("--") {
+            return Err(ArgError::UnknownFlag(arg));
+        }
+        numbers.push(arg);
+    }
+
+    let (year, day) = year_and_day(numbers)?;
+    Ok(Command::New { year, day })
+}
+
+fn year_and_day(numbers: Vec<String>) -> Result<(u32, u32), ArgError> {
+    let mut numbers = numbers.into_iter();
+    let year = number(numbers.next(), "year")?;
+    let day = number(numbers.next(), "day")?;
+    if let Some(extra) = numbers.next() {
+        return Err(ArgError::UnknownCommand(extra));
+    }
+    Ok((year, day))
+}
+
+pub fn repo_root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap()
+}
+
+// Laid out as YYYY/dayN like the repo, so fetched inputs land in day_dir
+pub fn inputs_root() -> PathBuf {
+    match env::var_os(input::INPUTS_VAR) {
+        Some(root) => PathBuf::from(root),
+        None => repo_root().to_path_buf(),
+    }
+}
+
+// Where a day's inputs live: its crate directory, YYYY/dayN, unless they've
+// been moved with AOC_INPUTS
+pub fn day_dir(year: u32, day: u32) -> PathBuf {
+    input::dir(
+        repo_root()
+            .join(year.to_string())
+            .join(format!("day{}", day)),
+    )
+}
+
+// A day's puzzle input, input.txt in its day_dir
+pub fn input_path(year: u32, day: u32) -> PathBuf {
+    day_dir(year, day).join("input.txt")
+}
+
+pub fn answers_path() -> PathBuf {
+    repo_root().join("answers.toml")
+}
+
+pub fn bench_history_path() -> PathBuf {
+    repo_root().join("bench-history.json")
+}
+
+// Days that keep more than one way of solving something, to compare them.
+// Each group runs on the named input in the day's directory.
+pub fn benches() -> Benches {
+    use aoc2020_day9::{find_error, find_weakness_set, find_weakness_set_improved};
+    use aoc2021_day14::{get_frequencies, get_score, grow, grow2, polymer2hashy, score};
+    use aoc2021_day6::{count_size, next_day, next_day2, next_day3, vec2hashy};
+
+    let mut benches = Benches::new();
+    benches
+        .group(
+            (2020, 9),
+            "weakness",
+            "input",
+            |text| -> ParseResult<_> {
+                let numbers = aoc2020_day9::Day9::parse(text)?;
+                let error = find_error(&numbers, 25);
+                Ok((numbers, error))
+            },
+            &[
+                ("find_weakness_set", |(numbers, error): &(Vec<i64>, i64)| {
+                    let set = find_weakness_set(numbers, *error);
+                    (set.iter().min().unwrap() + set.iter().max().unwrap()).to_string()
+                }),
+                ("find_weakness_set_improved", |(numbers, error)| {
+                    let set = find_weakness_set_improved(numbers, *error);
+                    (set.iter().min().unwrap() + set.iter().max().unwrap()).to_string()
+                }),
+            ],
+        )
+        // explore walks every arrangement, which the examples are small enough for
+        .group(
+            (2020, 10),
+            "arrangements",
+            "example1",
+            aoc2020_day10::Day10::parse,
+            &[
+                ("explore", |joltages| {
+                    aoc2020_day10::explore(joltages).to_string()
+                }),
+                ("explore_dp", |joltages| {
+                    aoc2020_day10::explore_dp(joltages).to_string()
+                }),
+            ],
+        )
+        .group(
+            (2020, 10),
+            "arrangements",
+            "example2",
+            aoc2020_day10::Day10::parse,
+            &[
+                ("explore", |joltages| {
+                    aoc2020_day10::explore(joltages).to_string()
+                }),
+                ("explore_dp", |joltages| {
+                    aoc2020_day10::explore_dp(joltages).to_string()
+                }),
+            ],
+        )
+        // but far too many in the real input, so only explore_dp is timed on it
+        .group(
+            (2020, 10),
+            "arrangements",
+            "input",
+            aoc2020_day10::Day10::parse,
+            &[("explore_dp", |joltages| {
+                aoc2020_day10::explore_dp(joltages).to_string()
+            })],
+        )
+        .group(
+            (2021, 6),
+            "80 days",
+            "input",
+            aoc2021_day6::Day6::parse,
+            &[
+                ("next_day", |state| {
+                    (0..80)
+                        .fold(state.clone(), |s, _| next_day(s))
+                        .len()
+                        .to_string()
+                }),
+                ("next_day2", |state| {
+                    (0..80)
+                        .fold(state.clone(), |s, _| next_day2(s))
+                        .len()
+                        .to_string()
+                }),
+                ("next_day3", |state| {
+                    let state = (0..80).fold(vec2hashy(state.clone()), |s, _| next_day3(s));
+                    count_size(&state).to_string()
+                }),
+            ],
+        )
+        .group(
+            (2021, 14),
+            "10 steps",
+            "input",
+            aoc2021_day14::Day14::parse,
+            &[
+                ("grow", |(template, rules)| {
+                    let polymer = (0..10).fold(template.clone(), |p, _| grow(p, rules));
+                    get_score(&polymer).to_string()
+                }),
+                ("grow2", |(template, rules)| {
+                    let polymer = (0..10).fold(polymer2hashy(template), |p, _| grow2(&p, rules));
+                    score(&get_frequencies(&polymer)).to_string()
+                }),
+            ],
+        );
+    benches
+}
+
+// The recorded answers for one input file.  Either part can be left out, e.g.
+// for an example that only covers part 1.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Expected {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl Expected {
+    pub fn get(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.part1.as_deref(),
+            Part::Two => self.part2.as_deref(),
+        }
+    }
+}
+
+// Keyed by (year, day) then by input name, the file stem in the day's directory
+pub type Answers = BTreeMap<(u32, u32), BTreeMap<String, Expected>>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnswersError(pub String);
+
+impl fmt::Display for AnswersError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "bad answers file: {}", self.0)
+    }
+}
+
+impl Error for AnswersError {}
+
+fn table<'a>(value: &'a Value, at: &str) -> Result<&'a Table, AnswersError> {
+    value
+        .as_table()
+        .ok_or_else(|| AnswersError(format!("{} should be a table", at)))
+}
+
+fn key_number(key: &str, at: &str) -> Result<u32, AnswersError> {
+    key.parse()
+        .map_err(|_| AnswersError(format!("{} isn't a number in {}", key, at)))
+}
+
+// The file is laid out as
+//
+//     [2020.15.input]
+//     part1 = 517
+//     part2 = 1047739
+//
+// with answers given as integers or strings
+pub fn parse_answers(text: &str) -> Result<Answers, AnswersError> {
+    let root: Table = text.parse().map_err(|e| AnswersError(format!("{}", e)))?;
+
+    let mut answers = Answers::new();
+    for (year, days) in &root {
+        for (day, inputs) in table(days, year)? {
+            let at = format!("{}.{}", year, day);
+            let key = (key_number(year, &at)?, key_number(day, &at)?);
+            for (name, parts) in table(inputs, &at)? {
+                let at = format!("{}.{}", at, name);
+                let mut expected = Expected::default();
+                for (part, value) in table(parts, &at)? {
+                    let value = match value {
+                        Value::String(s) => s.clone(),
+                        Value::Integer(i) => i.to_string(),
+                        _ => return Err(AnswersError(format!("{}.{} isn't an answer", at, part))),
+                    };
+                    match part.as_str() {
+                        "part1" => expected.part1 = Some(value),
+                        "part2" => expected.part2 = Some(value),
+                        _ => return Err(AnswersError(format!("unknown part {} in {}", part, at))),
+                    }
+                }
+                answers
+                    .entry(key)
+                    .or_default()
+                    .insert(name.clone(), expected);
+            }
+        }
+    }
+    Ok(answers)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    Pass,
+    Fail { expected: String, actual: String },
+    // The input didn't parse, and why
+    BadInput(String),
+    // The part couldn't find an answer, and why
+    NoAnswer(String),
+    // The solution panicked, with what it panicked with
+    Panicked(String),
+    Missing(String),
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "a panic without a message".to_string()
+    }
+}
+
+// The result of checking one part of a day against one input.  `part` is None
+// when the whole input couldn't be checked.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Check {
+    pub year: u32,
+    pub day: u32,
+    pub input: String,
+    pub part: Option<Part>,
+    pub outcome: Outcome,
+}
+
+// Run a day against each of its inputs with recorded answers.  Each part is run
+// on its own, so a panic in one doesn't stop the other being checked.
+pub fn verify_day(
+    year: u32,
+    day: u32,
+    runner: Option<Runner>,
+    inputs: Option<&BTreeMap<String, Expected>>,
+) -> Vec<Check> {
+    let check = |input: &str, part, outcome| Check {
+        year,
+        day,
+        input: input.to_string(),
+        part,
+        outcome,
+    };
+    let (runner, inputs) = match (runner, inputs) {
+        (None, _) => return vec![check("-", None, Outcome::Missing("no solution".into()))],
+        (_, None) => return vec![check("-", None, Outcome::Missing("no answers".into()))],
+        (Some(runner), Some(inputs)) => (runner, inputs),
+    };
+
+    let mut checks = Vec::new();
+    for (name, expected) in inputs {
+        let path = day_dir(year, day).join(format!("{}.txt", name));
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(_) => {
+                let missing = Outcome::Missing(format!("no file {}", path.display()));
+                checks.push(check(name, None, missing));
+                continue;
+            }
+        };
+
+        for part in [Part::One, Part::Two] {
+            let expected = match expected.get(part) {
+                Some(expected) => expected,
+                None => continue,
+            };
+            let outcome = match panic::catch_unwind(|| runner(&text, &[part])) {
+                Ok(Ok(mut run)) => match run.answers.remove(0).value {
+                    Ok(actual) if actual == expected => Outcome::Pass,
+                    Ok(actual) => Outcome::Fail {
+                        expected: expected.to_string(),
+                        actual,
+                    },
+                    Err(error) => Outcome::NoAnswer(error),
+                },
+                Ok(Err(error)) => Outcome::BadInput(error.to_string()),
+                Err(payload) => Outcome::Panicked(panic_message(&*payload)),
+            };
+            checks.push(check(name, Some(part), outcome));
+        }
+    }
+    checks
+}
+
+// Line by line, `-` for what was expected and `+` for what came out
+pub fn diff(expected: &str, actual: &str) -> String {
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+
+    let mut out = String::new();
+    for i in 0..expected.len().max(actual.len()) {
+        match (expected.get(i), actual.get(i)) {
+            (Some(e), Some(a)) if e == a => out += &format!("  {}\n", e),
+            (e, a) => {
+                if let Some(e) = e {
+                    out += &format!("- {}\n", e);
+                }
+                if let Some(a) = a {
+                    out += &format!("+ {}\n", a);
+                }
+            }
+        }
+    }
+    out
+}
+
+#[derive(Debug)]
+pub enum ScaffoldError {
+    Exists(PathBuf),
+    NotRegistered(PathBuf),
+    Io { path: PathBuf, source: io::Error },
+}
+
+impl fmt::Display for ScaffoldError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScaffoldError::Exists(path) => write!(f, "{} already exists", path.display()),
+            ScaffoldError::NotRegistered(path) => {
+                write!(f, "no days are registered in {}", path.display())
+            }
+            ScaffoldError::Io { path, source } => write!(f, "{}: {}", path.display(), source),
+        }
+    }
+}
+
+impl Error for ScaffoldError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ScaffoldError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+const CARGO_TEMPLATE: &str = r#"[package]
+name = "aoc{year}-day{day}"
+version = "0.1.0"
+edition = "2021"
+
+# See more keys and their definitions at https://doc.rust-lang.org/cargo/reference/manifest.html
+
+[dependencies]
+input = {path = "../../common/input"}
+solution = {path = "../../common/solution"}
+"#;
+
+const LIB_TEMPLATE: &str = include_str!("../template/lib.rs.in");
+
+fn fill(template: &str, year: u32, day: u32) -> String {
+    template
+        .replace("{year}", &year.to_string())
+        .replace("{day}", &day.to_string())
+}
+
+// The (year, day) of a dependency line in aoc/Cargo.toml,
+// `aoc2021-day15 = {path = "../2021/day15"}`
+fn dependency_day(line: &str) -> Option<(u32, u32)> {
+    let (name, _) = line.strip_prefix("aoc")?.split_once(" = ")?;
+    let (year, day) = name.split_once("-day")?;
+    Some((year.parse().ok()?, day.parse().ok()?))
+}
+
+// The (year, day) of a line in `registry`,
+// `.register::<aoc2021_day15::Day15>(2021, 15)`
+fn registered_day(line: &str) -> Option<(u32, u32)> {
+    let line = line.trim().strip_prefix(".register::<")?;
+    let (_, args) = line.trim_end_matches(';').split_once(">(")?;
+    let (year, day) = args.strip_suffix(')')?.split_once(", ")?;
+    Some((year.parse().ok()?, day.parse().ok()?))
+}
+
+// Add `new` among the lines `key` recognises, keeping them in (year, day)
+// order.  None if there are no such lines to go among.
+fn insert_day(
+    text: &str,
+    key: fn(&str) -> Option<(u32, u32)>,
+    at: (u32, u32),
+    new: &str,
+) -> Option<String> {
+    let mut lines: Vec<String> = text.lines().map(String::from).collect();
+    let days: Vec<(usize, (u32, u32))> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, line)| key(line).map(|day| (idx, day)))
+        .collect();
+    let &(last, _) = days.last()?;
+
+    match days.iter().find(|&&(_, day)| day > at) {
+        Some(&(idx, _)) => lines.insert(idx, new.to_string()),
+        None => {
+            // the last registration ends the chain
+            let mut new = new.to_string();
+            if let Some(line) = lines[last].strip_suffix(';') {
+                lines[last] = line.to_string();
+                new.push(';');
+            }
+            lines.insert(last + 1, new);
+        }
+    }
+
+    let mut text = lines.join("\n");
+    text.push('\n');
+    Some(text)
+}
+
+fn scaffold_io(path: &Path) -> impl FnOnce(io::Error) -> ScaffoldError + '_ {
+    move |source| ScaffoldError::Io {
+        path: path.to_path_buf(),
+        source,
+    }
+}
+
+// Start a new day under root, a checkout of this repo: its crate with a
+// Solution to fill in, empty example1.txt and input.txt if there aren't any yet,
+// and its entries in the aoc runner.  Nothing is written if the day's code or
+// registration already exists.  Returns the files written.
+pub fn scaffold(root: &Path, year: u32, day: u32) -> Result<Vec<PathBuf>, ScaffoldError> {
+    let dir = root.join(year.to_string()).join(format!("day{}", day));
+    for existing in [dir.join("Cargo.toml"), dir.join("src")] {
+        if existing.exists() {
+            return Err(ScaffoldError::Exists(existing));
+        }
+    }
+
+    let manifest = root.join("aoc").join("Cargo.toml");
+    let runner = root.join("aoc").join("src").join("lib.rs");
+    let mut edits = Vec::new();
+    for (path, key, line) in [
+        (
+            &manifest,
+            dependency_day as fn(&str) -> Option<(u32, u32)>,
+            fill(
+                r#"aoc{year}-day{day} = {path = "../{year}/day{day}"}"#,
+                year,
+                day,
+            ),
+        ),
+        (
+            &runner,
+            registered_day,
+            fill(
+                "        .register::<aoc{year}_day{day}::Day{day}>({year}, {day})",
+                year,
+                day,
+            ),
+        ),
+    ] {
+        let text = fs::read_to_string(path).map_err(scaffold_io(path))?;
+        if text.lines().any(|line| key(line) == Some((year, day))) {
+            return Err(ScaffoldError::Exists(path.clone()));
+        }
+        let text = insert_day(&text, key, (year, day), &line)
+            .ok_or_else(|| ScaffoldError::NotRegistered(path.clone()))?;
+        edits.push((path.clone(), text));
+    }
+
+    let mut files = vec![
+        (dir.join("Cargo.toml"), fill(CARGO_TEMPLATE, year, day)),
+        (
+            dir.join("src").join("lib.rs"),
+            fill(LIB_TEMPLATE, year, day),
+        ),
+    ];
+    for placeholder in ["example1.txt", "input.txt"] {
+        let path = dir.join(placeholder);
+        if !path.exists() {
+            files.push((path, String::new()));
+        }
+    }
+
+    fs::create_dir_all(dir.join("src")).map_err(scaffold_io(&dir))?;
+    let mut written = Vec::new();
+    for (path, text) in files.into_iter().chain(edits) {
+        fs::write(&path, text).map_err(scaffold_io(&path))?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solution::PartResult;
+
+    fn args(line: &str) -> Result<Command, ArgError> {
+        parse_args(line.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn run_args() {
+        assert_eq!(
+            args("run 2021 15 --part 2 --input path"),
+            Ok(Command::Run {
+                days: Days::One(2021, 15),
+                parts: vec![Part::Two],
+                input: Some(PathBuf::from("path")),
+            })
+        );
+        assert_eq!(
+            args("run 2018"),
+            Ok(Command::Run {
+                days: Days::Year(2018),
+                parts: vec![Part::One, Part::Two],
+                input: None,
+            })
+        );
+        assert_eq!(
+            args("run --all --part 1"),
+            Ok(Command::Run {
+                days: Days::All,
+                parts: vec![Part::One],
+                input: None,
+            })
+        );
+
+        assert_eq!(args(""), Err(ArgError::NoCommand));
+        assert_eq!(args("run"), Err(ArgError::MissingValue("year")));
+        assert_eq!(
+            args("run 2018 x"),
+            Err(ArgError::BadNumber("x".to_string()))
+        );
+        assert_eq!(
+            args("run 2018 1 --part 3"),
+            Err(ArgError::BadPart("3".to_string()))
+        );
+        assert_eq!(args("run --all --input x"), Err(ArgError::InputForManyDays));
+        assert_eq!(
+            args("run 2018 1 --fast"),
+            Err(ArgError::UnknownFlag("--fast".to_string()))
+        );
+    }
+
+    #[test]
+    fn verify_args() {
+        assert_eq!(args("verify"), Ok(Command::Verify { days: Days::All }));
+        assert_eq!(
+            args("verify 2020 15"),
+            Ok(Command::Verify {
+                days: Days::One(2020, 15)
+            })
+        );
+        assert_eq!(
+            args("verify 2020 --part 1"),
+            Err(ArgError::UnknownFlag("--part".to_string()))
+        );
+    }
+
+    #[test]
+    fn bench_args() {
+        assert_eq!(
+            args("bench"),
+            Ok(Command::Bench {
+                days: Days::All,
+                quick: false
+            })
+        );
+        assert_eq!(
+            args("bench 2021 --quick"),
+            Ok(Command::Bench {
+                days: Days::Year(2021),
+                quick: true
+            })
+        );
+        assert_eq!(
+            args("run 2021 --quick"),
+            Err(ArgError::UnknownFlag("--quick".to_string()))
+        );
+        assert_eq!(
+            args("bench 2021 6 --input x"),
+            Err(ArgError::UnknownFlag("--input".to_string()))
+        );
+    }
+
+    #[test]
+    fn fetch_args() {
+        assert_eq!(
+            args("fetch 2022 13"),
+            Ok(Command::Fetch {
+                year: 2022,
+                day: 13,
+                from: None
+            })
+        );
+        assert_eq!(
+            args("fetch 2022 13 --from stub"),
+            Ok(Command::Fetch {
+                year: 2022,
+                day: 13,
+                from: Some(PathBuf::from("stub"))
+            })
+        );
+        assert_eq!(args("fetch 2022"), Err(ArgError::MissingValue("day")));
+        assert_eq!(
+            args("fetch 2022 13 --from"),
+            Err(ArgError::MissingValue("directory"))
+        );
+        assert_eq!(
+            args("fetch --all"),
+            Err(ArgError::UnknownFlag("--all".to_string()))
+        );
+    }
+
+    #[test]
+    fn new_args() {
+        assert_eq!(
+            args("new 2022 13"),
+            Ok(Command::New {
+                year: 2022,
+                day: 13
+            })
+        );
+        assert_eq!(args("new 2022"), Err(ArgError::MissingValue("day")));
+        assert_eq!(
+            args("new 2022 13 14"),
+            Err(ArgError::UnknownCommand("14".to_string()))
+        );
+    }
+
+    #[test]
+    fn scaffold_day() {
+        let root = std::env::temp_dir().join(format!("aoc-new-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("aoc/src")).unwrap();
+        fs::write(
+            root.join("aoc/Cargo.toml"),
+            "[dependencies]\n\
+             solution = {path = \"../common/solution\"}\n\
+             aoc2022-day2 = {path = \"../2022/day2\"}\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("aoc/src/lib.rs"),
+            "    registry\n\
+             \x20       .register::<aoc2022_day2::Day2>(2022, 2)\n\
+             \x20       .register::<aoc2022_day12::Day12>(2022, 12);\n\
+             \x20   registry\n",
+        )
+        .unwrap();
+
+        let written = scaffold(&root, 2022, 1).unwrap();
+        assert_eq!(written.len(), 6);
+        scaffold(&root, 2022, 13).unwrap();
+        assert_eq!(
+            fs::read_to_string(root.join("aoc/Cargo.toml")).unwrap(),
+            "[dependencies]\n\
+             solution = {path = \"../common/solution\"}\n\
+             aoc2022-day1 = {path = \"../2022/day1\"}\n\
+             aoc2022-day2 = {path = \"../2022/day2\"}\n\
+             aoc2022-day13 = {path = \"../2022/day13\"}\n"
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("aoc/src/lib.rs")).unwrap(),
+            "    registry\n\
+             \x20       .register::<aoc2022_day1::Day1>(2022, 1)\n\
+             \x20       .register::<aoc2022_day2::Day2>(2022, 2)\n\
+             \x20       .register::<aoc2022_day12::Day12>(2022, 12)\n\
+             \x20       .register::<aoc2022_day13::Day13>(2022, 13);\n\
+             \x20   registry\n"
+        );
+
+        let lib = fs::read_to_string(root.join("2022/day13/src/lib.rs")).unwrap();
+        assert!(lib.contains("impl Solution for Day13 {"));
+        assert!(fs::read_to_string(root.join("2022/day13/Cargo.toml"))
+            .unwrap()
+            .contains("name = \"aoc2022-day13\""));
+        assert_eq!(
+            fs::read_to_string(root.join("2022/day13/input.txt")).unwrap(),
+            ""
+        );
+
+        // a fetched input is kept, but existing code is never replaced
+        fs::create_dir_all(root.join("2022/day14")).unwrap();
+        fs::write(root.join("2022/day14/input.txt"), "14\n").unwrap();
+        scaffold(&root, 2022, 14).unwrap();
+        assert_eq!(
+            fs::read_to_string(root.join("2022/day14/input.txt")).unwrap(),
+            "14\n"
+        );
+        fs::write(root.join("2022/day13/src/lib.rs"), "solved").unwrap();
+        assert!(matches!(
+            scaffold(&root, 2022, 13),
+            Err(ScaffoldError::Exists(_))
+        ));
+        assert_eq!(
+            fs::read_to_string(root.join("2022/day13/src/lib.rs")).unwrap(),
+            "solved"
+        );
+        assert!(matches!(
+            scaffold(&root, 2022, 2),
+            Err(ScaffoldError::Exists(path)) if path.ends_with("aoc/Cargo.toml")
+        ));
+        assert!(!root.join("2022/day2").exists());
+    }
+
+    #[test]
+    fn fetched_inputs_are_read() {
+        assert_eq!(
+            fetch::day_path(&inputs_root(), 2021, 15),
+            input_path(2021, 15)
+        );
+    }
+
+    #[test]
+    fn answers_file() {
+        let answers = parse_answers(
+            "[2018.1.input]\npart1 = 595\npart2 = 80598\n\n[2018.2.test_input2]\npart2 = \"fgij\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            answers[&(2018, 1)]["input"],
+            Expected {
+                part1: Some("595".to_string()),
+                part2: Some("80598".to_string()),
+            }
+        );
+        assert_eq!(answers[&(2018, 2)]["test_input2"].get(Part::One), None);
+        assert_eq!(
+            answers[&(2018, 2)]["test_input2"].get(Part::Two),
+            Some("fgij")
+        );
+
+        assert!(parse_answers("[2018.x.input]\npart1 = 1").is_err());
+        assert!(parse_answers("[2018.1.input]\npart3 = 1").is_err());
+        assert!(parse_answers("[2018.1.input]\npart1 = 1.5").is_err());
+
+        // the checked in file must at least parse
+        parse_answers(&fs::read_to_string(answers_path()).unwrap()).unwrap();
+    }
+
+    #[test]
+    fn verify_checks() {
+        let registry = registry();
+        let runner = registry.get(2018, 1);
+        let mut inputs = BTreeMap::new();
+        inputs.insert(
+            "test_input".to_string(),
+            Expected {
+                part1: Some("4".to_string()),
+                part2: Some("11".to_string()),
+            },
+        );
+        inputs.insert("nope".to_string(), Expected::default());
+
+        let outcomes: Vec<Outcome> = verify_day(2018, 1, runner, Some(&inputs))
+            .into_iter()
+            .map(|check| check.outcome)
+            .collect();
+        assert_eq!(outcomes.len(), 3);
+        assert!(matches!(&outcomes[0], Outcome::Missing(why) if why.starts_with("no file")));
+        assert_eq!(outcomes[1], Outcome::Pass);
+        assert_eq!(
+            outcomes[2],
+            Outcome::Fail {
+                expected: "11".to_string(),
+                actual: "10".to_string(),
+            }
+        );
+
+        let checks = verify_day(2018, 1, None, Some(&inputs));
+        assert_eq!(
+            checks[0].outcome,
+            Outcome::Missing("no solution".to_string())
+        );
+        let checks = verify_day(2018, 1, runner, None);
+        assert_eq!(
+            checks[0].outcome,
+            Outcome::Missing("no answers".to_string())
+        );
+    }
+
+    struct Explodes;
+
+    // Only takes short inputs, and always panics in part 1
+    impl Solution for Explodes {
+        type Input = usize;
+
+        fn parse(input: &str) -> ParseResult<usize> {
+            match input.lines().count() {
+                lines if lines > 10 => Err(format!("{} lines is too many", lines).into()),
+                _ => Ok(input.len()),
+            }
+        }
+
+        fn part1(&length: &usize) -> PartResult<impl fmt::Display> {
+            assert!(length == 0, "part 1 blew up");
+            Ok(length)
+        }
+
+        fn part2(_: &usize) -> PartResult<impl fmt::Display> {
+            Ok("still here")
+        }
+    }
+
+    #[test]
+    fn verify_failures() {
+        let expected = Expected {
+            part1: Some("4".to_string()),
+            part2: Some("still here".to_string()),
+        };
+        let mut inputs = BTreeMap::new();
+        inputs.insert("input".to_string(), expected.clone());
+        inputs.insert("test_input".to_string(), expected);
+
+        // a panic is reported and the other part still gets checked
+        let outcomes: Vec<Outcome> =
+            verify_day(2018, 1, Some(solution::run::<Explodes>), Some(&inputs))
+                .into_iter()
+                .map(|check| check.outcome)
+                .collect();
+        let bad_input = Outcome::BadInput("1025 lines is too many".to_string());
+        assert_eq!(
+            outcomes,
+            vec![
+                bad_input.clone(),
+                bad_input,
+                Outcome::Panicked("part 1 blew up".to_string()),
+                Outcome::Pass
+            ]
+        );
+    }
+
+    #[test]
+    fn line_diff() {
+        assert_eq!(diff("a\nb", "a\nc\nd"), "  a\n- b\n+ c\n+ d\n");
+        assert_eq!(diff("12", "12"), "  12\n");
+    }
+
+    #[test]
+    fn registered_days() {
+        let registry = registry();
+        let run =
+            registry.get(2018, 1).unwrap()("+1\n-2\n+3\n+1", &[Part::One, Part::Two]).unwrap();
+        let values: Vec<_> = run.answers.into_iter().map(|a| a.value).collect();
+        assert_eq!(values, vec![Ok("3".to_string()), Ok("2".to_string())]);
+
+        assert!(input_path(2018, 1).ends_with("2018/day1/input.txt"));
+        assert!(input_path(2018, 1).exists());
+    }
+}
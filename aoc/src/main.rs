@@ -0,0 +1,275 @@
+use aoc::{
+    answers_path, bench_history_path, benches, day_dir, diff, input_path, inputs_root,
+    parse_answers, parse_args, registry, repo_root, scaffold, verify_day, Command, Days, Outcome,
+    USAGE,
+};
+use bench::{load_history, save_history, table, Config, Record};
+use fetch::{default_cache_dir, FetchError, Fetcher, FileFetcher, HttpFetcher, Manager};
+use solution::{Part, Registry};
+use std::collections::BTreeSet;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+use std::time::Duration;
+
+fn main() {
+    let command = match parse_args(env::args().skip(1)) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("{}\n{}", e, USAGE);
+            process::exit(2);
+        }
+    };
+
+    let registry = registry();
+    let ok = match command {
+        Command::Run { days, parts, input } => run(&registry, days, &parts, input),
+        Command::Verify { days } => verify(&registry, days),
+        Command::Bench { days, quick } => bench(days, quick),
+        Command::Fetch { year, day, from } => {
+            let installed = match from {
+                // a local copy doesn't need to be spaced out
+                Some(dir) => fetch(
+                    Manager::new(FileFetcher::new(dir), default_cache_dir())
+                        .throttle(Duration::ZERO),
+                    year,
+                    day,
+                ),
+                None => HttpFetcher::from_env().and_then(|fetcher| {
+                    fetch(Manager::new(fetcher, default_cache_dir()), year, day)
+                }),
+            };
+            match installed {
+                Ok(path) => {
+                    println!("{}", path.display());
+                    true
+                }
+                Err(e) => {
+                    eprintln!("{} day {}: {}", year, day, e);
+                    false
+                }
+            }
+        }
+        Command::New { year, day } => match scaffold(repo_root(), year, day) {
+            Ok(written) => {
+                for path in written {
+                    println!("wrote {}", path.display());
+                }
+                true
+            }
+            Err(e) => {
+                eprintln!("{} day {}: {}", year, day, e);
+                false
+            }
+        },
+    };
+    if !ok {
+        process::exit(1);
+    }
+}
+
+fn run(registry: &Registry, days: Days, parts: &[Part], input: Option<PathBuf>) -> bool {
+    let selected: Vec<(u32, u32)> = registry
+        .days()
+        .filter(|&(year, day)| days.contains(year, day))
+        .collect();
+
+    if selected.is_empty() {
+        eprintln!("Nothing registered for {:?}", days);
+        return false;
+    }
+
+    let mut ok = true;
+    let mut total = Duration::ZERO;
+    for (year, day) in selected {
+        let path = input.clone().unwrap_or_else(|| input_path(year, day));
+        let text = match input::read_file(&path) {
+            Ok(text) => text,
+            Err(e) => {
+                eprintln!("{} day {}: {}", year, day, e);
+                ok = false;
+                continue;
+            }
+        };
+
+        let run = match registry.get(year, day).unwrap()(&text, parts) {
+            Ok(run) => run,
+            Err(e) => {
+                eprintln!("{} day {}: {}: {}", year, day, path.display(), e);
+                ok = false;
+                continue;
+            }
+        };
+        println!("{} day {} (parsed in {:?})", year, day, run.parse_time);
+        total += run.parse_time;
+        for answer in run.answers {
+            match answer.value {
+                Ok(value) => println!("  Part {}: {} ({:?})", answer.part, value, answer.time),
+                Err(e) => {
+                    eprintln!("  Part {}: {} ({:?})", answer.part, e, answer.time);
+                    ok = false;
+                }
+            }
+            total += answer.time;
+        }
+    }
+
+    if days == Days::All || matches!(days, Days::Year(_)) {
+        println!("Total: {:?}", total);
+    }
+    ok
+}
+
+fn bench(days: Days, quick: bool) -> bool {
+    let config = if quick {
+        Config::quick()
+    } else {
+        Config::default()
+    };
+    let path = bench_history_path();
+    let mut history = match load_history(&path) {
+        Ok(history) => history,
+        Err(e) => {
+            eprintln!("{}", e);
+            return false;
+        }
+    };
+
+    let benches = benches();
+    let groups: Vec<_> = benches
+        .groups()
+        .filter(|group| days.contains(group.year, group.day))
+        .collect();
+    if groups.is_empty() {
+        eprintln!("No benchmarks for {:?}", days);
+        return false;
+    }
+
+    let mut ok = true;
+    let mut records = Vec::new();
+    let timestamp = bench::now();
+    for group in groups {
+        let input = day_dir(group.year, group.day).join(format!("{}.txt", group.input));
+        let text = match input::read_file(&input) {
+            Ok(text) => text,
+            Err(e) => {
+                eprintln!("{} day {}: {}", group.year, group.day, e);
+                ok = false;
+                continue;
+            }
+        };
+
+        let measurements = match group.run(&text, &config) {
+            Ok(measurements) => measurements,
+            Err(e) => {
+                eprintln!(
+                    "{} day {}: {}: {}",
+                    group.year,
+                    group.day,
+                    input.display(),
+                    e
+                );
+                ok = false;
+                continue;
+            }
+        };
+        println!("{}", table(group, &measurements, &history));
+        if measurements
+            .iter()
+            .any(|m| m.output != measurements[0].output)
+        {
+            ok = false;
+        }
+        records.extend(
+            measurements
+                .iter()
+                .map(|m| Record::new(group, m, timestamp)),
+        );
+    }
+
+    history.extend(records);
+    if let Err(e) = save_history(&path, &history) {
+        eprintln!("{}", e);
+        return false;
+    }
+    ok
+}
+
+fn fetch<F: Fetcher>(manager: Manager<F>, year: u32, day: u32) -> Result<PathBuf, FetchError> {
+    manager.install(year, day, &inputs_root())
+}
+
+fn verify(registry: &Registry, days: Days) -> bool {
+    let path = answers_path();
+    let answers = match fs::read_to_string(&path) {
+        Ok(text) => parse_answers(&text),
+        Err(e) => {
+            eprintln!("unable to read {}: {}", path.display(), e);
+            return false;
+        }
+    };
+    let answers = match answers {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("{}: {}", path.display(), e);
+            return false;
+        }
+    };
+
+    // Days with a solution or with answers, so neither can go missing quietly
+    let selected: BTreeSet<(u32, u32)> = registry
+        .days()
+        .chain(answers.keys().copied())
+        .filter(|&(year, day)| days.contains(year, day))
+        .collect();
+
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+    for (year, day) in selected {
+        let checks = verify_day(
+            year,
+            day,
+            registry.get(year, day),
+            answers.get(&(year, day)),
+        );
+        for check in checks {
+            let what = match check.part {
+                Some(part) => format!("{} day {} {} part {}", year, day, check.input, part),
+                None => format!("{} day {} {}", year, day, check.input),
+            };
+            match check.outcome {
+                Outcome::Pass => {
+                    passed += 1;
+                    println!("pass     {}", what);
+                }
+                Outcome::Fail { expected, actual } => {
+                    failed += 1;
+                    println!("FAIL     {}", what);
+                    print!("{}", diff(&expected, &actual));
+                }
+                Outcome::BadInput(error) => {
+                    failed += 1;
+                    println!("FAIL     {} (bad input: {})", what, error);
+                }
+                Outcome::NoAnswer(error) => {
+                    failed += 1;
+                    println!("FAIL     {} (no answer: {})", what, error);
+                }
+                Outcome::Panicked(message) => {
+                    failed += 1;
+                    println!("FAIL     {} (panicked: {})", what, message);
+                }
+                Outcome::Missing(reason) => {
+                    missing += 1;
+                    println!("missing  {} ({})", what, reason);
+                }
+            }
+        }
+    }
+
+    println!(
+        "\n{} passed, {} failed, {} missing",
+        passed, failed, missing
+    );
+    failed == 0
+}
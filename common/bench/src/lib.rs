@@ -0,0 +1,459 @@
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt::{self, Write};
+use std::fs;
+use std::hint::black_box;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+// How long to run each variant for.  The warm up estimates how many runs fit in
+// a sample, so quick variants are timed over many runs at once.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Config {
+    pub warm_up: Duration,
+    pub measure: Duration,
+    pub samples: u32,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            warm_up: Duration::from_millis(500),
+            measure: Duration::from_secs(2),
+            samples: 20,
+        }
+    }
+}
+
+impl Config {
+    pub fn quick() -> Self {
+        Self {
+            warm_up: Duration::from_millis(50),
+            measure: Duration::from_millis(200),
+            samples: 10,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub median: Duration,
+    pub mean: Duration,
+    pub min: Duration,
+    pub max: Duration,
+}
+
+// Of the time per run in each sample
+pub fn stats(times: &[Duration]) -> Stats {
+    let mut sorted = times.to_vec();
+    sorted.sort_unstable();
+    let mid = sorted.len() / 2;
+    let median = if sorted.len().is_multiple_of(2) {
+        (sorted[mid - 1] + sorted[mid]) / 2
+    } else {
+        sorted[mid]
+    };
+    Stats {
+        median,
+        mean: sorted.iter().sum::<Duration>() / sorted.len() as u32,
+        min: sorted[0],
+        max: sorted[sorted.len() - 1],
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Measurement {
+    pub variant: &'static str,
+    // what the variant answered, which every variant in a group should agree on
+    pub output: String,
+    pub runs: u64,
+    pub stats: Stats,
+}
+
+pub fn measure(
+    config: &Config,
+    variant: &'static str,
+    mut f: impl FnMut() -> String,
+) -> Measurement {
+    let start = Instant::now();
+    let mut output = black_box(f());
+    let mut warm_runs = 1;
+    while start.elapsed() < config.warm_up {
+        output = black_box(f());
+        warm_runs += 1;
+    }
+    let per_run = start.elapsed() / warm_runs;
+
+    let per_sample = config.measure / config.samples.max(1);
+    let runs_per_sample =
+        (per_sample.as_nanos() / per_run.as_nanos().max(1)).clamp(1, u32::MAX as u128) as u32;
+
+    let times: Vec<Duration> = (0..config.samples.max(1))
+        .map(|_| {
+            let start = Instant::now();
+            for _ in 0..runs_per_sample {
+                black_box(f());
+            }
+            start.elapsed() / runs_per_sample
+        })
+        .collect();
+
+    Measurement {
+        variant,
+        output,
+        runs: warm_runs as u64 + times.len() as u64 * runs_per_sample as u64,
+        stats: stats(&times),
+    }
+}
+
+pub type Variant<T> = (&'static str, fn(&T) -> String);
+
+type Run = Box<dyn Fn(&str, &Config) -> Result<Vec<Measurement>, Box<dyn Error + Send + Sync>>>;
+
+// Different ways of doing the same thing for one day, timed against the same
+// parsed input
+pub struct Group {
+    pub year: u32,
+    pub day: u32,
+    pub name: &'static str,
+    // the stem of the input file in the day's directory
+    pub input: &'static str,
+    run: Run,
+}
+
+impl Group {
+    // Fails if the input doesn't parse
+    pub fn run(
+        &self,
+        text: &str,
+        config: &Config,
+    ) -> Result<Vec<Measurement>, Box<dyn Error + Send + Sync>> {
+        (self.run)(text, config)
+    }
+}
+
+#[derive(Default)]
+pub struct Benches {
+    groups: Vec<Group>,
+}
+
+impl Benches {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn group<T: 'static, E: Into<Box<dyn Error + Send + Sync>> + 'static>(
+        &mut self,
+        (year, day): (u32, u32),
+        name: &'static str,
+        input: &'static str,
+        parse: fn(&str) -> Result<T, E>,
+        variants: &[Variant<T>],
+    ) -> &mut Self {
+        let variants = variants.to_vec();
+        self.groups.push(Group {
+            year,
+            day,
+            name,
+            input,
+            run: Box::new(move |text, config| {
+                let parsed = parse(text).map_err(Into::into)?;
+                Ok(variants
+                    .iter()
+                    .map(|&(variant, f)| measure(config, variant, || f(&parsed)))
+                    .collect())
+            }),
+        });
+        self
+    }
+
+    pub fn groups(&self) -> impl Iterator<Item = &Group> {
+        self.groups.iter()
+    }
+}
+
+// One variant's timing from one run, as kept in the history file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    pub timestamp: u64,
+    pub year: u32,
+    pub day: u32,
+    pub group: String,
+    pub input: String,
+    pub variant: String,
+    pub runs: u64,
+    pub median_ns: u64,
+    pub mean_ns: u64,
+    pub min_ns: u64,
+    pub max_ns: u64,
+}
+
+impl Record {
+    pub fn new(group: &Group, measurement: &Measurement, timestamp: u64) -> Self {
+        let ns = |d: Duration| d.as_nanos() as u64;
+        Self {
+            timestamp,
+            year: group.year,
+            day: group.day,
+            group: group.name.to_string(),
+            input: group.input.to_string(),
+            variant: measurement.variant.to_string(),
+            runs: measurement.runs,
+            median_ns: ns(measurement.stats.median),
+            mean_ns: ns(measurement.stats.mean),
+            min_ns: ns(measurement.stats.min),
+            max_ns: ns(measurement.stats.max),
+        }
+    }
+
+    fn is(&self, group: &Group, variant: &str) -> bool {
+        (self.year, self.day) == (group.year, group.day)
+            && self.group == group.name
+            && self.input == group.input
+            && self.variant == variant
+    }
+}
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[derive(Debug)]
+pub enum HistoryError {
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+    Json {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+}
+
+impl fmt::Display for HistoryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HistoryError::Io { path, source } => write!(f, "{}: {}", path.display(), source),
+            HistoryError::Json { path, source } => {
+                write!(f, "bad history file {}: {}", path.display(), source)
+            }
+        }
+    }
+}
+
+impl Error for HistoryError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            HistoryError::Io { source, .. } => Some(source),
+            HistoryError::Json { source, .. } => Some(source),
+        }
+    }
+}
+
+// Every record so far, oldest first.  No file is no history yet.
+pub fn load_history(path: &Path) -> Result<Vec<Record>, HistoryError> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(source) => {
+            return Err(HistoryError::Io {
+                path: path.to_path_buf(),
+                source,
+            })
+        }
+    };
+    serde_json::from_str(&text).map_err(|source| HistoryError::Json {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+pub fn save_history(path: &Path, history: &[Record]) -> Result<(), HistoryError> {
+    let mut text = serde_json::to_string_pretty(history).map_err(|source| HistoryError::Json {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    text.push('\n');
+    fs::write(path, text).map_err(|source| HistoryError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+// The median against the last recorded one, as a percentage
+fn change(median: Duration, previous: Option<&Record>) -> String {
+    match previous {
+        Some(previous) if previous.median_ns > 0 => {
+            let change = median.as_nanos() as f64 / previous.median_ns as f64 - 1.0;
+            format!("{:+.1}%", change * 100.0)
+        }
+        _ => "new".to_string(),
+    }
+}
+
+// A group's results, fastest first, with how each moved since the history
+pub fn table(group: &Group, measurements: &[Measurement], history: &[Record]) -> String {
+    let mut sorted: Vec<&Measurement> = measurements.iter().collect();
+    sorted.sort_by_key(|m| m.stats.median);
+
+    let width = sorted
+        .iter()
+        .map(|m| m.variant.len())
+        .max()
+        .unwrap_or(0)
+        .max(7);
+    let mut table = format!(
+        "{} day {} {} ({})\n  {:width$}  {:>10}  {:>10}  {:>10}  {:>10}  {:>8}\n",
+        group.year,
+        group.day,
+        group.name,
+        group.input,
+        "variant",
+        "median",
+        "mean",
+        "min",
+        "max",
+        "change",
+    );
+    for m in &sorted {
+        let previous = history.iter().rev().find(|r| r.is(group, m.variant));
+        writeln!(
+            table,
+            "  {:width$}  {:>10}  {:>10}  {:>10}  {:>10}  {:>8}",
+            m.variant,
+            format!("{:.2?}", m.stats.median),
+            format!("{:.2?}", m.stats.mean),
+            format!("{:.2?}", m.stats.min),
+            format!("{:.2?}", m.stats.max),
+            change(m.stats.median, previous),
+        )
+        .unwrap();
+    }
+
+    if let [fastest, .., slowest] = sorted[..] {
+        let ratio = slowest.stats.median.as_secs_f64() / fastest.stats.median.as_secs_f64();
+        writeln!(
+            table,
+            "  {} is {:.1}x faster than {}",
+            fastest.variant, ratio, slowest.variant
+        )
+        .unwrap();
+    }
+    if let Some(odd) = measurements
+        .iter()
+        .find(|m| m.output != measurements[0].output)
+    {
+        writeln!(
+            table,
+            "  outputs differ: {} gave {}, {} gave {}",
+            measurements[0].variant, measurements[0].output, odd.variant, odd.output
+        )
+        .unwrap();
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sum(n: &u64) -> String {
+        (0..*n).sum::<u64>().to_string()
+    }
+
+    fn formula(n: &u64) -> String {
+        (n * (n - 1) / 2).to_string()
+    }
+
+    fn off_by_one(n: &u64) -> String {
+        (n * (n + 1) / 2).to_string()
+    }
+
+    fn tiny() -> Config {
+        Config {
+            warm_up: Duration::from_millis(1),
+            measure: Duration::from_millis(5),
+            samples: 5,
+        }
+    }
+
+    #[test]
+    fn summary_stats() {
+        let ms = Duration::from_millis;
+        let stats = stats(&[ms(4), ms(1), ms(3), ms(2)]);
+        assert_eq!(stats.median, Duration::from_micros(2500));
+        assert_eq!(stats.mean, Duration::from_micros(2500));
+        assert_eq!((stats.min, stats.max), (ms(1), ms(4)));
+    }
+
+    #[test]
+    fn compare_variants() {
+        let mut benches = Benches::new();
+        benches.group(
+            (2020, 1),
+            "sum",
+            "input",
+            |text| text.trim().parse(),
+            &[
+                ("sum", sum),
+                ("formula", formula),
+                ("off_by_one", off_by_one),
+            ],
+        );
+        let group = benches.groups().next().unwrap();
+        let measurements = group.run("1000\n", &tiny()).unwrap();
+        let outputs: Vec<_> = measurements.iter().map(|m| m.output.as_str()).collect();
+        assert_eq!(outputs, ["499500", "499500", "500500"]);
+        assert!(measurements.iter().all(|m| m.runs > 5));
+
+        let record = Record::new(group, &measurements[1], 1_700_000_000);
+        assert_eq!(record.variant, "formula");
+        let table = table(group, &measurements, &[record]);
+        assert!(table.starts_with("2020 day 1 sum (input)\n"));
+        assert!(table.contains("\n  outputs differ: sum gave 499500, off_by_one gave 500500\n"));
+        let formula = table
+            .lines()
+            .find(|line| line.trim_start().starts_with("formula"))
+            .unwrap();
+        assert!(formula.ends_with('%'));
+        let sum = table
+            .lines()
+            .find(|line| line.trim_start().starts_with("sum "))
+            .unwrap();
+        assert!(sum.ends_with("new"));
+    }
+
+    #[test]
+    fn history_file() {
+        let path = std::env::temp_dir().join(format!("aoc-bench-{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+        assert_eq!(load_history(&path).unwrap(), Vec::new());
+
+        let record = Record {
+            timestamp: 1_700_000_000,
+            year: 2021,
+            day: 6,
+            group: "lanternfish".to_string(),
+            input: "input".to_string(),
+            variant: "next_day3".to_string(),
+            runs: 100,
+            median_ns: 1500,
+            mean_ns: 1600,
+            min_ns: 1400,
+            max_ns: 2000,
+        };
+        save_history(&path, &[record.clone(), record.clone()]).unwrap();
+        assert_eq!(load_history(&path).unwrap(), vec![record.clone(), record]);
+
+        fs::write(&path, "[{").unwrap();
+        assert!(matches!(
+            load_history(&path),
+            Err(HistoryError::Json { .. })
+        ));
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,368 @@
+use sha2::{Digest, Sha256};
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub const SESSION_VAR: &str = "AOC_SESSION";
+pub const CACHE_VAR: &str = "AOC_CACHE";
+
+const USER_AGENT: &str = concat!("aoc-fetch/", env!("CARGO_PKG_VERSION"));
+
+#[derive(Debug)]
+pub enum FetchError {
+    NoSession,
+    Http(String),
+    Io { path: PathBuf, source: io::Error },
+    Empty(u32, u32),
+    Conflict(PathBuf),
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FetchError::NoSession => write!(
+                f,
+                "no session token, set {} or put it in ~/.config/aoc/session",
+                SESSION_VAR
+            ),
+            FetchError::Http(e) => write!(f, "request failed: {}", e),
+            FetchError::Io { path, source } => write!(f, "{}: {}", path.display(), source),
+            FetchError::Empty(year, day) => write!(f, "{} day {} came back empty", year, day),
+            FetchError::Conflict(path) => {
+                write!(f, "{} already exists and is different", path.display())
+            }
+        }
+    }
+}
+
+impl Error for FetchError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            FetchError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+fn io_error(path: &Path) -> impl FnOnce(io::Error) -> FetchError + '_ {
+    move |source| FetchError::Io {
+        path: path.to_path_buf(),
+        source,
+    }
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), FetchError> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(io_error(dir))?;
+    }
+    fs::write(path, contents).map_err(io_error(path))
+}
+
+fn checksum(text: &str) -> String {
+    format!("{:x}", Sha256::digest(text.as_bytes()))
+}
+
+// YYYY/dayN/input.txt under root
+pub fn day_path(root: &Path, year: u32, day: u32) -> PathBuf {
+    root.join(year.to_string())
+        .join(format!("day{}", day))
+        .join("input.txt")
+}
+
+// Where puzzle inputs come from
+pub trait Fetcher {
+    fn fetch(&self, year: u32, day: u32) -> Result<String, FetchError>;
+}
+
+// Downloads inputs from adventofcode.com for the logged in session
+pub struct HttpFetcher {
+    session: String,
+    base_url: String,
+}
+
+impl HttpFetcher {
+    pub fn new(session: &str) -> Self {
+        Self {
+            session: session.to_string(),
+            base_url: "https://adventofcode.com".to_string(),
+        }
+    }
+
+    // The session cookie from AOC_SESSION, or else ~/.config/aoc/session
+    pub fn from_env() -> Result<Self, FetchError> {
+        let session = match env::var(SESSION_VAR) {
+            Ok(session) => session,
+            Err(_) => {
+                let home = env::var_os("HOME").ok_or(FetchError::NoSession)?;
+                fs::read_to_string(Path::new(&home).join(".config/aoc/session"))
+                    .map_err(|_| FetchError::NoSession)?
+            }
+        };
+        match session.trim() {
+            "" => Err(FetchError::NoSession),
+            session => Ok(Self::new(session)),
+        }
+    }
+}
+
+impl Fetcher for HttpFetcher {
+    fn fetch(&self, year: u32, day: u32) -> Result<String, FetchError> {
+        let url = format!("{}/{}/day/{}/input", self.base_url, year, day);
+        ureq::get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .set("User-Agent", USER_AGENT)
+            .call()
+            .map_err(|e| FetchError::Http(e.to_string()))?
+            .into_string()
+            .map_err(|e| FetchError::Http(e.to_string()))
+    }
+}
+
+// Serves inputs out of a directory laid out as YYYY/dayN/input.txt, so tests
+// and offline setups never touch the network
+pub struct FileFetcher {
+    root: PathBuf,
+}
+
+impl FileFetcher {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+}
+
+impl Fetcher for FileFetcher {
+    fn fetch(&self, year: u32, day: u32) -> Result<String, FetchError> {
+        let path = day_path(&self.root, year, day);
+        fs::read_to_string(&path).map_err(io_error(&path))
+    }
+}
+
+// The cache directory from AOC_CACHE, or else ~/.cache/aoc
+pub fn default_cache_dir() -> PathBuf {
+    if let Some(dir) = env::var_os(CACHE_VAR) {
+        return PathBuf::from(dir);
+    }
+    match env::var_os("HOME") {
+        Some(home) => Path::new(&home).join(".cache/aoc"),
+        None => env::temp_dir().join("aoc-cache"),
+    }
+}
+
+// Keeps a checksummed copy of every input it fetches and spaces out requests,
+// even across runs, so the fetcher is only used when it has to be
+pub struct Manager<F> {
+    fetcher: F,
+    cache_dir: PathBuf,
+    interval: Duration,
+}
+
+impl<F: Fetcher> Manager<F> {
+    pub fn new(fetcher: F, cache_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            fetcher,
+            cache_dir: cache_dir.into(),
+            interval: Duration::from_secs(5),
+        }
+    }
+
+    // The least time to leave between two fetches
+    pub fn throttle(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    fn cached_path(&self, year: u32, day: u32) -> PathBuf {
+        self.cache_dir
+            .join(year.to_string())
+            .join(format!("day{}.txt", day))
+    }
+
+    fn checksum_path(&self, year: u32, day: u32) -> PathBuf {
+        self.cached_path(year, day).with_extension("sha256")
+    }
+
+    fn stamp_path(&self) -> PathBuf {
+        self.cache_dir.join("last-fetch")
+    }
+
+    // The cached input, as long as it still matches its checksum
+    pub fn cached(&self, year: u32, day: u32) -> Option<String> {
+        let text = fs::read_to_string(self.cached_path(year, day)).ok()?;
+        let sum = fs::read_to_string(self.checksum_path(year, day)).ok()?;
+        (sum.trim() == checksum(&text)).then_some(text)
+    }
+
+    pub fn get(&self, year: u32, day: u32) -> Result<String, FetchError> {
+        if let Some(text) = self.cached(year, day) {
+            return Ok(text);
+        }
+
+        thread::sleep(self.wait_time(SystemTime::now()));
+        let fetched = self.fetcher.fetch(year, day);
+        let millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        write(&self.stamp_path(), &millis.to_string())?;
+
+        let text = fetched?;
+        if text.trim().is_empty() {
+            return Err(FetchError::Empty(year, day));
+        }
+        write(&self.cached_path(year, day), &text)?;
+        write(&self.checksum_path(year, day), &checksum(&text))?;
+        Ok(text)
+    }
+
+    // Put the input at YYYY/dayN/input.txt under root, filling in an empty
+    // placeholder, leaving an identical file alone and refusing to replace a
+    // different one
+    pub fn install(&self, year: u32, day: u32, root: &Path) -> Result<PathBuf, FetchError> {
+        let text = self.get(year, day)?;
+        let path = day_path(root, year, day);
+        match fs::read_to_string(&path) {
+            Ok(existing) if existing == text => Ok(path),
+            Ok(existing) if !existing.trim().is_empty() => Err(FetchError::Conflict(path)),
+            _ => write(&path, &text).map(|_| path),
+        }
+    }
+
+    // When the last fetch finished, to the millisecond
+    fn last_fetch(&self) -> Option<SystemTime> {
+        fs::read_to_string(self.stamp_path())
+            .ok()
+            .and_then(|stamp| stamp.trim().parse().ok())
+            .map(|millis| UNIX_EPOCH + Duration::from_millis(millis))
+    }
+
+    fn wait_time(&self, now: SystemTime) -> Duration {
+        match self.last_fetch().map(|last| now.duration_since(last)) {
+            Some(Ok(elapsed)) => self.interval.saturating_sub(elapsed),
+            // the clock went backwards
+            Some(Err(_)) => self.interval,
+            None => Duration::ZERO,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::process;
+
+    struct Counting {
+        inner: FileFetcher,
+        calls: Cell<usize>,
+    }
+
+    impl Fetcher for Counting {
+        fn fetch(&self, year: u32, day: u32) -> Result<String, FetchError> {
+            self.calls.set(self.calls.get() + 1);
+            self.inner.fetch(year, day)
+        }
+    }
+
+    fn scratch(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-fetch-{}-{}", name, process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    // A stub with 2022 day 1 in it and a manager with an empty cache
+    fn setup(name: &str) -> (PathBuf, Manager<Counting>) {
+        let dir = scratch(name);
+        write(&day_path(&dir.join("stub"), 2022, 1), "1000\n2000\n").unwrap();
+        let fetcher = Counting {
+            inner: FileFetcher::new(dir.join("stub")),
+            calls: Cell::new(0),
+        };
+        let manager = Manager::new(fetcher, dir.join("cache")).throttle(Duration::ZERO);
+        (dir, manager)
+    }
+
+    #[test]
+    fn caches_inputs() {
+        let (dir, manager) = setup("cache");
+        assert_eq!(manager.cached(2022, 1), None);
+        assert_eq!(manager.get(2022, 1).unwrap(), "1000\n2000\n");
+        assert_eq!(manager.get(2022, 1).unwrap(), "1000\n2000\n");
+        assert_eq!(manager.fetcher.calls.get(), 1);
+
+        // a damaged cache entry is fetched again
+        fs::write(dir.join("cache/2022/day1.txt"), "1000\n").unwrap();
+        assert_eq!(manager.cached(2022, 1), None);
+        assert_eq!(manager.get(2022, 1).unwrap(), "1000\n2000\n");
+        assert_eq!(manager.fetcher.calls.get(), 2);
+    }
+
+    #[test]
+    fn missing_inputs() {
+        let (dir, manager) = setup("missing");
+        match manager.get(2022, 2) {
+            Err(FetchError::Io { path, .. }) => {
+                assert_eq!(path, day_path(&dir.join("stub"), 2022, 2))
+            }
+            other => panic!("expected an io error, got {:?}", other),
+        }
+
+        write(&day_path(&dir.join("stub"), 2022, 3), "\n").unwrap();
+        assert!(matches!(
+            manager.get(2022, 3),
+            Err(FetchError::Empty(2022, 3))
+        ));
+        assert_eq!(manager.cached(2022, 3), None);
+    }
+
+    #[test]
+    fn installs_into_day_dir() {
+        let (dir, manager) = setup("install");
+        let root = dir.join("repo");
+        let path = manager.install(2022, 1, &root).unwrap();
+        assert_eq!(path, root.join("2022/day1/input.txt"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1000\n2000\n");
+
+        // installing again or over a placeholder is fine, but not over someone
+        // else's input
+        fs::write(&path, "").unwrap();
+        manager.install(2022, 1, &root).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1000\n2000\n");
+        fs::write(&path, "3000\n").unwrap();
+        assert!(matches!(
+            manager.install(2022, 1, &root),
+            Err(FetchError::Conflict(_))
+        ));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "3000\n");
+    }
+
+    #[test]
+    fn throttles_fetches() {
+        let (_, manager) = setup("throttle");
+        let manager = manager.throttle(Duration::from_secs(10));
+        let now = SystemTime::now();
+        assert_eq!(manager.last_fetch(), None);
+        assert_eq!(manager.wait_time(now), Duration::ZERO);
+
+        // timed from when the fetch is stamped, however long it took
+        manager.get(2022, 1).unwrap();
+        let last = manager.last_fetch().unwrap();
+        assert!(last + Duration::from_millis(1) > now);
+        assert_eq!(
+            manager.wait_time(last + Duration::from_secs(4)),
+            Duration::from_secs(6)
+        );
+        assert_eq!(
+            manager.wait_time(last + Duration::from_secs(20)),
+            Duration::ZERO
+        );
+        assert_eq!(
+            manager.wait_time(last - Duration::from_secs(1)),
+            Duration::from_secs(10)
+        );
+    }
+}
@@ -0,0 +1,434 @@
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::ops::{Index, IndexMut};
+
+pub type Point = (usize, usize); // (x, y)
+
+// Up, left, right and down, which is reading order around a point
+pub const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+// All eight points around, in reading order
+pub const SURROUNDING: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridError {
+    // 1-based line number, how wide it was and how wide the first line was
+    Ragged {
+        line: usize,
+        width: usize,
+        expected: usize,
+    },
+    // 1-based line and column of a character that isn't a cell
+    BadCell {
+        line: usize,
+        column: usize,
+        found: char,
+    },
+}
+
+impl Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GridError::Ragged {
+                line,
+                width,
+                expected,
+            } => write!(
+                f,
+                "line {} is {} wide where the grid is {}",
+                line, width, expected
+            ),
+            GridError::BadCell {
+                line,
+                column,
+                found,
+            } => write!(
+                f,
+                "line {}, column {}: {:?} isn't a grid cell",
+                line, column, found
+            ),
+        }
+    }
+}
+
+impl Error for GridError {}
+
+// A rectangle of cells stored row by row, indexed by (x, y) from the top left
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Point) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(&mut f)
+            .collect();
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, GridError> {
+        let width = rows.first().map_or(0, Vec::len);
+        let height = rows.len();
+        let mut cells = Vec::with_capacity(width * height);
+        for (y, row) in rows.into_iter().enumerate() {
+            if row.len() != width {
+                return Err(GridError::Ragged {
+                    line: y + 1,
+                    width: row.len(),
+                    expected: width,
+                });
+            }
+            cells.extend(row);
+        }
+        Ok(Grid {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    // One row per line, one cell per character
+    pub fn parse(text: &str, mut f: impl FnMut(char) -> T) -> Result<Self, GridError> {
+        Self::from_rows(
+            text.lines()
+                .map(|line| line.chars().map(&mut f).collect())
+                .collect(),
+        )
+    }
+
+    // Like parse, for when not every character makes a cell
+    pub fn try_parse(text: &str, mut f: impl FnMut(char) -> Option<T>) -> Result<Self, GridError> {
+        let mut rows = Vec::new();
+        for (y, line) in text.lines().enumerate() {
+            let mut row = Vec::new();
+            for (x, c) in line.chars().enumerate() {
+                row.push(f(c).ok_or(GridError::BadCell {
+                    line: y + 1,
+                    column: x + 1,
+                    found: c,
+                })?);
+            }
+            rows.push(row);
+        }
+        Self::from_rows(rows)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (x, y): Point) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.contains(p)
+            .then(|| &self.cells[p.1 * self.width + p.0])
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        if self.contains(p) {
+            Some(&mut self.cells[p.1 * self.width + p.0])
+        } else {
+            None
+        }
+    }
+
+    // For coordinates that may have gone off the top or left
+    pub fn get_signed(&self, x: isize, y: isize) -> Option<&T> {
+        self.get((usize::try_from(x).ok()?, usize::try_from(y).ok()?))
+    }
+
+    // As if the grid repeated forever in every direction
+    pub fn wrapping(&self, x: isize, y: isize) -> &T {
+        let x = x.rem_euclid(self.width as isize) as usize;
+        let y = y.rem_euclid(self.height as isize) as usize;
+        &self[(x, y)]
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks won't take a size of 0
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        (0..self.height).map(move |y| &self[(x, y)])
+    }
+
+    // Every point in reading order, top to bottom then left to right
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn position(&self, mut f: impl FnMut(&T) -> bool) -> Option<Point> {
+        self.iter().find(|(_, v)| f(v)).map(|(p, _)| p)
+    }
+
+    // The point one step of (dx, dy) away, if that's still on the grid
+    pub fn offset(&self, (x, y): Point, (dx, dy): (isize, isize)) -> Option<Point> {
+        let p = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+        self.contains(p).then_some(p)
+    }
+
+    pub fn neighbours4(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        ORTHOGONAL.iter().filter_map(move |&d| self.offset(p, d))
+    }
+
+    pub fn neighbours8(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        SURROUNDING.iter().filter_map(move |&d| self.offset(p, d))
+    }
+
+    // The points in a line from p, not including p, up to the edge of the grid
+    pub fn ray(&self, p: Point, direction: (isize, isize)) -> impl Iterator<Item = Point> + '_ {
+        std::iter::successors(self.offset(p, direction), move |&q| {
+            self.offset(q, direction)
+        })
+    }
+
+    pub fn map<U>(&self, mut f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(&mut f).collect(),
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    pub fn transpose(&self) -> Self {
+        Self::from_fn(self.height, self.width, |(x, y)| self[(y, x)].clone())
+    }
+
+    // A quarter turn clockwise
+    pub fn rotate_right(&self) -> Self {
+        let height = self.height;
+        Self::from_fn(height, self.width, |(x, y)| {
+            self[(y, height - 1 - x)].clone()
+        })
+    }
+
+    // A quarter turn anticlockwise
+    pub fn rotate_left(&self) -> Self {
+        let width = self.width;
+        Self::from_fn(self.height, width, |(x, y)| {
+            self[(width - 1 - y, x)].clone()
+        })
+    }
+
+    // Mirrored left to right
+    pub fn flip_horizontal(&self) -> Self {
+        let width = self.width;
+        Self::from_fn(width, self.height, |(x, y)| {
+            self[(width - 1 - x, y)].clone()
+        })
+    }
+
+    // Mirrored top to bottom
+    pub fn flip_vertical(&self) -> Self {
+        let height = self.height;
+        Self::from_fn(self.width, height, |(x, y)| {
+            self[(x, height - 1 - y)].clone()
+        })
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point) -> &T {
+        match self.get(p) {
+            Some(v) => v,
+            None => panic!("{:?} is off a {}x{} grid", p, self.width, self.height),
+        }
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        match self.get_mut(p) {
+            Some(v) => v,
+            None => panic!("{:?} is off a {}x{} grid", p, width, height),
+        }
+    }
+}
+
+// Each row on its own line with no newline after the last, so a grid parsed
+// from text renders back to the same text
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "abc\ndef";
+
+    fn letters() -> Grid<char> {
+        Grid::parse(TEXT, |c| c).unwrap()
+    }
+
+    #[test]
+    fn parse_and_render() {
+        let grid = letters();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 0)], 'c');
+        assert_eq!(grid[(0, 1)], 'd');
+        assert_eq!(grid.row(1), ['d', 'e', 'f']);
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(grid.position(|&c| c == 'e'), Some((1, 1)));
+        assert_eq!(grid.to_string(), TEXT);
+        assert_eq!(Grid::parse("", |c| c).unwrap().to_string(), "");
+
+        let digits = Grid::parse("12\n34\n", |c| c.to_digit(10).unwrap()).unwrap();
+        assert_eq!(digits.values().sum::<u32>(), 10);
+        assert_eq!(digits.map(|d| d * 2).to_string(), "24\n68");
+
+        assert_eq!(
+            Grid::parse("ab\nc\n", |c| c),
+            Err(GridError::Ragged {
+                line: 2,
+                width: 1,
+                expected: 2
+            })
+        );
+
+        assert_eq!(
+            Grid::try_parse("12\n34\n", |c| c.to_digit(10)).unwrap(),
+            digits
+        );
+        let error = Grid::try_parse("12\n3x\n", |c| c.to_digit(10)).unwrap_err();
+        assert_eq!(
+            error,
+            GridError::BadCell {
+                line: 2,
+                column: 2,
+                found: 'x'
+            }
+        );
+        assert_eq!(error.to_string(), "line 2, column 2: 'x' isn't a grid cell");
+    }
+
+    #[test]
+    fn bounds() {
+        let mut grid = letters();
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.get_signed(-1, 0), None);
+        assert_eq!(grid.get_signed(1, 1), Some(&'e'));
+        *grid.get_mut((1, 1)).unwrap() = 'E';
+        grid[(0, 0)] = 'A';
+        assert_eq!(grid.to_string(), "Abc\ndEf");
+
+        assert_eq!(*grid.wrapping(3, 2), 'A');
+        assert_eq!(*grid.wrapping(-1, -1), 'f');
+        assert_eq!(*grid.wrapping(7, 1), 'E');
+    }
+
+    #[test]
+    #[should_panic(expected = "(0, 2) is off a 3x2 grid")]
+    fn off_the_grid() {
+        let _ = letters()[(0, 2)];
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = Grid::new(3, 3, 0);
+        assert_eq!(
+            grid.neighbours4((1, 1)).collect::<Vec<_>>(),
+            [(1, 0), (0, 1), (2, 1), (1, 2)]
+        );
+        assert_eq!(
+            grid.neighbours4((0, 0)).collect::<Vec<_>>(),
+            [(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.neighbours8((1, 1)).count(), 8);
+        assert_eq!(
+            grid.neighbours8((2, 2)).collect::<Vec<_>>(),
+            [(1, 1), (2, 1), (1, 2)]
+        );
+        assert_eq!(grid.points().nth(4), Some((1, 1)));
+    }
+
+    #[test]
+    fn rays() {
+        let grid = Grid::new(4, 3, 0);
+        assert_eq!(
+            grid.ray((1, 1), (1, 0)).collect::<Vec<_>>(),
+            [(2, 1), (3, 1)]
+        );
+        assert_eq!(grid.ray((1, 1), (0, -1)).collect::<Vec<_>>(), [(1, 0)]);
+        assert_eq!(
+            grid.ray((0, 0), (1, 1)).collect::<Vec<_>>(),
+            [(1, 1), (2, 2)]
+        );
+        assert_eq!(grid.ray((0, 0), (-1, 1)).count(), 0);
+    }
+
+    #[test]
+    fn transforms() {
+        let grid = letters();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_right().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_left().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.flip_horizontal().to_string(), "cba\nfed");
+        assert_eq!(grid.flip_vertical().to_string(), "def\nabc");
+        assert_eq!(grid.rotate_right().rotate_left(), grid);
+        assert_eq!(
+            grid.rotate_right().rotate_right(),
+            grid.flip_horizontal().flip_vertical()
+        );
+    }
+}
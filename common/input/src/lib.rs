@@ -0,0 +1,165 @@
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+
+// Points at a directory laid out like the repo, YYYY/dayN/, to read inputs
+// from there instead of from each crate
+pub const INPUTS_VAR: &str = "AOC_INPUTS";
+
+#[derive(Debug)]
+pub struct InputError {
+    pub path: PathBuf,
+    pub source: io::Error,
+}
+
+impl InputError {
+    // For a file that was read fine but doesn't make sense
+    pub fn invalid(path: impl AsRef<Path>, error: impl Into<Box<dyn Error + Send + Sync>>) -> Self {
+        InputError {
+            path: path.as_ref().to_path_buf(),
+            source: io::Error::new(io::ErrorKind::InvalidData, error),
+        }
+    }
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.source.kind() {
+            io::ErrorKind::InvalidData => write!(f, "{}: {}", self.path.display(), self.source),
+            _ => write!(f, "unable to read {}: {}", self.path.display(), self.source),
+        }
+    }
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+// The directory a day's inputs are read from, given its crate directory
+pub fn dir(manifest_dir: impl AsRef<Path>) -> PathBuf {
+    match env::var_os(INPUTS_VAR) {
+        Some(root) => under(Path::new(&root), manifest_dir.as_ref()),
+        None => manifest_dir.as_ref().to_path_buf(),
+    }
+}
+
+// The crate's YYYY/dayN moved under another root
+fn under(root: &Path, manifest_dir: &Path) -> PathBuf {
+    let mut components: Vec<_> = manifest_dir.components().rev().take(2).collect();
+    components.reverse();
+    let mut dir = root.to_path_buf();
+    dir.extend(components);
+    dir
+}
+
+pub fn locate(manifest_dir: impl AsRef<Path>, name: &str) -> PathBuf {
+    dir(manifest_dir).join(name)
+}
+
+pub fn open_file(path: impl AsRef<Path>) -> Result<File, InputError> {
+    let path = path.as_ref();
+    File::open(path).map_err(|source| InputError {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+pub fn read_file(path: impl AsRef<Path>) -> Result<String, InputError> {
+    let path = path.as_ref();
+    fs::read_to_string(path).map_err(|source| InputError {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+// These resolve names against the calling crate's directory, so they work
+// whatever directory cargo is run from
+
+#[macro_export]
+macro_rules! path {
+    ($name:expr) => {
+        $crate::locate(env!("CARGO_MANIFEST_DIR"), $name)
+    };
+}
+
+#[macro_export]
+macro_rules! open {
+    ($name:expr) => {
+        $crate::open_file($crate::path!($name))
+    };
+}
+
+#[macro_export]
+macro_rules! read {
+    ($name:expr) => {
+        $crate::read_file($crate::path!($name))
+    };
+}
+
+// Read and then parse with a function returning a Result, so a parse error
+// comes back as an InputError naming the file
+#[macro_export]
+macro_rules! parse {
+    ($name:expr, $parse:expr) => {
+        $crate::read!($name).and_then(|text| {
+            $parse(&text).map_err(|error| $crate::InputError::invalid($crate::path!($name), error))
+        })
+    };
+}
+
+// Built into the binary, so nothing is read at runtime and AOC_INPUTS is
+// ignored
+#[macro_export]
+macro_rules! embed {
+    ($name:literal) => {
+        include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/", $name))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_from_crate() {
+        assert_eq!(
+            path!("Cargo.toml"),
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml")
+        );
+        assert!(read!("Cargo.toml").unwrap().starts_with("[package]"));
+        assert!(embed!("Cargo.toml").starts_with("[package]"));
+    }
+
+    #[test]
+    fn inputs_elsewhere() {
+        assert_eq!(
+            under(Path::new("/tmp/inputs"), Path::new("/src/aoc/2020/day15")),
+            Path::new("/tmp/inputs/2020/day15")
+        );
+    }
+
+    #[test]
+    fn invalid_file() {
+        let number = |text: &str| text.trim().parse::<u32>();
+        let error = parse!("Cargo.toml", number).unwrap_err();
+        assert_eq!(error.source.kind(), io::ErrorKind::InvalidData);
+        assert!(error.path.ends_with("common/input/Cargo.toml"));
+        assert!(error
+            .to_string()
+            .ends_with("Cargo.toml: invalid digit found in string"));
+    }
+
+    #[test]
+    fn missing_file() {
+        let error = read!("nope.txt").unwrap_err();
+        assert!(error.path.ends_with("common/input/nope.txt"));
+        assert_eq!(error.source.kind(), io::ErrorKind::NotFound);
+        assert!(error.to_string().starts_with("unable to read "));
+        assert!(error.to_string().contains("nope.txt"));
+    }
+}
@@ -0,0 +1,316 @@
+// Reads the block-letter messages that some puzzles draw instead of printing an answer
+
+// The 4x6 capital letters, '#' for lit
+const SMALL_FONT: [(char, &[&str]); 18] = [
+    ('A', &[".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', &["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', &[".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', &["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', &["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', &[".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', &["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', &[".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', &["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', &["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', &["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', &[".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', &["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', &["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', &[".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', &["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', &["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', &["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+// The 6x10 capital letters
+const LARGE_FONT: [(char, &[&str]); 15] = [
+    (
+        'A',
+        &[
+            "..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#",
+            "#....#", "#....#",
+        ],
+    ),
+    (
+        'B',
+        &[
+            "#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#",
+            "#....#", "#####.",
+        ],
+    ),
+    (
+        'C',
+        &[
+            ".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....",
+            "#....#", ".####.",
+        ],
+    ),
+    (
+        'E',
+        &[
+            "######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....",
+            "#.....", "######",
+        ],
+    ),
+    (
+        'F',
+        &[
+            "######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....",
+            "#.....", "#.....",
+        ],
+    ),
+    (
+        'G',
+        &[
+            ".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#",
+            "#...##", ".###.#",
+        ],
+    ),
+    (
+        'H',
+        &[
+            "#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#",
+            "#....#", "#....#",
+        ],
+    ),
+    (
+        'J',
+        &[
+            "...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.",
+            "#...#.", ".###..",
+        ],
+    ),
+    (
+        'K',
+        &[
+            "#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..",
+            "#...#.", "#....#",
+        ],
+    ),
+    (
+        'L',
+        &[
+            "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....",
+            "#.....", "######",
+        ],
+    ),
+    (
+        'N',
+        &[
+            "#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##",
+            "#...##", "#....#",
+        ],
+    ),
+    (
+        'P',
+        &[
+            "#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....",
+            "#.....", "#.....",
+        ],
+    ),
+    (
+        'R',
+        &[
+            "#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.",
+            "#....#", "#....#",
+        ],
+    ),
+    (
+        'X',
+        &[
+            "#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.",
+            "#....#", "#....#",
+        ],
+    ),
+    (
+        'Z',
+        &[
+            "######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....",
+            "#.....", "######",
+        ],
+    ),
+];
+
+type Glyph = Vec<Vec<bool>>;
+
+fn to_glyph(pattern: &[&str]) -> Glyph {
+    pattern
+        .iter()
+        .map(|row| row.chars().map(|c| c == '#').collect())
+        .collect()
+}
+
+// Drop the blank columns either side of a glyph
+fn trim_columns(rows: &[Vec<bool>]) -> Glyph {
+    let width = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+    let used: Vec<usize> = (0..width)
+        .filter(|&x| rows.iter().any(|r| r.get(x) == Some(&true)))
+        .collect();
+
+    match (used.first(), used.last()) {
+        (Some(&first), Some(&last)) => rows
+            .iter()
+            .map(|r| (first..=last).map(|x| r.get(x) == Some(&true)).collect())
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+// Drop the blank rows above and below the message
+fn trim_rows(rows: &[Vec<bool>]) -> &[Vec<bool>] {
+    let used: Vec<usize> = (0..rows.len())
+        .filter(|&y| rows[y].iter().any(|&lit| lit))
+        .collect();
+
+    match (used.first(), used.last()) {
+        (Some(&first), Some(&last)) => &rows[first..=last],
+        _ => &[],
+    }
+}
+
+fn recognize(glyph: &[Vec<bool>]) -> char {
+    let font: &[(char, &[&str])] = match glyph.len() {
+        6 => &SMALL_FONT,
+        10 => &LARGE_FONT,
+        _ => return '?',
+    };
+
+    font.iter()
+        .find(|(_, pattern)| trim_columns(&to_glyph(pattern)) == glyph)
+        .map_or('?', |(letter, _)| *letter)
+}
+
+// Letters are told apart by the blank columns between them, anything that doesn't
+// match a letter from the font comes back as '?'
+pub fn read_grid(grid: &[Vec<bool>]) -> String {
+    let lit = trim_rows(grid);
+    let width = lit.iter().map(|r| r.len()).max().unwrap_or(0);
+    let blank = |x: usize| lit.iter().all(|r| r.get(x) != Some(&true));
+
+    let mut message = String::new();
+    let mut x = 0;
+    while x < width {
+        if blank(x) {
+            x += 1;
+            continue;
+        }
+
+        let start = x;
+        while x < width && !blank(x) {
+            x += 1;
+        }
+
+        let glyph: Glyph = lit
+            .iter()
+            .map(|r| (start..x).map(|x| r.get(x) == Some(&true)).collect())
+            .collect();
+        message.push(recognize(&glyph));
+    }
+    message
+}
+
+// Same as `read_grid` for a set of lit (x, y) coordinates, which can sit anywhere
+pub fn read_points<T, I>(points: I) -> String
+where
+    T: Copy + Into<i64>,
+    I: IntoIterator<Item = (T, T)>,
+{
+    let points: Vec<(i64, i64)> = points
+        .into_iter()
+        .map(|(x, y)| (x.into(), y.into()))
+        .collect();
+
+    if points.is_empty() {
+        return String::new();
+    }
+
+    let min_x = points.iter().map(|p| p.0).min().unwrap();
+    let max_x = points.iter().map(|p| p.0).max().unwrap();
+    let min_y = points.iter().map(|p| p.1).min().unwrap();
+    let max_y = points.iter().map(|p| p.1).max().unwrap();
+
+    let mut grid = vec![vec![false; (max_x - min_x + 1) as usize]; (max_y - min_y + 1) as usize];
+    for (x, y) in points {
+        grid[(y - min_y) as usize][(x - min_x) as usize] = true;
+    }
+
+    read_grid(&grid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(rows: &[&str]) -> Glyph {
+        to_glyph(rows)
+    }
+
+    #[test]
+    fn small_letters() {
+        let grid = parse(&[
+            "..............",
+            "#..#.####.###.",
+            "#..#.#....#..#",
+            "####.###..###.",
+            "#..#.#....#..#",
+            "#..#.#....#..#",
+            "#..#.####.###.",
+        ]);
+
+        assert_eq!(read_grid(&grid), "HEB");
+    }
+
+    #[test]
+    fn large_letters() {
+        let grid = parse(&[
+            "#....#..###...",
+            "#....#...#....",
+            "#....#...#....",
+            "#....#...#....",
+            "######...#....",
+            "#....#...#....",
+            "#....#...#....",
+            "#....#...#....",
+            "#....#...#....",
+            "#....#..###...",
+        ]);
+
+        // there's no I in the large font
+        assert_eq!(read_grid(&grid), "H?");
+    }
+
+    #[test]
+    fn every_letter() {
+        for font in [&SMALL_FONT[..], &LARGE_FONT[..]] {
+            let height = font[0].1.len();
+            let mut grid = vec![Vec::new(); height];
+            let mut expected = String::new();
+
+            for (letter, pattern) in font {
+                for (row, line) in grid.iter_mut().zip(pattern.iter()) {
+                    row.extend(line.chars().map(|c| c == '#'));
+                    row.extend([false, false]);
+                }
+                expected.push(*letter);
+            }
+
+            assert_eq!(read_grid(&grid), expected);
+        }
+    }
+
+    #[test]
+    fn points() {
+        // a Z drawn with negative coordinates
+        let mut points = Vec::new();
+        for (y, row) in SMALL_FONT[17].1.iter().enumerate() {
+            for (x, c) in row.chars().enumerate() {
+                if c == '#' {
+                    points.push((x as i32 - 10, y as i32 - 3));
+                }
+            }
+        }
+
+        assert_eq!(read_points(points), "Z");
+        assert_eq!(read_points(Vec::<(i32, i32)>::new()), "");
+    }
+}
@@ -0,0 +1,245 @@
+use nom::branch::alt;
+use nom::bytes::complete::{tag, take_till1};
+use nom::character::complete::{char, digit1, line_ending, multispace0, one_of, space0, space1};
+use nom::combinator::{cut, eof, map_res, not, opt, peek, recognize, value};
+use nom::error::{context, VerboseError, VerboseErrorKind};
+use nom::multi::{many1, separated_list1};
+use nom::sequence::{pair, separated_pair, terminated, tuple};
+use nom::{Finish, IResult, Parser};
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+// So days can write their own parsers against the same version
+pub use nom;
+
+pub type Parsed<'a, T> = IResult<&'a str, T, VerboseError<&'a str>>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    // Both 1-based, with the column counted in characters
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+    // The rest of the line from where it went wrong
+    pub found: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, found ",
+            self.line, self.column, self.expected
+        )?;
+        match self.found.as_str() {
+            "" => write!(f, "the end of the line"),
+            found => write!(f, "{:?}", found),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+fn describe(kind: &VerboseErrorKind) -> String {
+    match kind {
+        VerboseErrorKind::Context(context) => context.to_string(),
+        VerboseErrorKind::Char(c) => format!("{:?}", c),
+        VerboseErrorKind::Nom(kind) => kind.description().to_lowercase(),
+    }
+}
+
+// Blame the innermost context if there is one, as it says what was being
+// parsed, or else whatever failed first
+fn locate(text: &str, error: &VerboseError<&str>) -> ParseError {
+    let (rest, kind) = error
+        .errors
+        .iter()
+        .find(|(_, kind)| matches!(kind, VerboseErrorKind::Context(_)))
+        .unwrap_or(&error.errors[0]);
+
+    let before = &text[..text.len() - rest.len()];
+    ParseError {
+        line: before.matches('\n').count() + 1,
+        column: before.rsplit('\n').next().unwrap().chars().count() + 1,
+        expected: describe(kind),
+        found: rest.lines().next().unwrap_or("").to_string(),
+    }
+}
+
+// Parse the whole of text, leaving nothing but whitespace at the end
+pub fn parse_all<'a, T>(
+    text: &'a str,
+    parser: impl Parser<&'a str, T, VerboseError<&'a str>>,
+) -> Result<T, ParseError> {
+    terminated(parser, pair(multispace0, context("end of input", eof)))
+        .parse(text)
+        .finish()
+        .map(|(_, parsed)| parsed)
+        .map_err(|error| locate(text, &error))
+}
+
+// A whole number with an optional sign
+pub fn integer<T: FromStr>(input: &str) -> Parsed<'_, T> {
+    context(
+        "integer",
+        map_res(recognize(pair(opt(one_of("+-")), digit1)), str::parse),
+    )(input)
+}
+
+// Integers with a separator between each, like "3,-1,4" or "3 -> -1 -> 4"
+pub fn integers<'a, T: FromStr>(
+    separator: &'static str,
+) -> impl FnMut(&'a str) -> Parsed<'a, Vec<T>> {
+    separated_list1(tuple((space0, tag(separator), space0)), integer)
+}
+
+// A run of anything but whitespace
+pub fn word(input: &str) -> Parsed<'_, &str> {
+    context("word", take_till1(char::is_whitespace))(input)
+}
+
+// Looks for the end of a line without using it up
+fn end_of_line(input: &str) -> Parsed<'_, ()> {
+    context("end of line", peek(value((), alt((line_ending, eof)))))(input)
+}
+
+// A line break with another line after it
+fn line_break(input: &str) -> Parsed<'_, ()> {
+    value((), terminated(line_ending, not(alt((line_ending, eof)))))(input)
+}
+
+// One or more blank lines with more to come after them
+pub fn blank_lines(input: &str) -> Parsed<'_, ()> {
+    value((), tuple((line_ending, many1(line_ending), not(eof))))(input)
+}
+
+// One item per line.  Once a line is started it has to parse right to its end,
+// so a mistake is reported where it is and not as the end of the list.  A blank
+// line ends the list.
+pub fn lines<'a, T>(
+    item: impl Parser<&'a str, T, VerboseError<&'a str>>,
+) -> impl FnMut(&'a str) -> Parsed<'a, Vec<T>> {
+    separated_list1(line_break, cut(terminated(item, end_of_line)))
+}
+
+// Groups of lines with blank lines between them
+pub fn blocks<'a, T>(
+    block: impl Parser<&'a str, T, VerboseError<&'a str>>,
+) -> impl FnMut(&'a str) -> Parsed<'a, Vec<T>> {
+    separated_list1(blank_lines, cut(block))
+}
+
+// "key: value", where the key is everything up to the colon
+pub fn key_value<'a, T>(
+    item: impl Parser<&'a str, T, VerboseError<&'a str>>,
+) -> impl FnMut(&'a str) -> Parsed<'a, (&'a str, T)> {
+    let key = context("key", take_till1(|c| c == ':' || c == '\n' || c == '\r'));
+    separated_pair(key, pair(char(':'), space0), item)
+}
+
+// "key:value" fields split up by spaces or line breaks, like
+// "ecl:gry pid:860033327\nhcl:#fffffd".  Keys can't have spaces in them.
+pub fn record<'a, T>(
+    item: impl Parser<&'a str, T, VerboseError<&'a str>>,
+) -> impl FnMut(&'a str) -> Parsed<'a, Vec<(&'a str, T)>> {
+    let key = context("key", take_till1(|c: char| c == ':' || c.is_whitespace()));
+    let field = separated_pair(key, char(':'), item);
+    separated_list1(alt((value((), space1), line_break)), cut(field))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers() {
+        assert_eq!(parse_all("-12", integer::<i32>), Ok(-12));
+        assert_eq!(parse_all("+7\n", integer::<u8>), Ok(7));
+        assert_eq!(
+            parse_all("3,-1, 4", integers::<i64>(",")),
+            Ok(vec![3, -1, 4])
+        );
+        assert_eq!(parse_all("1 -> 2", integers::<i64>("->")), Ok(vec![1, 2]));
+
+        let error = parse_all("-3", integer::<u32>).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 1));
+        assert_eq!(error.expected, "integer");
+        assert_eq!(
+            parse_all("300", integer::<u8>).unwrap_err().to_string(),
+            "line 1, column 1: expected integer, found \"300\""
+        );
+    }
+
+    #[test]
+    fn line_by_line() {
+        let text = "1,2\n3,4\n5,6\n";
+        assert_eq!(
+            parse_all(text, lines(integers::<i32>(","))),
+            Ok(vec![vec![1, 2], vec![3, 4], vec![5, 6]])
+        );
+
+        // the mistake, not the end of the last line that parsed
+        let error = parse_all("1,2\n3,x\n5,6", lines(integers::<i32>(","))).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 2: expected end of line, found \",x\""
+        );
+
+        let error = parse_all("1,2\n3,4\n5,6 and", lines(integers::<i32>(","))).unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.expected.as_str()),
+            (3, 4, "end of line")
+        );
+
+        let error = parse_all("1\n\n2", lines(integer::<i32>)).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 3, column 1: expected end of input, found \"2\""
+        );
+    }
+
+    #[test]
+    fn grouped() {
+        let text = "1\n2\n\n3\n\n\n4\n5\n\n";
+        assert_eq!(
+            parse_all(text, blocks(lines(integer::<u32>))),
+            Ok(vec![vec![1, 2], vec![3], vec![4, 5]])
+        );
+
+        let error = parse_all("1\n\n2\nthree", blocks(lines(integer::<u32>))).unwrap_err();
+        assert_eq!((error.line, error.column), (4, 1));
+        assert_eq!(error.found, "three");
+    }
+
+    #[test]
+    fn fields() {
+        assert_eq!(
+            parse_all("class: 1-3", key_value(word)),
+            Ok(("class", "1-3"))
+        );
+        assert_eq!(
+            parse_all("departure time:12", key_value(integer::<u32>)),
+            Ok(("departure time", 12))
+        );
+
+        let passport = "ecl:gry pid:860033327\nhcl:#fffffd";
+        assert_eq!(
+            parse_all(passport, record(word)),
+            Ok(vec![
+                ("ecl", "gry"),
+                ("pid", "860033327"),
+                ("hcl", "#fffffd")
+            ])
+        );
+
+        let passports = "ecl:gry byr:1937\niyr:2017\n\nhcl:#cfa07d";
+        let parsed = parse_all(passports, blocks(record(word))).unwrap();
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0][2], ("iyr", "2017"));
+
+        let error = parse_all("ecl:gry\nbyr 1937", record(word)).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 4));
+    }
+}
@@ -0,0 +1,398 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+// What a search found out: the cheapest known way to every node it reached,
+// the goal if it stopped at one, and how much work it took to get there
+#[derive(Debug, Clone)]
+pub struct Search<N, C> {
+    pub goal: Option<N>,
+    // Distinct nodes reached, and nodes whose successors were looked at
+    pub visited: usize,
+    pub expanded: usize,
+    // The cost of the best way found to each node and the node before it
+    reached: HashMap<N, (C, Option<N>)>,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy + Ord> Search<N, C> {
+    fn new() -> Self {
+        Search {
+            goal: None,
+            visited: 0,
+            expanded: 0,
+            reached: HashMap::new(),
+        }
+    }
+
+    // Whether this is a better way to node than any found so far
+    fn reach(&mut self, node: N, cost: C, parent: Option<N>) -> bool {
+        match self.reached.get(&node) {
+            Some(&(known, _)) if known <= cost => false,
+            _ => {
+                if self.reached.insert(node, (cost, parent)).is_none() {
+                    self.visited += 1;
+                }
+                true
+            }
+        }
+    }
+
+    pub fn cost(&self, node: &N) -> Option<C> {
+        self.reached.get(node).map(|&(cost, _)| cost)
+    }
+
+    pub fn goal_cost(&self) -> Option<C> {
+        self.cost(self.goal.as_ref()?)
+    }
+
+    // From whichever start it was reached from up to and including node
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        let mut path = vec![node.clone()];
+        let mut parent = &self.reached.get(node)?.1;
+        while let Some(previous) = parent {
+            path.push(previous.clone());
+            parent = &self.reached[previous].1;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    pub fn path(&self) -> Option<Vec<N>> {
+        self.path_to(self.goal.as_ref()?)
+    }
+
+    pub fn reached(&self) -> impl Iterator<Item = &N> {
+        self.reached.keys()
+    }
+}
+
+// Breadth first from every start at once, stopping at the first node that's a
+// goal.  Every step costs 1.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Search<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut search = Search::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if search.reach(start.clone(), 0, None) {
+            queue.push_back(start);
+        }
+    }
+
+    while let Some(node) = queue.pop_front() {
+        if goal(&node) {
+            search.goal = Some(node);
+            break;
+        }
+
+        search.expanded += 1;
+        let cost = search.reached[&node].0 + 1;
+        for next in successors(&node) {
+            if search.reach(next.clone(), cost, Some(node.clone())) {
+                queue.push_back(next);
+            }
+        }
+    }
+    search
+}
+
+// Everything reachable from the starts
+pub fn flood_fill<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+) -> Search<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    bfs(starts, successors, |_| false)
+}
+
+pub fn dijkstra<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+    goal: impl FnMut(&N) -> bool,
+) -> Search<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(starts, successors, |_| C::default(), goal)
+}
+
+// Successors come with the cost of the step to them, and the default cost is
+// zero.  The heuristic must never overestimate the cost left to a goal, or the
+// path found may not be the cheapest.
+pub fn astar<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Search<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut search = Search::new();
+    let mut heap = BinaryHeap::new();
+    for start in starts {
+        if search.reach(start.clone(), C::default(), None) {
+            heap.push(Entry {
+                estimate: heuristic(&start),
+                cost: C::default(),
+                node: start,
+            });
+        }
+    }
+
+    while let Some(Entry { cost, node, .. }) = heap.pop() {
+        // A cheaper way here turned up after this one was queued
+        if cost > search.reached[&node].0 {
+            continue;
+        }
+        if goal(&node) {
+            search.goal = Some(node);
+            break;
+        }
+
+        search.expanded += 1;
+        for (next, step) in successors(&node) {
+            let cost = cost + step;
+            if search.reach(next.clone(), cost, Some(node.clone())) {
+                heap.push(Entry {
+                    estimate: cost + heuristic(&next),
+                    cost,
+                    node: next,
+                });
+            }
+        }
+    }
+    search
+}
+
+struct Entry<N, C> {
+    estimate: C,
+    cost: C,
+    node: N,
+}
+
+// BinaryHeap is a max-heap, so the lowest estimate has to compare greatest.
+// Ties go to whichever has come furthest, as it's likely nearer a goal.
+impl<N, C: Ord> Ord for Entry<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .estimate
+            .cmp(&self.estimate)
+            .then_with(|| self.cost.cmp(&other.cost))
+    }
+}
+
+impl<N, C: Ord> PartialOrd for Entry<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> PartialEq for Entry<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<N, C: Ord> Eq for Entry<N, C> {}
+
+// How many different paths lead from start to a goal, each ending at the first
+// goal it comes to.  Counts are shared between paths through the same node, so
+// the nodes can't lead back round to themselves.
+pub fn count_paths<N, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut goal: impl FnMut(&N) -> bool,
+) -> usize
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut counts = HashMap::new();
+    count_from(&start, &mut successors, &mut goal, &mut counts)
+}
+
+// A node is counted as None while the paths out of it are still being counted
+fn count_from<N, I, S, G>(
+    node: &N,
+    successors: &mut S,
+    goal: &mut G,
+    counts: &mut HashMap<N, Option<usize>>,
+) -> usize
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+    S: FnMut(&N) -> I,
+    G: FnMut(&N) -> bool,
+{
+    if goal(node) {
+        return 1;
+    }
+    match counts.get(node) {
+        Some(Some(count)) => return *count,
+        Some(None) => panic!("The paths loop, so there are infinitely many"),
+        None => (),
+    }
+
+    counts.insert(node.clone(), None);
+    let nexts: Vec<N> = successors(node).into_iter().collect();
+    let count = nexts
+        .iter()
+        .map(|next| count_from(next, successors, goal, counts))
+        .sum();
+    counts.insert(node.clone(), Some(count));
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Point = (i32, i32);
+
+    // # are walls, and so is anywhere off the maze
+    const MAZE: [&str; 6] = [
+        "S.#......",
+        ".##.###.#",
+        "....#...#",
+        "####..#.#",
+        "#..#.##..",
+        "#..#....G",
+    ];
+
+    fn moves(&(x, y): &Point) -> Vec<Point> {
+        [(x, y - 1), (x - 1, y), (x + 1, y), (x, y + 1)]
+            .into_iter()
+            .filter(|&(x, y)| {
+                MAZE.get(y as usize)
+                    .and_then(|row| row.as_bytes().get(x as usize))
+                    .is_some_and(|&c| c != b'#')
+            })
+            .collect()
+    }
+
+    fn is_goal(p: &Point) -> bool {
+        *p == (8, 5)
+    }
+
+    #[test]
+    fn breadth_first() {
+        let search = bfs([(0, 0)], moves, is_goal);
+        assert_eq!(search.goal, Some((8, 5)));
+        assert_eq!(search.goal_cost(), Some(17));
+
+        let path = search.path().unwrap();
+        assert_eq!(path.len(), 18);
+        assert_eq!(path[0], (0, 0));
+        assert!(path.windows(2).all(|w| moves(&w[0]).contains(&w[1])));
+        assert!(search.expanded < search.visited);
+        assert_eq!(search.path_to(&(3, 0)).unwrap().len(), 8);
+
+        // nothing gets out of the walled in corner
+        let search = bfs([(1, 4)], moves, is_goal);
+        assert_eq!(search.goal, None);
+        assert_eq!(search.path(), None);
+        assert_eq!(search.cost(&(2, 5)), Some(2));
+    }
+
+    #[test]
+    fn several_starts() {
+        let search = bfs([(0, 0), (7, 0)], moves, is_goal);
+        assert_eq!(search.goal_cost(), Some(6));
+        assert_eq!(search.path().unwrap()[0], (7, 0));
+        assert_eq!(search.cost(&(0, 0)), Some(0));
+
+        // starting on a goal
+        let search = bfs([(8, 5)], moves, is_goal);
+        assert_eq!(search.path(), Some(vec![(8, 5)]));
+        assert_eq!(search.expanded, 0);
+    }
+
+    #[test]
+    fn flood() {
+        let search = flood_fill([(1, 4)], moves);
+        assert_eq!(search.visited, 4);
+        assert_eq!(search.reached().count(), 4);
+        assert_eq!(search.expanded, 4);
+
+        assert_eq!(flood_fill([(0, 0)], moves).visited, 29);
+    }
+
+    #[test]
+    fn weighted() {
+        // the direct way has fewer steps but costs more
+        let edges = |node: &char| match node {
+            'a' => vec![('b', 1), ('c', 2)],
+            'b' => vec![('d', 5)],
+            'c' => vec![('d', 1)],
+            _ => vec![],
+        };
+        assert_eq!(
+            bfs(
+                ['a'],
+                |n| edges(n).into_iter().map(|(m, _)| m),
+                |&n| n == 'd'
+            )
+            .path(),
+            Some(vec!['a', 'b', 'd'])
+        );
+
+        let search = dijkstra(['a'], edges, |&n| n == 'd');
+        assert_eq!(search.goal_cost(), Some(3));
+        assert_eq!(search.path(), Some(vec!['a', 'c', 'd']));
+
+        let search = dijkstra(['a'], edges, |_| false);
+        assert_eq!(search.cost(&'b'), Some(1));
+        assert_eq!(search.cost(&'d'), Some(3));
+        assert_eq!(search.visited, 4);
+    }
+
+    #[test]
+    fn a_star() {
+        let distance = |&(x, y): &Point| (8 - x).abs() + (5 - y).abs();
+        let steps = |p: &Point| moves(p).into_iter().map(|q| (q, 1));
+
+        let informed = astar([(0, 0)], steps, distance, is_goal);
+        let uninformed = dijkstra([(0, 0)], steps, is_goal);
+        assert_eq!(informed.goal_cost(), Some(17));
+        assert_eq!(uninformed.goal_cost(), Some(17));
+        assert_eq!(informed.path().unwrap().len(), 18);
+        assert!(informed.expanded < uninformed.expanded);
+    }
+
+    #[test]
+    fn counting_paths() {
+        // right or down across a 2x2 square of cells
+        let lattice = |&(x, y): &Point| {
+            [(x + 1, y), (x, y + 1)]
+                .into_iter()
+                .filter(|&(x, y)| x <= 2 && y <= 2)
+        };
+        assert_eq!(count_paths((0, 0), lattice, |&p| p == (2, 2)), 6);
+        assert_eq!(count_paths((2, 0), lattice, |&p| p == (2, 2)), 1);
+        assert_eq!(count_paths((0, 0), lattice, |&p| p == (3, 3)), 0);
+
+        // paths stop at the first goal
+        assert_eq!(count_paths((0, 0), lattice, |&(x, y)| x + y == 1), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "infinitely many")]
+    fn looping_paths() {
+        count_paths((0, 0), moves, is_goal);
+    }
+}
@@ -0,0 +1,192 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::time::{Duration, Instant};
+
+// Bad input is an error saying what's wrong with it, rather than a panic
+pub type ParseResult<T> = Result<T, Box<dyn Error + Send + Sync>>;
+
+// As is input that parses but that a part can't find an answer in
+pub type PartResult<T> = Result<T, Box<dyn Error + Send + Sync>>;
+
+// A day's puzzle.  The input is parsed once and both parts answer from that.
+pub trait Solution {
+    type Input;
+
+    fn parse(input: &str) -> ParseResult<Self::Input>;
+    fn part1(input: &Self::Input) -> PartResult<impl Display>;
+    fn part2(input: &Self::Input) -> PartResult<impl Display>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Answer {
+    pub part: Part,
+    // The answer, or why there isn't one
+    pub value: Result<String, String>,
+    pub time: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Run {
+    pub parse_time: Duration,
+    pub answers: Vec<Answer>,
+}
+
+// Parse the input and answer the parts asked for, timing each step.  If the
+// input doesn't parse there's nothing to run, while a part that fails still
+// leaves the other to answer.
+pub fn run<S: Solution>(input: &str, parts: &[Part]) -> ParseResult<Run> {
+    let start = Instant::now();
+    let parsed = S::parse(input)?;
+    let parse_time = start.elapsed();
+
+    let answers = parts
+        .iter()
+        .map(|&part| {
+            let start = Instant::now();
+            let value = match part {
+                Part::One => S::part1(&parsed).map(|answer| answer.to_string()),
+                Part::Two => S::part2(&parsed).map(|answer| answer.to_string()),
+            }
+            .map_err(|error| error.to_string());
+            Answer {
+                part,
+                value,
+                time: start.elapsed(),
+            }
+        })
+        .collect();
+
+    Ok(Run {
+        parse_time,
+        answers,
+    })
+}
+
+// `run` for one solution with its input type erased, so different days can sit
+// side by side
+pub type Runner = fn(&str, &[Part]) -> ParseResult<Run>;
+
+// Every solved day, keyed by (year, day)
+#[derive(Default)]
+pub struct Registry {
+    days: BTreeMap<(u32, u32), Runner>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Registry::default()
+    }
+
+    pub fn register<S: Solution>(&mut self, year: u32, day: u32) -> &mut Self {
+        let previous = self.days.insert((year, day), run::<S>);
+        assert!(previous.is_none(), "{} day {} registered twice", year, day);
+        self
+    }
+
+    pub fn get(&self, year: u32, day: u32) -> Option<Runner> {
+        self.days.get(&(year, day)).copied()
+    }
+
+    // In order of year then day
+    pub fn days(&self) -> impl Iterator<Item = (u32, u32)> + '_ {
+        self.days.keys().copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Input = Vec<i32>;
+
+        fn parse(input: &str) -> ParseResult<Vec<i32>> {
+            Ok(input
+                .split(',')
+                .map(|n| n.trim().parse())
+                .collect::<Result<_, _>>()?)
+        }
+
+        fn part1(input: &Vec<i32>) -> PartResult<impl Display> {
+            Ok(input.iter().sum::<i32>())
+        }
+
+        fn part2(input: &Vec<i32>) -> PartResult<impl Display> {
+            match input.len() {
+                1 => Err("only one number".into()),
+                n => Ok(format!("{} numbers", n)),
+            }
+        }
+    }
+
+    #[test]
+    fn runs_parts() {
+        let runner: Runner = run::<Sum>;
+        let values: Vec<(Part, Result<String, String>)> = runner("1,2,3", &[Part::Two, Part::One])
+            .unwrap()
+            .answers
+            .into_iter()
+            .map(|answer| (answer.part, answer.value))
+            .collect();
+        assert_eq!(
+            values,
+            vec![
+                (Part::Two, Ok("3 numbers".to_string())),
+                (Part::One, Ok("6".to_string()))
+            ]
+        );
+    }
+
+    #[test]
+    fn failed_part() {
+        let run = run::<Sum>("7", &[Part::One, Part::Two]).unwrap();
+        assert_eq!(run.answers[0].value, Ok("7".to_string()));
+        assert_eq!(run.answers[1].value, Err("only one number".to_string()));
+    }
+
+    #[test]
+    fn bad_input() {
+        let error = run::<Sum>("1,x", &[Part::One]).unwrap_err();
+        assert_eq!(error.to_string(), "invalid digit found in string");
+    }
+
+    #[test]
+    fn registry() {
+        let mut registry = Registry::new();
+        registry.register::<Sum>(2020, 2).register::<Sum>(2019, 25);
+
+        assert_eq!(
+            registry.days().collect::<Vec<_>>(),
+            vec![(2019, 25), (2020, 2)]
+        );
+        assert!(registry.get(2020, 1).is_none());
+        let run = registry.get(2020, 2).unwrap()("4,5", &[Part::One]).unwrap();
+        assert_eq!(run.answers[0].value, Ok("9".to_string()));
+    }
+
+    #[test]
+    #[should_panic(expected = "2020 day 2 registered twice")]
+    fn registered_twice() {
+        Registry::new()
+            .register::<Sum>(2020, 2)
+            .register::<Sum>(2020, 2);
+    }
+}